@@ -0,0 +1,461 @@
+//! 命令行子命令
+//!
+//! 不带子命令时等同于 `serve`。其余子命令直接操作数据库，供运维在无法登录后台时
+//! 初始化系统、重置密码、解锁账号等，无需手写 SQL。
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Args, Parser, Subcommand};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use validator::Validate;
+
+use crate::config::Config;
+use crate::dto::{
+    CreateAdminRequest, RbacChangeAction, RbacDocument, RbacEntryKind, RbacImportMode,
+    RbacImportReport,
+};
+use crate::entities::{prelude::Roles, roles};
+use crate::error::GuardianError;
+use crate::migration::{migration_status, run_migrations};
+use crate::router::{AppState, build_state, connect_database};
+use crate::service::admin_service::{
+    create_admin_service, disable_admin_2fa_service, find_admin_by_username,
+    reset_admin_password_service, unlock_admin_service,
+};
+use crate::service::init::{find_super_admin, init_system};
+use crate::service::password_policy_service::PasswordPolicy;
+use crate::service::rbac_service::{export_rbac, import_rbac, parse_document};
+use crate::utils::hash_password;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Guardian 后台权限服务")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 启动 HTTP 服务（默认）
+    Serve,
+    /// 执行数据库迁移
+    Migrate {
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+    /// 创建首个超级管理员，已存在超级管理员时不做修改
+    Init {
+        /// 超级管理员用户名
+        #[arg(long, default_value = "guardian")]
+        username: String,
+        /// 同时以 create 模式导入的 RBAC 定义文件（YAML 或 JSON）
+        #[arg(long, value_name = "FILE")]
+        rbac: Option<PathBuf>,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+    /// 管理员账号运维
+    Admin {
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+    /// 权限与角色的声明式导入导出
+    #[command(alias = "role")]
+    Rbac {
+        #[command(subcommand)]
+        action: RbacAction,
+    },
+    /// 计算密码的 Argon2 哈希，用于手工写库或核对
+    HashPassword {
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateAction {
+    /// 只列出各迁移的执行状态
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AdminAction {
+    /// 创建管理员
+    Create {
+        username: String,
+        #[arg(long)]
+        email: Option<String>,
+        /// 创建为超级管理员
+        #[arg(long)]
+        super_admin: bool,
+        /// 分配的角色代码，可重复指定
+        #[arg(long = "role", value_name = "CODE")]
+        roles: Vec<String>,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+    /// 重置密码，同时解除锁定并使已签发的令牌失效
+    ResetPassword {
+        username: String,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+    /// 清除登录失败次数和锁定状态
+    Unlock { username: String },
+    /// 关闭两步验证并删除备用码
+    #[command(name = "disable-2fa")]
+    Disable2fa { username: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RbacAction {
+    /// 导出全部权限和角色
+    Export {
+        /// 输出文件，不指定时写到标准输出
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// json 或 yaml，默认按输出文件扩展名判断，标准输出默认 yaml
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// 导入 YAML 或 JSON 定义文件，按代码新增或更新，系统内置条目只读
+    Import {
+        file: PathBuf,
+        /// create 只新增；update 同时更新已有条目；prune 再删除文件中没有的非内置条目
+        #[arg(long, default_value = "update")]
+        mode: RbacImportMode,
+        /// 只列出变更，不写入数据库
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct PasswordArgs {
+    /// 从标准输入读取一行作为密码，便于脚本调用
+    #[arg(long, conflicts_with = "generate")]
+    password_stdin: bool,
+    /// 随机生成符合密码策略的密码并打印，首次登录后须修改
+    #[arg(long)]
+    generate: bool,
+}
+
+impl PasswordArgs {
+    /// 返回密码及是否为随机生成；两个参数都未指定时在终端提示输入两次
+    fn read(&self, policy: &PasswordPolicy) -> Result<(String, bool)> {
+        if self.generate {
+            return Ok((policy.generate(), true));
+        }
+
+        let password = if self.password_stdin {
+            let mut line = String::new();
+            std::io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .context("读取标准输入失败")?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        } else {
+            let password = rpassword::prompt_password("密码：").context("读取密码失败")?;
+            let confirm = rpassword::prompt_password("确认密码：").context("读取密码失败")?;
+            if password != confirm {
+                bail!("两次输入的密码不一致");
+            }
+            password
+        };
+
+        if password.is_empty() {
+            bail!("密码不能为空");
+        }
+        Ok((password, false))
+    }
+}
+
+/// 执行 `serve` 以外的子命令
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Serve => unreachable!("serve 由 main 直接处理"),
+        Command::Migrate { action } => migrate(action.is_some()).await,
+        Command::Init {
+            username,
+            rbac,
+            password,
+        } => {
+            let state = connect().await?;
+            if let Some(file) = rbac {
+                let report =
+                    import_rbac(&state, read_document(&file)?, RbacImportMode::Create, false)
+                        .await?;
+                print_report(&report);
+            }
+
+            if let Some(admin) = find_super_admin(&state).await? {
+                println!("系统已初始化（超级管理员：{}）", admin.username);
+                return Ok(());
+            }
+
+            let (password, generated) = password.read(&state.config.password_policy)?;
+            println!(
+                "{}",
+                init_system(state, &username, &password, generated).await?
+            );
+            if generated {
+                print_generated(&username, &password);
+            }
+            Ok(())
+        }
+        Command::Admin { action } => admin(action).await,
+        Command::Rbac { action } => rbac(action).await,
+        Command::HashPassword { password } => {
+            let config = Config::load_offline()?;
+            let (password, generated) = password.read(&config.password_policy)?;
+            if generated {
+                eprintln!("生成的密码：{}", password);
+            }
+            println!("{}", hash_password(&config.password_hash, &password));
+            Ok(())
+        }
+    }
+}
+
+async fn connect() -> Result<AppState> {
+    build_state(Config::load()?).await
+}
+
+fn print_generated(username: &str, password: &str) {
+    println!("用户名：{}", username);
+    println!("密码：{}", password);
+    println!("请妥善保存，首次登录后需修改密码");
+}
+
+/// 执行未执行的数据库迁移，`status_only` 时只列出各迁移的执行状态
+async fn migrate(status_only: bool) -> Result<()> {
+    let config = Config::load()?;
+    let conn = connect_database(&config.database).await?;
+    if !status_only {
+        run_migrations(&conn).await?;
+    }
+
+    for (name, applied) in migration_status(&conn).await? {
+        println!(
+            "{} {}",
+            if applied {
+                "[已执行]"
+            } else {
+                "[未执行]"
+            },
+            name
+        );
+    }
+    Ok(())
+}
+
+async fn admin(action: AdminAction) -> Result<()> {
+    let state = connect().await?;
+
+    let msg = match action {
+        AdminAction::Create {
+            username,
+            email,
+            super_admin,
+            roles,
+            password,
+        } => {
+            let (password, generated) = password.read(&state.config.password_policy)?;
+            let role_ids = resolve_roles(&state, &roles).await?;
+            let payload = CreateAdminRequest {
+                username: username.clone(),
+                email,
+                password: password.clone(),
+                is_super_admin: Some(super_admin),
+                role_ids: (!role_ids.is_empty()).then_some(role_ids),
+                locale: None,
+            };
+            payload.validate().map_err(GuardianError::from)?;
+            let msg = create_admin_service(state, payload).await?.msg;
+            if generated {
+                print_generated(&username, &password);
+            }
+            msg
+        }
+        AdminAction::ResetPassword { username, password } => {
+            let (password, generated) = password.read(&state.config.password_policy)?;
+            let admin = find_admin_by_username(&state, &username).await?;
+            let msg = reset_admin_password_service(state, admin.id, &password, generated)
+                .await?
+                .msg;
+            if generated {
+                print_generated(&username, &password);
+            }
+            msg
+        }
+        AdminAction::Unlock { username } => {
+            let admin = find_admin_by_username(&state, &username).await?;
+            unlock_admin_service(state, admin.id).await?.msg
+        }
+        AdminAction::Disable2fa { username } => {
+            let admin = find_admin_by_username(&state, &username).await?;
+            disable_admin_2fa_service(state, admin.id).await?.msg
+        }
+    };
+
+    if let Some(msg) = msg {
+        eprintln!("{}", msg);
+    }
+    Ok(())
+}
+
+async fn resolve_roles(state: &AppState, codes: &[String]) -> Result<Vec<uuid::Uuid>> {
+    let mut ids = Vec::with_capacity(codes.len());
+    for code in codes {
+        let role = Roles::find()
+            .filter(roles::Column::Code.eq(code))
+            .one(&state.conn)
+            .await?
+            .ok_or_else(|| anyhow!("角色不存在：{}", code))?;
+        ids.push(role.id);
+    }
+    Ok(ids)
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+}
+
+fn read_document(file: &Path) -> Result<RbacDocument> {
+    let content =
+        std::fs::read_to_string(file).with_context(|| format!("读取 {} 失败", file.display()))?;
+    parse_document(&content, is_yaml(file)).with_context(|| format!("解析 {} 失败", file.display()))
+}
+
+fn print_report(report: &RbacImportReport) {
+    for change in &report.changes {
+        let (sign, note) = match change.action {
+            RbacChangeAction::Create => ("+", ""),
+            RbacChangeAction::Update => ("~", ""),
+            RbacChangeAction::Delete => ("-", ""),
+            RbacChangeAction::Skip => ("!", "（系统内置，跳过）"),
+        };
+        let kind = match change.kind {
+            RbacEntryKind::Permission => "权限",
+            RbacEntryKind::Role => "角色",
+        };
+        println!("{} {} {}{}", sign, kind, change.code, note);
+        for detail in &change.details {
+            println!("    {}", detail);
+        }
+    }
+    println!(
+        "{}新增 {}，更新 {}，删除 {}，跳过 {}",
+        if report.dry_run { "[预览] " } else { "" },
+        report.created,
+        report.updated,
+        report.deleted,
+        report.skipped
+    );
+}
+
+async fn rbac(action: RbacAction) -> Result<()> {
+    let state = connect().await?;
+
+    match action {
+        RbacAction::Export { output, format } => {
+            let document = export_rbac(&state).await?;
+            let yaml = match format.as_deref() {
+                Some("yaml") | Some("yml") => true,
+                Some("json") => false,
+                Some(other) => bail!("未知的格式: {}（可选 json、yaml）", other),
+                None => output.as_deref().is_none_or(is_yaml),
+            };
+            let content = if yaml {
+                serde_yaml::to_string(&document)?
+            } else {
+                serde_json::to_string_pretty(&document)? + "\n"
+            };
+            match output {
+                Some(path) => std::fs::write(&path, content)
+                    .with_context(|| format!("写入 {} 失败", path.display()))?,
+                None => write!(std::io::stdout(), "{}", content)?,
+            }
+        }
+        RbacAction::Import {
+            file,
+            mode,
+            dry_run,
+        } => {
+            let report = import_rbac(&state, read_document(&file)?, mode, dry_run).await?;
+            print_report(&report);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_parse_commands() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from(["guardian"]).unwrap();
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["guardian", "admin", "disable-2fa", "alice"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Admin {
+                action: AdminAction::Disable2fa { ref username }
+            }) if username == "alice"
+        ));
+
+        let cli = Cli::try_parse_from([
+            "guardian",
+            "admin",
+            "create",
+            "bob",
+            "--role",
+            "ops",
+            "--role",
+            "audit",
+            "--generate",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Admin {
+                action:
+                    AdminAction::Create {
+                        roles, password, ..
+                    },
+            }) => {
+                assert_eq!(roles, ["ops", "audit"]);
+                assert!(password.generate);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        assert!(
+            Cli::try_parse_from(["guardian", "init", "--generate", "--password-stdin"]).is_err()
+        );
+
+        let cli =
+            Cli::try_parse_from(["guardian", "role", "import", "seed.yaml", "--dry-run"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Rbac {
+                action: RbacAction::Import {
+                    mode: RbacImportMode::Update,
+                    dry_run: true,
+                    ..
+                }
+            })
+        ));
+        assert!(
+            Cli::try_parse_from(["guardian", "rbac", "import", "x.json", "--mode", "merge"])
+                .is_err()
+        );
+    }
+}
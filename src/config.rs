@@ -0,0 +1,1447 @@
+//! 启动配置
+//!
+//! 先取默认值，再读取 TOML 配置文件（`CONFIG_FILE`，未设置时尝试当前目录下的
+//! `guardian.toml`），最后用环境变量覆盖。加载完成后统一校验一次，有问题时列出
+//! 全部错误并拒绝启动，运行期间通过 `AppState::config` 读取。
+
+use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use axum::http::{HeaderName, HeaderValue, Method};
+use lettre::message::Mailbox;
+use serde::Deserialize;
+
+use crate::i18n::Locale;
+use crate::logging::LogFormat;
+use crate::middleware::client_ip::parse_proxy_network;
+use crate::middleware::rate_limit::{RateLimitAlgorithm, RateLimitKey, default_policies};
+use crate::service::login_throttle_service::LoginThrottleConfig;
+use crate::service::password_policy_service::PasswordPolicy;
+use crate::utils::PasswordHashConfig;
+
+/// 未设置 `CONFIG_FILE` 时尝试读取的配置文件
+const DEFAULT_CONFIG_FILE: &str = "guardian.toml";
+
+/// JWT 签名密钥的最小字节数（HS256 建议不短于 256 位）
+const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// 天数类配置的上限，超出后计算截止时间会溢出
+const MAX_DAYS: u64 = 36_500;
+
+/// 密码最小长度的上限
+const MAX_PASSWORD_MIN_LENGTH: usize = 128;
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub jwt: JwtConfig,
+    pub rate_limit: RateLimitConfig,
+    pub cors: CorsConfig,
+    pub security_headers: SecurityHeadersConfig,
+    pub tls: TlsConfig,
+    pub password_policy: PasswordPolicy,
+    pub password_hash: PasswordHashConfig,
+    pub password_reset: PasswordResetConfig,
+    pub login_throttle: LoginThrottleConfig,
+    pub captcha: CaptchaConfig,
+    pub mfa: MfaConfig,
+    pub webauthn: WebAuthnConfig,
+    pub mail: MailConfig,
+    pub ip_rules: IpRulesConfig,
+    pub logging: LoggingConfig,
+    pub metrics: MetricsConfig,
+    pub maintenance: MaintenanceConfig,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// 收到退出信号后，就绪检查先返回 503，等待该秒数让负载均衡摘除本实例，再停止接受新连接
+    pub shutdown_grace_secs: u64,
+    /// 收到退出信号后等待处理中请求完成的最长时间，超时后强制退出
+    pub shutdown_timeout_secs: u64,
+    /// 受信任的反向代理（CIDR 或单个 IP），只采信来自这些地址的转发头
+    pub trusted_proxies: Vec<String>,
+    /// 请求未指定或无法协商语言时使用的语言
+    pub default_locale: Locale,
+    /// 是否对外提供 `openapi.json` 和 Swagger UI
+    pub api_docs_enabled: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 6123,
+            shutdown_grace_secs: 5,
+            shutdown_timeout_secs: 30,
+            trusted_proxies: Vec::new(),
+            default_locale: Locale::ZhCn,
+            api_docs_enabled: true,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn bind_addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn shutdown_grace(&self) -> Duration {
+        Duration::from_secs(self.shutdown_grace_secs)
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// PostgreSQL 连接字符串，必填
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout_secs: u64,
+    /// 空闲连接回收时间，0 表示不回收
+    pub idle_timeout_secs: u64,
+    /// 启动时自动执行未执行的数据库迁移
+    pub auto_migrate: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            max_connections: 10,
+            min_connections: 0,
+            connect_timeout_secs: 30,
+            idle_timeout_secs: 600,
+            auto_migrate: true,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs)
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout_secs > 0).then(|| Duration::from_secs(self.idle_timeout_secs))
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtConfig {
+    /// 签名密钥，必填，至少 32 字节
+    pub secret: String,
+    pub access_token_ttl_secs: u64,
+    pub refresh_token_ttl_secs: u64,
+    /// 已吊销令牌的存储：`postgres`（多实例共享且重启不丢失）或 `memory`
+    pub revocation_store: String,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            secret: String::new(),
+            access_token_ttl_secs: 15 * 60,
+            refresh_token_ttl_secs: 7 * 24 * 60 * 60,
+            revocation_store: "postgres".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// 计数存储：`memory`（仅单实例有效）或 `postgres`（多实例共享）
+    pub store: String,
+    /// `default` 策略的限额，即未单独配置的路由
+    pub max_requests: u64,
+    pub window_secs: u64,
+    /// 按策略名覆盖默认策略
+    pub policies: BTreeMap<String, RateLimitPolicyConfig>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            store: "memory".to_string(),
+            max_requests: 100,
+            window_secs: 60,
+            policies: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitPolicyConfig {
+    pub limit: Option<u64>,
+    pub window_secs: Option<u64>,
+    pub algorithm: Option<RateLimitAlgorithm>,
+    pub key: Option<RateLimitKey>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// 允许跨域访问的来源，如 `https://admin.example.com`，`*` 表示任意来源
+    pub allowed_origins: Vec<String>,
+    /// 允许的请求方法，`*` 表示任意方法
+    pub allowed_methods: Vec<String>,
+    /// 允许携带的请求头，`*` 表示任意请求头
+    pub allowed_headers: Vec<String>,
+    /// 允许前端脚本读取的响应头
+    pub expose_headers: Vec<String>,
+    /// 是否允许携带 Cookie 等凭据，开启后来源、方法和请求头都不能使用 `*`
+    pub allow_credentials: bool,
+    /// 预检结果的缓存时间，0 表示不返回 `Access-Control-Max-Age`
+    pub max_age_secs: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: vec!["*".to_string()],
+            allowed_headers: vec!["*".to_string()],
+            expose_headers: [
+                "x-request-id",
+                "traceparent",
+                "retry-after",
+                "ratelimit-limit",
+                "ratelimit-remaining",
+                "ratelimit-reset",
+                "ratelimit-policy",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            allow_credentials: false,
+            max_age_secs: 600,
+        }
+    }
+}
+
+impl CorsConfig {
+    pub fn allow_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+
+    pub fn allow_any_method(&self) -> bool {
+        self.allowed_methods.iter().any(|method| method == "*")
+    }
+
+    pub fn allow_any_header(&self) -> bool {
+        self.allowed_headers.iter().any(|header| header == "*")
+    }
+}
+
+/// 附加在所有响应上的安全响应头，值为空字符串时不返回对应的头
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityHeadersConfig {
+    pub enabled: bool,
+    /// `Strict-Transport-Security` 的 max-age，0 表示不返回。浏览器只在 HTTPS 响应中采纳
+    pub hsts_max_age_secs: u64,
+    pub hsts_include_subdomains: bool,
+    /// `X-Frame-Options`，可选 DENY / SAMEORIGIN
+    pub frame_options: String,
+    pub referrer_policy: String,
+    /// 接口响应的 `Content-Security-Policy`
+    pub content_security_policy: String,
+    /// Swagger UI 页面的 `Content-Security-Policy`，需要放行自身的脚本、样式和图片
+    pub docs_content_security_policy: String,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hsts_max_age_secs: 31_536_000,
+            hsts_include_subdomains: false,
+            frame_options: "DENY".to_string(),
+            referrer_policy: "no-referrer".to_string(),
+            content_security_policy: "default-src 'none'; frame-ancestors 'none'".to_string(),
+            docs_content_security_policy: "default-src 'self'; img-src 'self' data:; \
+                style-src 'self' 'unsafe-inline'; frame-ancestors 'none'"
+                .to_string(),
+        }
+    }
+}
+
+/// HTTPS 配置，未配置证书时只监听明文 HTTP
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    /// PEM 格式的证书链，第一张为服务端证书
+    pub cert_file: String,
+    /// PEM 格式的私钥（PKCS#8、PKCS#1 或 SEC1）
+    pub key_file: String,
+    /// 签发客户端证书的 CA（PEM），配置后 `client_cert_paths` 下的接口要求有效的客户端证书
+    pub client_ca_file: String,
+    /// 要求客户端证书的路径前缀，只应列出由其他服务调用的接口，浏览器访问的接口列入后将无法使用
+    pub client_cert_paths: Vec<String>,
+    /// 检查证书文件是否变化的间隔，0 表示不自动重新加载
+    pub reload_interval_secs: u64,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            cert_file: String::new(),
+            key_file: String::new(),
+            client_ca_file: String::new(),
+            client_cert_paths: vec!["/metrics".to_string()],
+            reload_interval_secs: 30,
+        }
+    }
+}
+
+impl TlsConfig {
+    pub fn enabled(&self) -> bool {
+        !self.cert_file.is_empty()
+    }
+
+    pub fn client_auth_enabled(&self) -> bool {
+        self.enabled() && !self.client_ca_file.is_empty()
+    }
+
+    /// 该路径是否要求客户端证书，未配置客户端 CA 时不要求
+    pub fn requires_client_cert(&self, path: &str) -> bool {
+        self.client_auth_enabled()
+            && self.client_cert_paths.iter().any(|prefix| {
+                path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+                })
+            })
+    }
+
+    pub fn reload_interval(&self) -> Option<Duration> {
+        (self.reload_interval_secs > 0).then(|| Duration::from_secs(self.reload_interval_secs))
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasswordResetConfig {
+    /// 重置链接的有效期
+    pub token_ttl_secs: u64,
+    /// 邮件中的重置链接，`{token}` 替换为重置令牌
+    pub url: String,
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl_secs: 30 * 60,
+            url: "http://localhost:3000/reset-password?token={token}".to_string(),
+        }
+    }
+}
+
+/// 登录验证码，兼容 reCAPTCHA / hCaptcha / Turnstile 的 siteverify 接口
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CaptchaConfig {
+    /// 服务端密钥，为空时不启用验证码
+    pub secret: String,
+    pub verify_url: String,
+}
+
+impl Default for CaptchaConfig {
+    fn default() -> Self {
+        Self {
+            secret: String::new(),
+            verify_url: "https://challenges.cloudflare.com/turnstile/v0/siteverify".to_string(),
+        }
+    }
+}
+
+impl CaptchaConfig {
+    pub fn enabled(&self) -> bool {
+        !self.secret.is_empty()
+    }
+}
+
+/// 登录两步验证
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MfaConfig {
+    /// 密码校验通过后，提交验证码的有效期
+    pub challenge_ttl_secs: u64,
+    /// 同一挑战最多可提交验证码的次数
+    pub challenge_max_attempts: i32,
+}
+
+impl Default for MfaConfig {
+    fn default() -> Self {
+        Self {
+            challenge_ttl_secs: 5 * 60,
+            challenge_max_attempts: 5,
+        }
+    }
+}
+
+/// WebAuthn（安全密钥 / 通行密钥）两步验证，`rp_id` 为空时不启用
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebAuthnConfig {
+    /// 依赖方 ID，即前端页面的域名（不含协议和端口）
+    pub rp_id: String,
+    /// 浏览器注册凭证时展示的名称
+    pub rp_name: String,
+    /// 允许发起 WebAuthn 请求的页面来源，如 `https://admin.example.com`
+    pub origins: Vec<String>,
+}
+
+impl Default for WebAuthnConfig {
+    fn default() -> Self {
+        Self {
+            rp_id: String::new(),
+            rp_name: "Guardian".to_string(),
+            origins: Vec::new(),
+        }
+    }
+}
+
+impl WebAuthnConfig {
+    pub fn enabled(&self) -> bool {
+        !self.rp_id.is_empty()
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MailConfig {
+    /// 发送通道：`none`（默认，不发送邮件）、`smtp`，或仅用于开发测试的 `file`、`stdout`
+    pub transport: String,
+    /// 允许使用 `file` / `stdout` 通道。它们会把重置链接写入本地文件或日志，生产环境不要开启
+    pub allow_dev_transports: bool,
+    /// `file` 通道写入的文件
+    pub file_path: String,
+    pub smtp_host: String,
+    /// 未配置时使用加密方式对应的默认端口
+    pub smtp_port: Option<u16>,
+    /// `starttls`（默认）、`tls` 或 `none`
+    pub smtp_tls: String,
+    /// 为空时不认证
+    pub smtp_username: String,
+    pub smtp_password: String,
+    /// 发件人，如 `Guardian <noreply@example.com>`
+    pub smtp_from: String,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            transport: "none".to_string(),
+            allow_dev_transports: false,
+            file_path: "mail.log".to_string(),
+            smtp_host: String::new(),
+            smtp_port: None,
+            smtp_tls: "starttls".to_string(),
+            smtp_username: String::new(),
+            smtp_password: String::new(),
+            smtp_from: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IpRulesConfig {
+    /// 规则缓存时间，其他实例修改规则后最迟在该时间后生效
+    pub cache_secs: u64,
+}
+
+impl Default for IpRulesConfig {
+    fn default() -> Self {
+        Self { cache_secs: 30 }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// 运行日志格式：text 或 json
+    pub format: LogFormat,
+}
+
+/// Prometheus 指标，启用后由 `GET /metrics` 导出，同样受 IP 黑白名单约束
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+}
+
+/// 后台维护任务，间隔为 0 时禁用对应任务
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenanceConfig {
+    pub limiter_cleanup_interval_secs: u64,
+    pub expired_tokens_interval_secs: u64,
+    pub audit_retention_interval_secs: u64,
+    pub lock_reset_interval_secs: u64,
+    /// 审计日志保留天数，超期的记录由后台任务删除。默认 0，表示永久保留
+    pub audit_log_retention_days: u64,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            limiter_cleanup_interval_secs: 60,
+            expired_tokens_interval_secs: 60 * 60,
+            audit_retention_interval_secs: 24 * 60 * 60,
+            lock_reset_interval_secs: 5 * 60,
+            audit_log_retention_days: 0,
+        }
+    }
+}
+
+impl Config {
+    /// 按 默认值 → 配置文件 → 环境变量 的顺序加载并校验
+    pub fn load() -> Result<Self> {
+        Self::load_with(Self::validate)
+    }
+
+    /// 不连接数据库的命令（如 `hash-password`）使用，不要求配置数据库和 JWT 密钥
+    pub fn load_offline() -> Result<Self> {
+        Self::load_with(Self::validate_settings)
+    }
+
+    fn load_with(validate: fn(&Self) -> Vec<String>) -> Result<Self> {
+        let path = std::env::var("CONFIG_FILE").ok();
+        let mut config = match &path {
+            Some(path) => Self::from_file(Path::new(path))?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            None => Self::default(),
+        };
+
+        let mut errors = config.apply_env(|name| std::env::var(name).ok());
+        errors.extend(validate(&config));
+        into_result(errors)?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("无法读取配置文件 {}", path.display()))?;
+        Self::from_toml_str(&content)
+            .with_context(|| format!("配置文件 {} 格式错误", path.display()))
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// 用环境变量覆盖配置，返回无法解析的变量，而不是静默使用默认值
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut env = EnvOverrides {
+            lookup: &lookup,
+            errors: &mut errors,
+        };
+
+        env.string("SERVER_HOST", &mut self.server.host);
+        env.parse("SERVER_PORT", &mut self.server.port);
+        env.parse(
+            "SERVER_SHUTDOWN_GRACE_SECS",
+            &mut self.server.shutdown_grace_secs,
+        );
+        env.parse(
+            "SERVER_SHUTDOWN_TIMEOUT_SECS",
+            &mut self.server.shutdown_timeout_secs,
+        );
+        env.list("TRUSTED_PROXIES", &mut self.server.trusted_proxies);
+        env.parse("DEFAULT_LOCALE", &mut self.server.default_locale);
+        env.parse("API_DOCS_ENABLED", &mut self.server.api_docs_enabled);
+
+        env.string("DATABASE_URL", &mut self.database.url);
+        env.parse(
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        );
+        env.parse(
+            "DATABASE_MIN_CONNECTIONS",
+            &mut self.database.min_connections,
+        );
+        env.parse(
+            "DATABASE_CONNECT_TIMEOUT_SECS",
+            &mut self.database.connect_timeout_secs,
+        );
+        env.parse(
+            "DATABASE_IDLE_TIMEOUT_SECS",
+            &mut self.database.idle_timeout_secs,
+        );
+        env.parse("DATABASE_AUTO_MIGRATE", &mut self.database.auto_migrate);
+
+        env.string("JWT_SECRET", &mut self.jwt.secret);
+        env.parse(
+            "JWT_ACCESS_TOKEN_TTL_SECS",
+            &mut self.jwt.access_token_ttl_secs,
+        );
+        env.parse(
+            "JWT_REFRESH_TOKEN_TTL_SECS",
+            &mut self.jwt.refresh_token_ttl_secs,
+        );
+        env.string("REVOCATION_STORE", &mut self.jwt.revocation_store);
+
+        env.string("RATE_LIMIT_STORE", &mut self.rate_limit.store);
+        env.parse("RATE_LIMIT_MAX_REQUESTS", &mut self.rate_limit.max_requests);
+        env.parse("RATE_LIMIT_WINDOW_SECS", &mut self.rate_limit.window_secs);
+        for policy in default_policies(&RateLimitConfig::default()) {
+            let prefix = format!("RATE_LIMIT_{}", policy.name.to_uppercase());
+            let mut overrides = self
+                .rate_limit
+                .policies
+                .get(policy.name)
+                .cloned()
+                .unwrap_or_default();
+
+            if let Some(value) = (env.lookup)(&prefix) {
+                match parse_limit(&value) {
+                    Some((limit, window_secs)) => {
+                        overrides.limit = Some(limit);
+                        overrides.window_secs = Some(window_secs);
+                    }
+                    None => env.errors.push(format!(
+                        "{} 格式应为 <次数>/<秒数>，当前值: {}",
+                        prefix, value
+                    )),
+                }
+            }
+            env.parse_opt(&format!("{}_ALGORITHM", prefix), &mut overrides.algorithm);
+            env.parse_opt(&format!("{}_KEY", prefix), &mut overrides.key);
+
+            if overrides.limit.is_some()
+                || overrides.window_secs.is_some()
+                || overrides.algorithm.is_some()
+                || overrides.key.is_some()
+            {
+                self.rate_limit
+                    .policies
+                    .insert(policy.name.to_string(), overrides);
+            }
+        }
+
+        env.list("CORS_ALLOWED_ORIGINS", &mut self.cors.allowed_origins);
+        env.list("CORS_ALLOWED_METHODS", &mut self.cors.allowed_methods);
+        env.list("CORS_ALLOWED_HEADERS", &mut self.cors.allowed_headers);
+        env.list("CORS_EXPOSE_HEADERS", &mut self.cors.expose_headers);
+        env.parse("CORS_ALLOW_CREDENTIALS", &mut self.cors.allow_credentials);
+        env.parse("CORS_MAX_AGE_SECS", &mut self.cors.max_age_secs);
+
+        let headers = &mut self.security_headers;
+        env.parse("SECURITY_HEADERS_ENABLED", &mut headers.enabled);
+        env.parse("SECURITY_HSTS_MAX_AGE_SECS", &mut headers.hsts_max_age_secs);
+        env.parse(
+            "SECURITY_HSTS_INCLUDE_SUBDOMAINS",
+            &mut headers.hsts_include_subdomains,
+        );
+        env.string("SECURITY_FRAME_OPTIONS", &mut headers.frame_options);
+        env.string("SECURITY_REFERRER_POLICY", &mut headers.referrer_policy);
+        env.string("SECURITY_CSP", &mut headers.content_security_policy);
+        env.string(
+            "SECURITY_DOCS_CSP",
+            &mut headers.docs_content_security_policy,
+        );
+
+        env.string("TLS_CERT_FILE", &mut self.tls.cert_file);
+        env.string("TLS_KEY_FILE", &mut self.tls.key_file);
+        env.string("TLS_CLIENT_CA_FILE", &mut self.tls.client_ca_file);
+        env.list("TLS_CLIENT_CERT_PATHS", &mut self.tls.client_cert_paths);
+        env.parse(
+            "TLS_RELOAD_INTERVAL_SECS",
+            &mut self.tls.reload_interval_secs,
+        );
+
+        let policy = &mut self.password_policy;
+        env.parse("PASSWORD_MIN_LENGTH", &mut policy.min_length);
+        env.parse("PASSWORD_REQUIRE_UPPERCASE", &mut policy.require_uppercase);
+        env.parse("PASSWORD_REQUIRE_LOWERCASE", &mut policy.require_lowercase);
+        env.parse("PASSWORD_REQUIRE_DIGIT", &mut policy.require_digit);
+        env.parse("PASSWORD_REQUIRE_SYMBOL", &mut policy.require_symbol);
+        env.parse("PASSWORD_HISTORY_SIZE", &mut policy.history_size);
+        env.parse("PASSWORD_MAX_AGE_DAYS", &mut policy.max_age_days);
+
+        let hash = &mut self.password_hash;
+        env.parse("PASSWORD_HASH_M_COST", &mut hash.m_cost);
+        env.parse("PASSWORD_HASH_T_COST", &mut hash.t_cost);
+        env.parse("PASSWORD_HASH_P_COST", &mut hash.p_cost);
+        env.optional("PASSWORD_PEPPER", &mut hash.pepper);
+        env.string("PASSWORD_PEPPER_ID", &mut hash.pepper_id);
+
+        env.parse(
+            "PASSWORD_RESET_TOKEN_TTL_SECS",
+            &mut self.password_reset.token_ttl_secs,
+        );
+        env.string("PASSWORD_RESET_URL", &mut self.password_reset.url);
+
+        let throttle = &mut self.login_throttle;
+        env.parse(
+            "LOGIN_THROTTLE_IP_FREE_ATTEMPTS",
+            &mut throttle.ip_free_attempts,
+        );
+        env.parse(
+            "LOGIN_THROTTLE_USERNAME_FREE_ATTEMPTS",
+            &mut throttle.username_free_attempts,
+        );
+        env.parse(
+            "LOGIN_THROTTLE_BASE_DELAY_SECS",
+            &mut throttle.base_delay_secs,
+        );
+        env.parse(
+            "LOGIN_THROTTLE_MAX_DELAY_SECS",
+            &mut throttle.max_delay_secs,
+        );
+        env.parse("LOGIN_THROTTLE_RESET_SECS", &mut throttle.reset_after_secs);
+        env.parse(
+            "LOGIN_CAPTCHA_AFTER_FAILURES",
+            &mut throttle.captcha_after_failures,
+        );
+
+        env.string("CAPTCHA_SECRET", &mut self.captcha.secret);
+        env.string("CAPTCHA_VERIFY_URL", &mut self.captcha.verify_url);
+
+        env.parse("MFA_CHALLENGE_TTL_SECS", &mut self.mfa.challenge_ttl_secs);
+        env.parse(
+            "MFA_CHALLENGE_MAX_ATTEMPTS",
+            &mut self.mfa.challenge_max_attempts,
+        );
+
+        env.string("WEBAUTHN_RP_ID", &mut self.webauthn.rp_id);
+        env.string("WEBAUTHN_RP_NAME", &mut self.webauthn.rp_name);
+        env.list("WEBAUTHN_ORIGINS", &mut self.webauthn.origins);
+
+        let mail = &mut self.mail;
+        env.string("MAIL_TRANSPORT", &mut mail.transport);
+        env.parse("MAIL_ALLOW_DEV_TRANSPORTS", &mut mail.allow_dev_transports);
+        env.string("MAIL_FILE_PATH", &mut mail.file_path);
+        env.string("SMTP_HOST", &mut mail.smtp_host);
+        env.parse_opt("SMTP_PORT", &mut mail.smtp_port);
+        env.string("SMTP_TLS", &mut mail.smtp_tls);
+        env.string("SMTP_USERNAME", &mut mail.smtp_username);
+        env.string("SMTP_PASSWORD", &mut mail.smtp_password);
+        env.string("SMTP_FROM", &mut mail.smtp_from);
+
+        env.parse("IP_RULES_CACHE_SECS", &mut self.ip_rules.cache_secs);
+
+        env.parse("LOG_FORMAT", &mut self.logging.format);
+        env.parse("METRICS_ENABLED", &mut self.metrics.enabled);
+
+        let maintenance = &mut self.maintenance;
+        env.parse(
+            "MAINTENANCE_LIMITER_CLEANUP_INTERVAL_SECS",
+            &mut maintenance.limiter_cleanup_interval_secs,
+        );
+        env.parse(
+            "MAINTENANCE_EXPIRED_TOKENS_INTERVAL_SECS",
+            &mut maintenance.expired_tokens_interval_secs,
+        );
+        env.parse(
+            "MAINTENANCE_AUDIT_RETENTION_INTERVAL_SECS",
+            &mut maintenance.audit_retention_interval_secs,
+        );
+        env.parse(
+            "MAINTENANCE_LOCK_RESET_INTERVAL_SECS",
+            &mut maintenance.lock_reset_interval_secs,
+        );
+        env.parse(
+            "AUDIT_LOG_RETENTION_DAYS",
+            &mut maintenance.audit_log_retention_days,
+        );
+
+        errors
+    }
+
+    /// 启动时的一次性校验，返回全部问题而不是遇到第一个就停止
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = self.validate_connections();
+        errors.extend(self.validate_settings());
+        errors
+    }
+
+    /// 数据库连接和令牌签名，只有需要连接数据库的命令才要求
+    fn validate_connections(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let database = &self.database;
+        if database.url.is_empty() {
+            errors.push("未配置数据库连接（DATABASE_URL 或 database.url）".to_string());
+        } else if !(database.url.starts_with("postgres://")
+            || database.url.starts_with("postgresql://"))
+        {
+            errors.push("database.url 必须是 postgres:// 或 postgresql:// 连接字符串".to_string());
+        }
+        if database.max_connections == 0 {
+            errors.push("database.max_connections 必须大于 0".to_string());
+        }
+        if database.min_connections > database.max_connections {
+            errors.push(format!(
+                "database.min_connections（{}）不能大于 max_connections（{}）",
+                database.min_connections, database.max_connections
+            ));
+        }
+        if database.connect_timeout_secs == 0 {
+            errors.push("database.connect_timeout_secs 必须大于 0".to_string());
+        }
+
+        let jwt = &self.jwt;
+        if jwt.secret.is_empty() {
+            errors.push("未配置 JWT 签名密钥（JWT_SECRET 或 jwt.secret）".to_string());
+        } else if jwt.secret.len() < MIN_JWT_SECRET_LEN {
+            errors.push(format!(
+                "JWT 签名密钥长度为 {} 字节，至少需要 {} 字节",
+                jwt.secret.len(),
+                MIN_JWT_SECRET_LEN
+            ));
+        }
+        if jwt.access_token_ttl_secs == 0 {
+            errors.push("jwt.access_token_ttl_secs 必须大于 0".to_string());
+        }
+        if jwt.refresh_token_ttl_secs <= jwt.access_token_ttl_secs {
+            errors.push("jwt.refresh_token_ttl_secs 必须大于 access_token_ttl_secs".to_string());
+        }
+        if !matches!(jwt.revocation_store.as_str(), "postgres" | "memory") {
+            errors.push(format!(
+                "不支持的令牌吊销存储: {}（可选 postgres / memory）",
+                jwt.revocation_store
+            ));
+        }
+
+        errors
+    }
+
+    fn validate_settings(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            errors.push("server.host 不能为空".to_string());
+        }
+        if self.server.shutdown_grace_secs > 300 {
+            errors.push(format!(
+                "server.shutdown_grace_secs 不能超过 300: {}",
+                self.server.shutdown_grace_secs
+            ));
+        }
+
+        let rate_limit = &self.rate_limit;
+        if !matches!(rate_limit.store.as_str(), "memory" | "postgres") {
+            errors.push(format!(
+                "不支持的限流计数存储: {}（可选 memory / postgres）",
+                rate_limit.store
+            ));
+        }
+        if rate_limit.window_secs == 0 {
+            errors.push("rate_limit.window_secs 必须大于 0".to_string());
+        }
+        let known: Vec<&str> = default_policies(&RateLimitConfig::default())
+            .iter()
+            .map(|policy| policy.name)
+            .collect();
+        for (name, policy) in &rate_limit.policies {
+            if !known.contains(&name.as_str()) {
+                errors.push(format!(
+                    "未知的限流策略: {}（可选 {}）",
+                    name,
+                    known.join(" / ")
+                ));
+            }
+            if policy.window_secs == Some(0) {
+                errors.push(format!("限流策略 {} 的 window_secs 必须大于 0", name));
+            }
+        }
+
+        let origins = &self.cors.allowed_origins;
+        if origins.is_empty() {
+            errors.push("cors.allowed_origins 不能为空，允许任意来源请使用 \"*\"".to_string());
+        }
+        for origin in origins.iter().filter(|origin| *origin != "*") {
+            let valid = origin
+                .strip_prefix("https://")
+                .or_else(|| origin.strip_prefix("http://"))
+                .is_some_and(|host| !host.is_empty() && !host.contains('/'));
+            if !valid {
+                errors.push(format!(
+                    "无效的跨域来源: {}（格式如 https://admin.example.com）",
+                    origin
+                ));
+            }
+        }
+        let cors = &self.cors;
+        for method in cors.allowed_methods.iter().filter(|method| *method != "*") {
+            if Method::from_bytes(method.as_bytes()).is_err() {
+                errors.push(format!("无效的跨域请求方法: {}", method));
+            }
+        }
+        for header in cors
+            .allowed_headers
+            .iter()
+            .filter(|header| *header != "*")
+            .chain(&cors.expose_headers)
+        {
+            if HeaderName::from_bytes(header.as_bytes()).is_err() {
+                errors.push(format!("无效的跨域请求头: {}", header));
+            }
+        }
+        // 浏览器不接受携带凭据的响应使用通配符
+        if cors.allow_credentials && cors.allow_any_origin() {
+            errors.push("cors.allow_credentials 开启时 allowed_origins 不能包含 \"*\"".to_string());
+        }
+
+        let headers = &self.security_headers;
+        if !matches!(
+            headers.frame_options.to_ascii_uppercase().as_str(),
+            "" | "DENY" | "SAMEORIGIN"
+        ) {
+            errors.push(format!(
+                "不支持的 X-Frame-Options: {}（可选 DENY / SAMEORIGIN）",
+                headers.frame_options
+            ));
+        }
+        for (name, value) in [
+            ("referrer_policy", &headers.referrer_policy),
+            ("content_security_policy", &headers.content_security_policy),
+            (
+                "docs_content_security_policy",
+                &headers.docs_content_security_policy,
+            ),
+        ] {
+            if HeaderValue::from_str(value).is_err() {
+                errors.push(format!("security_headers.{} 包含无效字符", name));
+            }
+        }
+
+        // 证书文件的内容在启动监听时加载，这里只检查配置是否完整
+        let tls = &self.tls;
+        if tls.cert_file.is_empty() != tls.key_file.is_empty() {
+            errors.push("tls.cert_file 和 tls.key_file 需要同时配置".to_string());
+        }
+        if !tls.client_ca_file.is_empty() && !tls.enabled() {
+            errors.push("配置 tls.client_ca_file 时需要同时配置服务端证书".to_string());
+        }
+        for path in tls
+            .client_cert_paths
+            .iter()
+            .filter(|path| !path.starts_with('/'))
+        {
+            errors.push(format!("tls.client_cert_paths 需要以 / 开头: {}", path));
+        }
+
+        for proxy in &self.server.trusted_proxies {
+            if parse_proxy_network(proxy).is_none() {
+                errors.push(format!(
+                    "无效的受信任代理: {}（格式如 10.0.0.0/8 或 127.0.0.1）",
+                    proxy
+                ));
+            }
+        }
+
+        let policy = &self.password_policy;
+        if !(1..=MAX_PASSWORD_MIN_LENGTH).contains(&policy.min_length) {
+            errors.push(format!(
+                "password_policy.min_length 需要在 1 到 {} 之间",
+                MAX_PASSWORD_MIN_LENGTH
+            ));
+        }
+        if policy.max_age_days > MAX_DAYS {
+            errors.push(format!(
+                "password_policy.max_age_days 不能超过 {}，永不过期请使用 0",
+                MAX_DAYS
+            ));
+        }
+
+        if let Err(e) = self.password_hash.validate() {
+            errors.push(format!("password_hash 配置无效: {}", e));
+        }
+
+        let reset = &self.password_reset;
+        if reset.token_ttl_secs == 0 {
+            errors.push("password_reset.token_ttl_secs 必须大于 0".to_string());
+        }
+        if !reset.url.contains("{token}") {
+            errors.push("password_reset.url 需要包含 {token} 占位符".to_string());
+        }
+
+        let throttle = &self.login_throttle;
+        if throttle.base_delay_secs > throttle.max_delay_secs {
+            errors.push("login_throttle.base_delay_secs 不能大于 max_delay_secs".to_string());
+        }
+
+        let captcha = &self.captcha;
+        if captcha.enabled()
+            && !(captcha.verify_url.starts_with("https://")
+                || captcha.verify_url.starts_with("http://"))
+        {
+            errors.push(format!(
+                "captcha.verify_url 必须是 http(s) 地址: {}",
+                captcha.verify_url
+            ));
+        }
+
+        if self.mfa.challenge_ttl_secs == 0 {
+            errors.push("mfa.challenge_ttl_secs 必须大于 0".to_string());
+        }
+        if self.mfa.challenge_max_attempts <= 0 {
+            errors.push("mfa.challenge_max_attempts 必须大于 0".to_string());
+        }
+
+        let webauthn = &self.webauthn;
+        if webauthn.enabled() {
+            if webauthn.origins.is_empty() {
+                errors.push("启用 WebAuthn 时需要配置 webauthn.origins".to_string());
+            }
+            for origin in &webauthn.origins {
+                if !webauthn_origin_allowed(&webauthn.rp_id, origin) {
+                    errors.push(format!(
+                        "webauthn.origins 中的来源必须是 rp_id（{}）或其子域名的 https 地址: {}",
+                        webauthn.rp_id, origin
+                    ));
+                }
+            }
+        }
+
+        let mail = &self.mail;
+        match mail.transport.as_str() {
+            "none" => {}
+            "stdout" | "file" if !mail.allow_dev_transports => {
+                errors.push(format!(
+                    "mail.transport 为 {} 时重置链接会写入日志或本地文件，仅限开发测试使用，\
+                     需同时设置 mail.allow_dev_transports = true（MAIL_ALLOW_DEV_TRANSPORTS）",
+                    mail.transport
+                ));
+            }
+            "stdout" => {}
+            "file" if mail.file_path.is_empty() => {
+                errors.push("mail.transport 为 file 时需要配置 mail.file_path".to_string());
+            }
+            "file" => {}
+            "smtp" => {
+                if mail.smtp_host.is_empty() {
+                    errors.push("未配置 SMTP 服务器（SMTP_HOST 或 mail.smtp_host）".to_string());
+                }
+                if let Err(e) = mail.smtp_from.parse::<Mailbox>() {
+                    errors.push(format!("mail.smtp_from 格式错误: {}", e));
+                }
+                if !matches!(mail.smtp_tls.as_str(), "starttls" | "tls" | "none") {
+                    errors.push(format!(
+                        "不支持的 SMTP 加密方式: {}（可选 starttls / tls / none）",
+                        mail.smtp_tls
+                    ));
+                }
+            }
+            other => errors.push(format!(
+                "不支持的邮件发送通道: {}（可选 none / smtp / file / stdout）",
+                other
+            )),
+        }
+
+        if self.maintenance.audit_log_retention_days > MAX_DAYS {
+            errors.push(format!(
+                "maintenance.audit_log_retention_days 不能超过 {}，永久保留请使用 0",
+                MAX_DAYS
+            ));
+        }
+
+        errors
+    }
+}
+
+struct EnvOverrides<'a, F: Fn(&str) -> Option<String>> {
+    lookup: &'a F,
+    errors: &'a mut Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvOverrides<'_, F> {
+    fn string(&mut self, name: &str, target: &mut String) {
+        if let Some(value) = (self.lookup)(name) {
+            *target = value;
+        }
+    }
+
+    /// 空字符串视为未设置
+    fn optional(&mut self, name: &str, target: &mut Option<String>) {
+        if let Some(value) = (self.lookup)(name) {
+            *target = (!value.is_empty()).then_some(value);
+        }
+    }
+
+    /// 逗号分隔的列表，忽略空项
+    fn list(&mut self, name: &str, target: &mut Vec<String>) {
+        if let Some(value) = (self.lookup)(name) {
+            *target = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+
+    fn parse<T: FromStr>(&mut self, name: &str, target: &mut T) {
+        if let Some(value) = (self.lookup)(name) {
+            match value.trim().parse() {
+                Ok(parsed) => *target = parsed,
+                Err(_) => self
+                    .errors
+                    .push(format!("环境变量 {} 的值无效: {}", name, value)),
+            }
+        }
+    }
+
+    fn parse_opt<T: FromStr>(&mut self, name: &str, target: &mut Option<T>) {
+        if let Some(value) = (self.lookup)(name) {
+            match value.trim().parse() {
+                Ok(parsed) => *target = Some(parsed),
+                Err(_) => self
+                    .errors
+                    .push(format!("环境变量 {} 的值无效: {}", name, value)),
+            }
+        }
+    }
+}
+
+/// 解析 `<次数>/<秒数>`
+fn parse_limit(value: &str) -> Option<(u64, u64)> {
+    let (limit, window_secs) = value.split_once('/')?;
+    Some((limit.trim().parse().ok()?, window_secs.trim().parse().ok()?))
+}
+
+/// 来源须为 `https://<host>[:port]`（本地调试可用 `http://localhost`），且 host 为 rp_id 或其子域名
+fn webauthn_origin_allowed(rp_id: &str, origin: &str) -> bool {
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return false;
+    };
+    let host = authority.split(':').next().unwrap_or_default();
+    let secure = scheme == "https" || (scheme == "http" && host == "localhost");
+
+    secure && !authority.contains('/') && (host == rp_id || host.ends_with(&format!(".{}", rp_id)))
+}
+
+fn into_result(errors: Vec<String>) -> Result<()> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let details: Vec<String> = errors.iter().map(|e| format!("  - {}", e)).collect();
+    Err(anyhow!("配置校验失败：\n{}", details.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn valid_config() -> Config {
+        let mut config = Config::default();
+        config.database.url = "postgresql://postgres@127.0.0.1:5432/guardian_auth".to_string();
+        config.jwt.secret = "x".repeat(MIN_JWT_SECRET_LEN);
+        config
+    }
+
+    fn env(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml_str(
+            r#"
+            [server]
+            port = 8080
+            default_locale = "en-US"
+
+            [database]
+            url = "postgresql://postgres@db/guardian_auth"
+            max_connections = 20
+
+            [rate_limit.policies.login]
+            limit = 5
+            algorithm = "token_bucket"
+            key = "ip_admin"
+
+            [cors]
+            allowed_origins = ["https://admin.example.com"]
+            allow_credentials = true
+
+            [security_headers]
+            frame_options = "SAMEORIGIN"
+
+            [password_policy]
+            require_symbol = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.server.bind_addr(), "0.0.0.0:8080");
+        assert_eq!(config.database.max_connections, 20);
+        assert_eq!(config.database.connect_timeout_secs, 30);
+        let login = &config.rate_limit.policies["login"];
+        assert_eq!(login.limit, Some(5));
+        assert_eq!(login.window_secs, None);
+        assert_eq!(login.algorithm, Some(RateLimitAlgorithm::TokenBucket));
+        assert_eq!(login.key, Some(RateLimitKey::IpAndAdmin));
+        assert!(!config.cors.allow_any_origin());
+        assert!(config.cors.allow_credentials);
+        assert!(config.cors.allow_any_method());
+        assert_eq!(config.security_headers.frame_options, "SAMEORIGIN");
+        assert_eq!(config.security_headers.hsts_max_age_secs, 31_536_000);
+        // 审计日志默认永久保留，指标默认不提供
+        assert_eq!(config.maintenance.audit_log_retention_days, 0);
+        assert!(!config.metrics.enabled);
+        assert_eq!(config.logging.format, LogFormat::Text);
+        assert_eq!(config.tls.client_cert_paths, ["/metrics"]);
+        assert_eq!(config.server.default_locale, Locale::EnUs);
+        assert!(config.password_policy.require_symbol);
+        assert_eq!(config.password_policy.min_length, 8);
+
+        // 模板与结构体保持同步
+        let example = Config::from_toml_str(include_str!("../guardian.example.toml")).unwrap();
+        assert_eq!(example.server.port, 6123);
+
+        // 拼错的字段直接报错，而不是被忽略
+        assert!(Config::from_toml_str("[server]\nprot = 8080").is_err());
+        assert!(Config::from_toml_str("[server]\ndefault_locale = \"fr\"").is_err());
+        assert!(Config::from_toml_str("[logging]\nformat = \"logfmt\"").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = Config::from_toml_str("[server]\nport = 8080").unwrap();
+        let errors = config.apply_env(env(&[
+            ("SERVER_PORT", "9090"),
+            ("SERVER_SHUTDOWN_GRACE_SECS", "0"),
+            ("SERVER_SHUTDOWN_TIMEOUT_SECS", "5"),
+            ("JWT_SECRET", "from-env"),
+            ("RATE_LIMIT_LOGIN", "3/30"),
+            ("RATE_LIMIT_WRITE_KEY", "ip"),
+            (
+                "CORS_ALLOWED_ORIGINS",
+                "https://a.example.com, http://localhost:5173",
+            ),
+            ("CORS_ALLOWED_METHODS", "GET,POST,"),
+            ("CORS_ALLOW_CREDENTIALS", "true"),
+            ("SECURITY_HSTS_MAX_AGE_SECS", "0"),
+            ("SECURITY_CSP", "default-src 'self'"),
+            ("TLS_CERT_FILE", "/etc/guardian/tls.crt"),
+            ("TLS_KEY_FILE", "/etc/guardian/tls.key"),
+            ("TLS_CLIENT_CA_FILE", "/etc/guardian/clients.pem"),
+            ("TLS_CLIENT_CERT_PATHS", "/metrics, /guardian-auth/v1/rbac/"),
+            ("AUDIT_LOG_RETENTION_DAYS", "180"),
+            ("TRUSTED_PROXIES", "10.0.0.0/8, 127.0.0.1"),
+            ("DEFAULT_LOCALE", "en-us"),
+            ("API_DOCS_ENABLED", "false"),
+            ("METRICS_ENABLED", "true"),
+            ("LOG_FORMAT", "json"),
+            ("PASSWORD_MIN_LENGTH", "12"),
+            ("PASSWORD_PEPPER", ""),
+            ("WEBAUTHN_RP_ID", "example.com"),
+            (
+                "WEBAUTHN_ORIGINS",
+                "https://admin.example.com,https://example.com",
+            ),
+            ("MAIL_TRANSPORT", "smtp"),
+            ("MAIL_ALLOW_DEV_TRANSPORTS", "true"),
+            ("SMTP_PORT", "2525"),
+            ("MAINTENANCE_LOCK_RESET_INTERVAL_SECS", "0"),
+        ]));
+
+        assert!(errors.is_empty());
+        assert_eq!(config.server.port, 9090);
+        assert_eq!(config.server.shutdown_grace(), Duration::ZERO);
+        assert_eq!(config.server.shutdown_timeout(), Duration::from_secs(5));
+        assert_eq!(config.jwt.secret, "from-env");
+        let login = &config.rate_limit.policies["login"];
+        assert_eq!((login.limit, login.window_secs), (Some(3), Some(30)));
+        assert_eq!(
+            config.rate_limit.policies["write"].key,
+            Some(RateLimitKey::Ip)
+        );
+        assert_eq!(config.cors.allowed_origins.len(), 2);
+        assert_eq!(config.cors.allowed_methods, ["GET", "POST"]);
+        assert!(config.cors.allow_credentials);
+        assert_eq!(config.security_headers.hsts_max_age_secs, 0);
+        assert_eq!(
+            config.security_headers.content_security_policy,
+            "default-src 'self'"
+        );
+        assert!(config.tls.client_auth_enabled());
+        assert!(config.tls.requires_client_cert("/metrics"));
+        assert!(
+            config
+                .tls
+                .requires_client_cert("/guardian-auth/v1/rbac/export")
+        );
+        assert!(!config.tls.requires_client_cert("/metricsx"));
+        assert!(!config.tls.requires_client_cert("/guardian-auth/v1/admins"));
+        assert_eq!(config.maintenance.audit_log_retention_days, 180);
+        assert_eq!(config.server.trusted_proxies, ["10.0.0.0/8", "127.0.0.1"]);
+        assert_eq!(config.server.default_locale, Locale::EnUs);
+        assert!(!config.server.api_docs_enabled);
+        assert!(config.metrics.enabled);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.password_policy.min_length, 12);
+        assert_eq!(config.password_hash.pepper, None);
+        assert!(config.webauthn.enabled());
+        assert_eq!(config.webauthn.rp_name, "Guardian");
+        assert_eq!(config.webauthn.origins.len(), 2);
+        assert_eq!(config.mail.transport, "smtp");
+        assert!(config.mail.allow_dev_transports);
+        assert_eq!(config.mail.smtp_port, Some(2525));
+        assert_eq!(config.maintenance.lock_reset_interval_secs, 0);
+    }
+
+    #[test]
+    fn test_invalid_env_reported() {
+        let mut config = Config::default();
+        let err = config
+            .apply_env(env(&[
+                ("SERVER_PORT", "http"),
+                ("RATE_LIMIT_LOGIN", "10"),
+                ("RATE_LIMIT_REFRESH_ALGORITHM", "leaky_bucket"),
+                ("CORS_ALLOW_CREDENTIALS", "yes"),
+                ("AUDIT_LOG_RETENTION_DAYS", "-1"),
+                ("PASSWORD_MIN_LENGTH", "abc"),
+                ("DEFAULT_LOCALE", "fr-FR"),
+                ("MFA_CHALLENGE_TTL_SECS", "5m"),
+            ]))
+            .join("\n");
+
+        assert!(err.contains("SERVER_PORT"));
+        assert!(err.contains("RATE_LIMIT_LOGIN"));
+        assert!(err.contains("RATE_LIMIT_REFRESH_ALGORITHM"));
+        assert!(err.contains("CORS_ALLOW_CREDENTIALS"));
+        assert!(err.contains("AUDIT_LOG_RETENTION_DAYS"));
+        assert!(err.contains("PASSWORD_MIN_LENGTH"));
+        assert!(err.contains("DEFAULT_LOCALE"));
+        assert!(err.contains("MFA_CHALLENGE_TTL_SECS"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(valid_config().validate().is_empty());
+
+        let err = Config::default().validate().join("\n");
+        assert!(err.contains("DATABASE_URL"));
+        assert!(err.contains("JWT_SECRET"));
+        // 不连接数据库的命令不要求数据库和 JWT 配置
+        assert!(Config::default().validate_settings().is_empty());
+
+        let mut config = valid_config();
+        config.jwt.secret = "short".to_string();
+        config.database.min_connections = 50;
+        config.rate_limit.store = "redis".to_string();
+        config
+            .rate_limit
+            .policies
+            .insert("signup".to_string(), RateLimitPolicyConfig::default());
+        config.cors.allowed_origins = vec!["admin.example.com".to_string(), "*".to_string()];
+        config.cors.allowed_methods = vec!["GET POST".to_string()];
+        config.cors.expose_headers = vec!["x request id".to_string()];
+        config.cors.allow_credentials = true;
+        config.security_headers.frame_options = "ALLOW-FROM https://a.example.com".to_string();
+        config.tls.key_file = "/etc/guardian/tls.key".to_string();
+        config.tls.client_ca_file = "/etc/guardian/clients.pem".to_string();
+        config.tls.client_cert_paths = vec!["metrics".to_string()];
+        config.maintenance.audit_log_retention_days = 100_000;
+        config.server.trusted_proxies = vec!["garbage".to_string()];
+        config.server.shutdown_grace_secs = 600;
+        config.password_policy.min_length = 0;
+        config.password_hash.p_cost = 0;
+        config.password_reset.url = "https://admin.example.com/reset".to_string();
+        config.login_throttle.base_delay_secs = 3600;
+        config.mfa.challenge_max_attempts = 0;
+        config.webauthn.rp_id = "example.com".to_string();
+        config.webauthn.origins = vec![
+            "https://admin.example.com".to_string(),
+            "http://admin.example.com".to_string(),
+            "https://example.com.evil.net".to_string(),
+        ];
+        config.mail.transport = "smtp".to_string();
+        config.mail.smtp_tls = "ssl".to_string();
+        let err = config.validate().join("\n");
+        let mut dev_mail = valid_config();
+        dev_mail.mail.transport = "stdout".to_string();
+        let dev_mail_err = dev_mail.validate().join("\n");
+        assert!(
+            dev_mail_err.contains("allow_dev_transports"),
+            "{}",
+            dev_mail_err
+        );
+        dev_mail.mail.allow_dev_transports = true;
+        assert!(dev_mail.validate().is_empty());
+        for expected in [
+            "至少需要",
+            "min_connections",
+            "redis",
+            "signup",
+            "admin.example.com",
+            "GET POST",
+            "x request id",
+            "allow_credentials",
+            "ALLOW-FROM",
+            "tls.cert_file",
+            "tls.client_ca_file",
+            "tls.client_cert_paths",
+            "audit_log_retention_days",
+            "garbage",
+            "shutdown_grace_secs",
+            "password_policy.min_length",
+            "password_hash",
+            "{token}",
+            "base_delay_secs",
+            "challenge_max_attempts",
+            "http://admin.example.com",
+            "https://example.com.evil.net",
+            "SMTP_HOST",
+            "smtp_from",
+            "ssl",
+        ] {
+            assert!(err.contains(expected), "缺少错误信息 {}: {}", expected, err);
+        }
+    }
+
+    #[test]
+    fn test_webauthn_origin_allowed() {
+        assert!(webauthn_origin_allowed(
+            "example.com",
+            "https://example.com"
+        ));
+        assert!(webauthn_origin_allowed(
+            "example.com",
+            "https://admin.example.com:8443"
+        ));
+        assert!(webauthn_origin_allowed(
+            "localhost",
+            "http://localhost:5173"
+        ));
+        assert!(!webauthn_origin_allowed(
+            "example.com",
+            "http://admin.example.com"
+        ));
+        assert!(!webauthn_origin_allowed(
+            "example.com",
+            "https://badexample.com"
+        ));
+        assert!(!webauthn_origin_allowed(
+            "example.com",
+            "https://example.com/login"
+        ));
+        assert!(!webauthn_origin_allowed("example.com", "example.com"));
+    }
+}
@@ -1,91 +1,151 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     response::IntoResponse,
 };
+use utoipa::TupleUnit;
 use uuid::Uuid;
 
 use crate::dto::{
-    AdminDetailResponse, AdminListQuery, AdminListResponse, AdminResponse, CreateAdminRequest,
-    UpdateAdminRequest,
+    AdminDetailResponse, AdminListQuery, AdminListResponse, AdminResponse, AssignRolesRequest,
+    CreateAdminRequest, UpdateAdminRequest,
 };
-use crate::response::Response;
+use crate::extract::{ValidatedJson, ValidatedQuery};
+use crate::response::{ErrorResponse, Response};
 use crate::router::AppState;
 use crate::service::admin_service::*;
 
+#[utoipa::path(
+    get,
+    path = "/admins",
+    tag = "管理员",
+    summary = "查询管理员列表",
+    params(AdminListQuery),
+    responses((status = 200, description = "成功", body = Response<AdminListResponse>)),
+    security(("bearer" = []))
+)]
 pub async fn list_admin(
     State(state): State<AppState>,
-    Query(query): Query<AdminListQuery>,
+    ValidatedQuery(query): ValidatedQuery<AdminListQuery>,
 ) -> impl IntoResponse {
-    match list_admin_service(state, query).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(Response::failed(e.to_string())),
-        ),
-    }
+    list_admin_service(state, query).await.map(Json)
 }
 
-pub async fn get_admin(
-    State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
-) -> impl IntoResponse {
-    match get_admin_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+#[utoipa::path(
+    get,
+    path = "/admins/{id}",
+    tag = "管理员",
+    summary = "获取管理员详情",
+    params(("id" = Uuid, Path, description = "管理员 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<AdminDetailResponse>),
+        (status = 404, description = "管理员不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_admin(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    get_admin_service(state, id).await.map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/admins",
+    tag = "管理员",
+    summary = "创建管理员",
+    request_body = CreateAdminRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<AdminResponse>),
+        (status = 409, description = "用户名或邮箱已存在，或角色列表有重复", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或密码不满足密码策略", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_admin(
     State(state): State<AppState>,
-    Json(payload): Json<CreateAdminRequest>,
+    ValidatedJson(payload): ValidatedJson<CreateAdminRequest>,
 ) -> impl IntoResponse {
-    match create_admin_service(state, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    create_admin_service(state, payload).await.map(Json)
 }
 
+#[utoipa::path(
+    put,
+    path = "/admins/{id}",
+    tag = "管理员",
+    summary = "更新管理员",
+    request_body = UpdateAdminRequest,
+    params(("id" = Uuid, Path, description = "管理员 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<AdminResponse>),
+        (status = 404, description = "管理员不存在", body = ErrorResponse),
+        (status = 409, description = "邮箱已被使用，或角色列表有重复", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或密码不满足密码策略", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn update_admin(
     State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
-    Json(payload): Json<UpdateAdminRequest>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateAdminRequest>,
 ) -> impl IntoResponse {
-    match update_admin_service(state, id, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    update_admin_service(state, id, payload).await.map(Json)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/admins/{id}",
+    tag = "管理员",
+    summary = "删除管理员（暂不支持）",
+    params(("id" = Uuid, Path, description = "管理员 ID")),
+    responses((status = 200, description = "成功", body = Response<TupleUnit>)),
+    security(("bearer" = []))
+)]
 pub async fn delete_admin(
     State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
+    Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match delete_admin_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    delete_admin_service(state, id).await.map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/admins/{id}/roles",
+    tag = "管理员",
+    summary = "全量替换管理员的角色",
+    request_body = AssignRolesRequest,
+    params(("id" = Uuid, Path, description = "管理员 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 400, description = "超级管理员不可分配角色", body = ErrorResponse),
+        (status = 404, description = "管理员不存在", body = ErrorResponse),
+        (status = 409, description = "角色列表有重复", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn assign_roles(
     State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
-    Json(payload): Json<serde_json::Value>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<AssignRolesRequest>,
 ) -> impl IntoResponse {
-    let role_ids: Vec<Uuid> = payload
-        .get("role_ids")
-        .and_then(|v| v.as_array())
-        .and_then(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().and_then(|s| Uuid::parse_str(s).ok()))
-                .collect::<Vec<_>>()
-                .into()
-        })
-        .unwrap_or_default();
+    assign_roles_service(state, id, payload.role_ids)
+        .await
+        .map(Json)
+}
 
-    match assign_roles_service(state, id, role_ids).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+#[utoipa::path(
+    post,
+    path = "/admins/{id}/unlock",
+    tag = "管理员",
+    summary = "解除管理员的登录锁定",
+    params(("id" = Uuid, Path, description = "管理员 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 404, description = "管理员不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn unlock_admin(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    unlock_admin_service(state, id).await.map(Json)
 }
@@ -1,114 +1,287 @@
+use axum::http::{HeaderMap, header};
 use axum::response::IntoResponse;
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{Extension, Json, extract::State};
+use utoipa::TupleUnit;
 
 use crate::dto::{
-    ChangeOwnPasswordRequest, LoginRequest, LoginResponse, RefreshTokenRequest,
-    RefreshTokenResponse, ResetPasswordRequest, TwoFaDisableResponse, TwoFaSetupResponse,
-    TwoFaVerifyRequest, TwoFaVerifyResponse,
+    ChangePasswordRequest, LoginRequest, LoginResponse, LoginResult, LoginTwoFaRequest,
+    PasswordResetConfirmRequest, PasswordResetRequest, RefreshTokenRequest, RefreshTokenResponse,
+    ResetPasswordRequest, TwoFaDisableResponse, TwoFaSetupResponse, TwoFaVerifyRequest,
+    TwoFaVerifyResponse, UpdatePreferencesRequest,
 };
+use crate::extract::ValidatedJson;
 use crate::middleware::auth::AuthContext;
-use crate::response::{Response, ResponseCode};
+use crate::middleware::client_ip::ClientIp;
+use crate::response::{ErrorResponse, Response};
 use crate::router::AppState;
+use crate::service::password_reset_service::{
+    confirm_password_reset_service, request_password_reset_service,
+};
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "认证",
+    summary = "用户名密码登录，启用 2FA 时返回挑战令牌",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<LoginResult>),
+        (status = 400, description = "需要人机验证或验证码无效", body = ErrorResponse),
+        (status = 401, description = "用户名或密码错误", body = ErrorResponse),
+        (status = 403, description = "账户已禁用或当前 IP 不允许该账户登录", body = ErrorResponse),
+        (status = 429, description = "登录失败次数过多，需等待 Retry-After 秒后重试", body = ErrorResponse),
+    )
+)]
+pub async fn login(
+    state: State<AppState>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    ValidatedJson(payload): ValidatedJson<LoginRequest>,
+) -> impl IntoResponse {
+    crate::service::login_service(state.0, payload, client_ip)
+        .await
+        .map(Json)
+}
 
-pub async fn login(state: State<AppState>, Json(payload): Json<LoginRequest>) -> impl IntoResponse {
-    match crate::service::login_service(state.0, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::AuthError.to_response(Some(e.to_string()))),
-        ),
-    }
+#[utoipa::path(
+    post,
+    path = "/auth/login/2fa",
+    tag = "认证",
+    summary = "提交 2FA 验证码完成登录",
+    request_body = LoginTwoFaRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<LoginResult>),
+        (status = 401, description = "验证码错误，或挑战令牌无效、已过期", body = ErrorResponse),
+        (status = 403, description = "账户已禁用", body = ErrorResponse),
+    )
+)]
+pub async fn login_2fa(
+    state: State<AppState>,
+    ValidatedJson(payload): ValidatedJson<LoginTwoFaRequest>,
+) -> impl IntoResponse {
+    crate::service::login_2fa_service(state.0, payload)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "认证",
+    summary = "登出并吊销当前令牌",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 401, description = "未登录、令牌已失效或刷新令牌不属于当前账户", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn logout(
     state: State<AppState>,
-    Json(payload): Json<RefreshTokenRequest>,
+    auth_context: axum::Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenRequest>,
 ) -> impl IntoResponse {
-    match crate::service::logout_service(state.0, payload.refresh_token).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::AuthError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::logout_service(state.0, auth_context.0, payload.refresh_token)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    tag = "认证",
+    summary = "刷新访问令牌",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<RefreshTokenResponse>),
+        (status = 401, description = "刷新令牌无效、已过期或已吊销", body = ErrorResponse),
+    )
+)]
 pub async fn refresh_token(
     state: State<AppState>,
-    Json(payload): Json<RefreshTokenRequest>,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenRequest>,
 ) -> impl IntoResponse {
-    match crate::service::refresh_token_service(state.0, payload.refresh_token).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::AuthError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::refresh_token_service(state.0, payload.refresh_token)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/setup",
+    tag = "认证",
+    summary = "生成 2FA 密钥和备用码",
+    responses(
+        (status = 200, description = "成功", body = Response<TwoFaSetupResponse>),
+        (status = 409, description = "已启用两步验证", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn setup_2fa(
     State(state): State<AppState>,
     auth_context: axum::Extension<AuthContext>,
 ) -> impl IntoResponse {
-    match crate::service::setup_2fa_service(state, auth_context.0).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::InternalError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::setup_2fa_service(state, auth_context.0)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/verify",
+    tag = "认证",
+    summary = "验证并启用 2FA",
+    request_body = TwoFaVerifyRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<TwoFaVerifyResponse>),
+        (status = 400, description = "尚未开始设置两步验证", body = ErrorResponse),
+        (status = 401, description = "验证码错误", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn verify_2fa(
     State(state): State<AppState>,
     auth_context: axum::Extension<AuthContext>,
-    Json(payload): Json<TwoFaVerifyRequest>,
+    ValidatedJson(payload): ValidatedJson<TwoFaVerifyRequest>,
 ) -> impl IntoResponse {
-    match crate::service::verify_2fa_service(state, auth_context.0, payload.code).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::InternalError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::verify_2fa_service(state, auth_context.0, payload.code)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/disable",
+    tag = "认证",
+    summary = "禁用 2FA",
+    responses(
+        (status = 200, description = "成功", body = Response<TwoFaDisableResponse>),
+        (status = 400, description = "未启用两步验证", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn disable_2fa(
     State(state): State<AppState>,
     auth_context: axum::Extension<AuthContext>,
 ) -> impl IntoResponse {
-    match crate::service::disable_2fa_service(state, auth_context.0).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::InternalError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::disable_2fa_service(state, auth_context.0)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/change-password",
+    tag = "认证",
+    summary = "修改当前账户密码，返回新的令牌",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<LoginResponse>),
+        (status = 401, description = "未登录、令牌已失效或原密码错误", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或新密码不满足密码策略", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn change_password(
     State(state): State<AppState>,
     auth_context: axum::Extension<AuthContext>,
-    Json(payload): Json<ChangeOwnPasswordRequest>,
+    ValidatedJson(payload): ValidatedJson<ChangePasswordRequest>,
 ) -> impl IntoResponse {
-    match crate::service::change_own_password_service(state, auth_context.0, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::InternalError.to_response(Some(e.to_string()))),
-        ),
-    }
+    crate::service::change_own_password_service(state, auth_context.0, payload)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    put,
+    path = "/auth/preferences",
+    tag = "认证",
+    summary = "设置当前账户的偏好语言",
+    request_body = UpdatePreferencesRequest,
+    responses((status = 200, description = "成功", body = Response<TupleUnit>)),
+    security(("bearer" = []))
+)]
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    auth_context: axum::Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<UpdatePreferencesRequest>,
+) -> impl IntoResponse {
+    crate::service::update_preferences_service(state, auth_context.0, payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/reset-password",
+    tag = "认证",
+    summary = "通过 2FA 验证码重置密码",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 400, description = "账户未启用两步验证", body = ErrorResponse),
+        (status = 401, description = "验证码错误", body = ErrorResponse),
+        (status = 404, description = "用户不存在", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或新密码不满足密码策略", body = ErrorResponse),
+    )
+)]
 pub async fn reset_password(
     state: State<AppState>,
-    Json(payload): Json<ResetPasswordRequest>,
+    ValidatedJson(payload): ValidatedJson<ResetPasswordRequest>,
+) -> impl IntoResponse {
+    crate::service::reset_password_service(state.0, payload)
+        .await
+        .map(Json)
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password-reset/request",
+    tag = "认证",
+    summary = "申请邮件重置密码",
+    request_body = PasswordResetRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 503, description = "未配置邮件发送，无法找回密码", body = ErrorResponse),
+    )
+)]
+pub async fn request_password_reset(
+    state: State<AppState>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    headers: HeaderMap,
+    ValidatedJson(payload): ValidatedJson<PasswordResetRequest>,
+) -> impl IntoResponse {
+    request_password_reset_service(state.0, payload, client_ip, user_agent(&headers))
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password-reset/confirm",
+    tag = "认证",
+    summary = "使用邮件中的令牌重置密码",
+    request_body = PasswordResetConfirmRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 400, description = "重置令牌无效或已过期", body = ErrorResponse),
+        (status = 403, description = "账户已禁用", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或新密码不满足密码策略", body = ErrorResponse),
+    )
+)]
+pub async fn confirm_password_reset(
+    state: State<AppState>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    headers: HeaderMap,
+    ValidatedJson(payload): ValidatedJson<PasswordResetConfirmRequest>,
 ) -> impl IntoResponse {
-    match crate::service::reset_password_service(state.0, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::OK,
-            Json(ResponseCode::InternalError.to_response(Some(e.to_string()))),
-        ),
-    }
+    confirm_password_reset_service(state.0, payload, client_ip, user_agent(&headers))
+        .await
+        .map(Json)
 }
@@ -0,0 +1,27 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::dto::{LivenessResponse, ReadinessResponse};
+use crate::response::{Response, ResponseCode};
+use crate::router::AppState;
+use crate::service::health_service::{liveness, readiness};
+
+/// 存活检查：进程能处理请求即返回 200，不访问数据库
+pub async fn healthz(State(state): State<AppState>) -> Json<Response<LivenessResponse>> {
+    Json(Response::ok_data(liveness(&state)))
+}
+
+/// 就绪检查：不就绪时返回 503，data 中列出各项检查结果
+pub async fn readyz(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<Response<ReadinessResponse>>) {
+    let report = readiness(&state).await;
+    if report.ready {
+        return (StatusCode::OK, Json(Response::ok_data(report)));
+    }
+
+    let code = ResponseCode::ServiceUnavailable;
+    (
+        code.status(),
+        Json(Response::from_code_with_data(code, None, report)),
+    )
+}
@@ -0,0 +1,123 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use utoipa::TupleUnit;
+use uuid::Uuid;
+
+use crate::dto::{
+    CreateIpRuleRequest, IpRuleListQuery, IpRuleListResponse, IpRuleResponse, UpdateIpRuleRequest,
+};
+use crate::extract::{ValidatedJson, ValidatedQuery};
+use crate::middleware::auth::AuthContext;
+use crate::middleware::client_ip::ClientIp;
+use crate::response::{ErrorResponse, Response};
+use crate::router::AppState;
+use crate::service::ip_rule_service::*;
+
+#[utoipa::path(
+    get,
+    path = "/ip-rules",
+    tag = "IP 规则",
+    summary = "查询 IP 规则列表",
+    params(IpRuleListQuery),
+    responses((status = 200, description = "成功", body = Response<IpRuleListResponse>)),
+    security(("bearer" = []))
+)]
+pub async fn list_ip_rule(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<IpRuleListQuery>,
+) -> impl IntoResponse {
+    list_ip_rule_service(state, query).await.map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/ip-rules/{id}",
+    tag = "IP 规则",
+    summary = "获取 IP 规则详情",
+    params(("id" = Uuid, Path, description = "IP 规则 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<IpRuleResponse>),
+        (status = 404, description = "规则不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_ip_rule(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    get_ip_rule_service(state, id).await.map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/ip-rules",
+    tag = "IP 规则",
+    summary = "创建 IP 规则",
+    request_body = CreateIpRuleRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<IpRuleResponse>),
+        (status = 403, description = "权限不足或规则生效后当前 IP 将无法访问", body = ErrorResponse),
+        (status = 404, description = "指定的管理员不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_ip_rule(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    ValidatedJson(payload): ValidatedJson<CreateIpRuleRequest>,
+) -> impl IntoResponse {
+    create_ip_rule_service(state, auth_context, client_ip, payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/ip-rules/{id}",
+    tag = "IP 规则",
+    summary = "更新 IP 规则",
+    request_body = UpdateIpRuleRequest,
+    params(("id" = Uuid, Path, description = "IP 规则 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<IpRuleResponse>),
+        (status = 403, description = "权限不足或规则生效后当前 IP 将无法访问", body = ErrorResponse),
+        (status = 404, description = "规则不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_ip_rule(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateIpRuleRequest>,
+) -> impl IntoResponse {
+    update_ip_rule_service(state, auth_context, client_ip, id, payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/ip-rules/{id}",
+    tag = "IP 规则",
+    summary = "删除 IP 规则",
+    params(("id" = Uuid, Path, description = "IP 规则 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 403, description = "权限不足或删除后当前 IP 将无法访问", body = ErrorResponse),
+        (status = 404, description = "规则不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_ip_rule(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    delete_ip_rule_service(state, auth_context, client_ip, id)
+        .await
+        .map(Json)
+}
@@ -0,0 +1,9 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::metrics::{CONTENT_TYPE, render};
+use crate::router::AppState;
+
+/// Prometheus 抓取地址
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], render(&state))
+}
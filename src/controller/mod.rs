@@ -1,18 +1,17 @@
 pub mod admin_controller;
 pub mod auth_controller;
+pub mod health_controller;
+pub mod ip_rule_controller;
+pub mod metrics_controller;
 pub mod permission_controller;
+pub mod rbac_controller;
 pub mod role_controller;
 pub mod system_info_controller;
+pub mod webauthn_controller;
 
 use crate::response::Response;
 use axum::{Json, http::StatusCode};
 
-pub(crate) use admin_controller::*;
-pub(crate) use auth_controller::*;
-pub(crate) use permission_controller::*;
-pub(crate) use role_controller::*;
-pub(crate) use system_info_controller::*;
-
 pub(crate) async fn root() -> (StatusCode, Json<Response<()>>) {
     (
         StatusCode::OK,
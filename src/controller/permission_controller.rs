@@ -1,7 +1,6 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     response::IntoResponse,
 };
 
@@ -9,71 +8,119 @@ use crate::dto::{
     CreatePermissionRequest, PermissionListQuery, PermissionListResponse, PermissionResponse,
     PermissionTreeResponse, UpdatePermissionRequest,
 };
-use crate::response::Response;
+use crate::extract::{ValidatedJson, ValidatedQuery};
+use crate::response::{ErrorResponse, Response};
 use crate::router::AppState;
 use crate::service::permission_service::*;
+use utoipa::TupleUnit;
 use uuid::Uuid;
 
+#[utoipa::path(
+    get,
+    path = "/permissions",
+    tag = "权限",
+    summary = "查询权限列表",
+    params(PermissionListQuery),
+    responses((status = 200, description = "成功", body = Response<PermissionListResponse>)),
+    security(("bearer" = []))
+)]
 pub async fn list_permission(
     State(state): State<AppState>,
-    Query(query): Query<PermissionListQuery>,
+    ValidatedQuery(query): ValidatedQuery<PermissionListQuery>,
 ) -> impl IntoResponse {
-    match list_permission_service(state, query).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(Response::failed(e.to_string())),
-        ),
-    }
+    list_permission_service(state, query).await.map(Json)
 }
 
+#[utoipa::path(
+    get,
+    path = "/permissions/tree",
+    tag = "权限",
+    summary = "获取权限树",
+    responses((status = 200, description = "成功", body = Response<Vec<PermissionTreeResponse>>)),
+    security(("bearer" = []))
+)]
 pub async fn get_permission_tree(State(state): State<AppState>) -> impl IntoResponse {
-    match get_permission_tree_service(state).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(Response::failed(e.to_string())),
-        ),
-    }
+    get_permission_tree_service(state).await.map(Json)
 }
 
+#[utoipa::path(
+    get,
+    path = "/permissions/{id}",
+    tag = "权限",
+    summary = "获取权限详情",
+    params(("id" = Uuid, Path, description = "权限 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<PermissionResponse>),
+        (status = 404, description = "权限不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn get_permission(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match get_permission_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    get_permission_service(state, id).await.map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/permissions",
+    tag = "权限",
+    summary = "创建权限",
+    request_body = CreatePermissionRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<PermissionResponse>),
+        (status = 409, description = "权限代码已存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_permission(
     State(state): State<AppState>,
-    Json(payload): Json<CreatePermissionRequest>,
+    ValidatedJson(payload): ValidatedJson<CreatePermissionRequest>,
 ) -> impl IntoResponse {
-    match create_permission_service(state, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    create_permission_service(state, payload).await.map(Json)
 }
 
+#[utoipa::path(
+    put,
+    path = "/permissions/{id}",
+    tag = "权限",
+    summary = "更新权限",
+    request_body = UpdatePermissionRequest,
+    params(("id" = Uuid, Path, description = "权限 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<PermissionResponse>),
+        (status = 403, description = "权限不足或系统内置权限不可修改", body = ErrorResponse),
+        (status = 404, description = "权限不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn update_permission(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdatePermissionRequest>,
+    ValidatedJson(payload): ValidatedJson<UpdatePermissionRequest>,
 ) -> impl IntoResponse {
-    match update_permission_service(state, id, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    update_permission_service(state, id, payload)
+        .await
+        .map(Json)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/permissions/{id}",
+    tag = "权限",
+    summary = "删除权限",
+    params(("id" = Uuid, Path, description = "权限 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 403, description = "权限不足或系统内置权限不可删除", body = ErrorResponse),
+        (status = 404, description = "权限不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn delete_permission(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    match delete_permission_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    delete_permission_service(state, id).await.map(Json)
 }
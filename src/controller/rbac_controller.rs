@@ -0,0 +1,44 @@
+use axum::{Json, extract::State};
+
+use crate::dto::{RbacDocument, RbacImportReport, RbacImportRequest};
+use crate::error::Result;
+use crate::extract::ValidatedJson;
+use crate::response::{ErrorResponse, Response};
+use crate::router::AppState;
+use crate::service::rbac_service::*;
+
+#[utoipa::path(
+    get,
+    path = "/rbac/export",
+    tag = "权限",
+    summary = "导出全部权限和角色定义",
+    responses((status = 200, description = "成功", body = Response<RbacDocument>)),
+    security(("bearer" = []))
+)]
+pub async fn export_rbac_document(
+    State(state): State<AppState>,
+) -> Result<Json<Response<RbacDocument>>> {
+    export_rbac_service(state).await.map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/rbac/import",
+    tag = "权限",
+    summary = "按声明式定义导入权限和角色",
+    description = "按代码匹配已有条目，`mode` 为 create / update / prune，`dry_run` 为 true 时只返回变更清单。系统内置条目不会被修改或删除。",
+    request_body = RbacImportRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<RbacImportReport>),
+        (status = 422, description = "文档格式或内容校验失败", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn import_rbac_document(
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RbacImportRequest>,
+) -> Result<Json<Response<RbacImportReport>>> {
+    import_rbac_service(state, payload.document, payload.mode, payload.dry_run)
+        .await
+        .map(Json)
+}
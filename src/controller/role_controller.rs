@@ -1,85 +1,135 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     response::IntoResponse,
 };
 
 use crate::dto::{
-    CreateRoleRequest, RoleDetailResponse, RoleListQuery, RoleListResponse, RoleResponse,
-    UpdateRoleRequest,
+    AssignPermissionsRequest, CreateRoleRequest, RoleDetailResponse, RoleListQuery,
+    RoleListResponse, RoleResponse, UpdateRoleRequest,
 };
-use crate::response::Response;
+use crate::error::Result;
+use crate::extract::{ValidatedJson, ValidatedQuery};
+use crate::response::{ErrorResponse, Response};
 use crate::router::AppState;
 use crate::service::role_service::*;
+use utoipa::TupleUnit;
 use uuid::Uuid;
 
+#[utoipa::path(
+    get,
+    path = "/roles",
+    tag = "角色",
+    summary = "查询角色列表",
+    params(RoleListQuery),
+    responses((status = 200, description = "成功", body = Response<RoleListResponse>)),
+    security(("bearer" = []))
+)]
 pub async fn list_role(
     State(state): State<AppState>,
-    Query(query): Query<RoleListQuery>,
-) -> (StatusCode, Json<Response<RoleListResponse>>) {
-    match list_role_service(state, query).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(Response::failed(e.to_string())),
-        ),
-    }
+    ValidatedQuery(query): ValidatedQuery<RoleListQuery>,
+) -> Result<Json<Response<RoleListResponse>>> {
+    list_role_service(state, query).await.map(Json)
 }
 
+#[utoipa::path(
+    get,
+    path = "/roles/{id}",
+    tag = "角色",
+    summary = "获取角色详情",
+    params(("id" = Uuid, Path, description = "角色 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<RoleDetailResponse>),
+        (status = 404, description = "角色不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn get_role(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
-    match get_role_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    get_role_service(state, id).await.map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/roles",
+    tag = "角色",
+    summary = "创建角色",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<RoleResponse>),
+        (status = 409, description = "角色代码已存在，或权限列表有重复", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或权限列表中有不存在的权限", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_role(
     State(state): State<AppState>,
-    Json(payload): Json<CreateRoleRequest>,
+    ValidatedJson(payload): ValidatedJson<CreateRoleRequest>,
 ) -> impl IntoResponse {
-    match create_role_service(state, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    create_role_service(state, payload).await.map(Json)
 }
 
+#[utoipa::path(
+    put,
+    path = "/roles/{id}",
+    tag = "角色",
+    summary = "更新角色",
+    request_body = UpdateRoleRequest,
+    params(("id" = Uuid, Path, description = "角色 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<RoleResponse>),
+        (status = 403, description = "权限不足或系统内置角色不可修改", body = ErrorResponse),
+        (status = 404, description = "角色不存在", body = ErrorResponse),
+        (status = 409, description = "权限列表有重复", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或权限列表中有不存在的权限", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn update_role(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateRoleRequest>,
+    ValidatedJson(payload): ValidatedJson<UpdateRoleRequest>,
 ) -> impl IntoResponse {
-    match update_role_service(state, id, payload).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    update_role_service(state, id, payload).await.map(Json)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/roles/{id}",
+    tag = "角色",
+    summary = "删除角色",
+    params(("id" = Uuid, Path, description = "角色 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 403, description = "权限不足或系统内置角色不可删除", body = ErrorResponse),
+        (status = 404, description = "角色不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn delete_role(State(state): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
-    match delete_role_service(state, id).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    delete_role_service(state, id).await.map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/roles/{id}/permissions",
+    tag = "角色",
+    summary = "全量替换角色的权限",
+    request_body = AssignPermissionsRequest,
+    params(("id" = Uuid, Path, description = "角色 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 404, description = "角色不存在", body = ErrorResponse),
+        (status = 409, description = "权限列表有重复", body = ErrorResponse),
+        (status = 422, description = "参数验证失败或权限列表中有不存在的权限", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn assign_permissions(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<serde_json::Value>,
+    ValidatedJson(payload): ValidatedJson<AssignPermissionsRequest>,
 ) -> impl IntoResponse {
-    let permission_ids: Vec<Uuid> = payload
-        .get("permission_ids")
-        .and_then(|v| v.as_array())
-        .and_then(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().and_then(|s| Uuid::parse_str(s).ok()))
-                .collect::<Vec<_>>()
-                .into()
-        })
-        .unwrap_or_default();
-
-    match assign_permissions_service(state, id, permission_ids).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (StatusCode::OK, Json(Response::failed(e.to_string()))),
-    }
+    assign_permissions_service(state, id, payload.permission_ids)
+        .await
+        .map(Json)
 }
@@ -1,24 +1,23 @@
-use axum::{
-    Json,
-    extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
-};
+use axum::{Json, extract::State, response::IntoResponse};
 
 use crate::dto::{SystemInfoQuery, SystemInfoResponse};
+use crate::extract::ValidatedQuery;
 use crate::response::Response;
 use crate::router::AppState;
 use crate::service::list_system_info_service;
 
+#[utoipa::path(
+    get,
+    path = "/systeminfo",
+    tag = "系统信息",
+    summary = "查询系统监控数据",
+    params(SystemInfoQuery),
+    responses((status = 200, description = "成功", body = Response<Vec<SystemInfoResponse>>)),
+    security(("bearer" = []))
+)]
 pub async fn list_system_info(
     State(state): State<AppState>,
-    Query(query): Query<SystemInfoQuery>,
+    ValidatedQuery(query): ValidatedQuery<SystemInfoQuery>,
 ) -> impl IntoResponse {
-    match list_system_info_service(state, query).await {
-        Ok(res) => (StatusCode::OK, Json(res)),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(Response::failed(e.to_string())),
-        ),
-    }
+    list_system_info_service(state, query).await.map(Json)
 }
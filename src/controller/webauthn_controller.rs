@@ -0,0 +1,100 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use utoipa::TupleUnit;
+use uuid::Uuid;
+
+use crate::dto::{
+    WebAuthnCredentialResponse, WebAuthnRegisterOptionsResponse, WebAuthnRegisterRequest,
+};
+use crate::extract::ValidatedJson;
+use crate::middleware::auth::AuthContext;
+use crate::response::{ErrorResponse, Response};
+use crate::router::AppState;
+use crate::service::webauthn_service::{
+    delete_credential_service, list_credentials_service, register_options_service, register_service,
+};
+
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register/options",
+    tag = "认证",
+    summary = "获取注册 WebAuthn 凭证的参数",
+    responses(
+        (status = 200, description = "成功", body = Response<WebAuthnRegisterOptionsResponse>),
+        (status = 400, description = "未启用 WebAuthn", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn webauthn_register_options(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+) -> impl IntoResponse {
+    register_options_service(state, auth_context)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register",
+    tag = "认证",
+    summary = "校验并保存 WebAuthn 凭证",
+    request_body = WebAuthnRegisterRequest,
+    responses(
+        (status = 200, description = "成功", body = Response<WebAuthnCredentialResponse>),
+        (status = 400, description = "未启用 WebAuthn、注册挑战无效或校验失败", body = ErrorResponse),
+        (status = 409, description = "凭证已注册", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn webauthn_register(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<WebAuthnRegisterRequest>,
+) -> impl IntoResponse {
+    register_service(state, auth_context, payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/webauthn/credentials",
+    tag = "认证",
+    summary = "当前账户的 WebAuthn 凭证",
+    responses((status = 200, description = "成功", body = Response<Vec<WebAuthnCredentialResponse>>)),
+    security(("bearer" = []))
+)]
+pub async fn list_webauthn_credentials(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+) -> impl IntoResponse {
+    list_credentials_service(state, auth_context)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/auth/webauthn/credentials/{id}",
+    tag = "认证",
+    summary = "删除 WebAuthn 凭证",
+    params(("id" = Uuid, Path, description = "凭证 ID")),
+    responses(
+        (status = 200, description = "成功", body = Response<TupleUnit>),
+        (status = 404, description = "凭证不存在", body = ErrorResponse),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_webauthn_credential(
+    State(state): State<AppState>,
+    Extension(auth_context): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    delete_credential_service(state, auth_context, id)
+        .await
+        .map(Json)
+}
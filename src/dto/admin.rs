@@ -1,61 +1,100 @@
 use chrono::{DateTime, Local};
-use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Serialize)]
+use crate::dto::validation::{USERNAME_PATTERN, validate_email_or_empty, validate_locale_or_empty};
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RoleSimple {
     pub id: Uuid,
     pub code: String,
     pub name: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct CreateAdminRequest {
+    #[validate(
+        length(min = 3, max = 32, message = "validation.username_length"),
+        regex(path = *USERNAME_PATTERN, message = "validation.username_chars")
+    )]
     pub username: String,
+    #[validate(email(message = "validation.email"))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, max = 128, message = "validation.password_length"))]
     pub password: String,
     pub is_super_admin: Option<bool>,
     pub role_ids: Option<Vec<Uuid>>,
+    /// 偏好语言 zh-CN / en-US，不传则按请求头协商
+    #[validate(custom(function = validate_locale_or_empty))]
+    pub locale: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateAdminRequest {
+    /// 传空字符串表示解绑邮箱
+    #[validate(custom(function = validate_email_or_empty))]
+    pub email: Option<String>,
+    #[validate(length(min = 1, max = 128, message = "validation.password_length"))]
     pub password: Option<String>,
+    /// 1 启用，0 禁用
+    #[validate(range(min = 0, max = 1, message = "validation.status"))]
     pub status: Option<i16>,
     pub role_ids: Option<Vec<Uuid>>,
+    /// 传空字符串表示清除偏好语言
+    #[validate(custom(function = validate_locale_or_empty))]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AssignRolesRequest {
+    /// 全量替换管理员的角色
+    pub role_ids: Vec<Uuid>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, max = 128, message = "validation.old_password_required"))]
     pub old_password: String,
+    #[validate(length(min = 1, max = 128, message = "validation.new_password_length"))]
     pub new_password: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AdminListQuery {
+    #[validate(range(min = 1, max = 10000, message = "validation.page"))]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100, message = "validation.page_size"))]
     pub page_size: Option<u64>,
+    #[validate(range(min = 0, max = 1, message = "validation.status"))]
     pub status: Option<i16>,
+    #[validate(length(max = 64, message = "validation.keyword_length"))]
     pub keyword: Option<String>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct AdminResponse {
     pub id: Uuid,
     pub username: String,
+    pub email: Option<String>,
     pub is_super_admin: bool,
     pub status: i16,
+    pub locale: Option<String>,
     pub last_login_at: Option<DateTime<Local>>,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct AdminDetailResponse {
     pub id: Uuid,
     pub username: String,
+    pub email: Option<String>,
     pub is_super_admin: bool,
     pub status: i16,
+    pub locale: Option<String>,
     pub last_login_at: Option<DateTime<Local>>,
     pub login_attempts: i32,
     pub locked_until: Option<DateTime<Local>>,
@@ -64,7 +103,7 @@ pub struct AdminDetailResponse {
     pub roles: Vec<RoleSimple>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct AdminListResponse {
     pub total: u64,
     pub page: u64,
@@ -1,39 +1,108 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
 
-#[derive(Debug, Deserialize)]
+use crate::dto::validation::validate_locale_or_empty;
+use crate::dto::{TwoFaMethod, WebAuthnAssertionOptions, WebAuthnAssertionRequest};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
+    #[validate(length(min = 1, max = 64, message = "validation.username_required"))]
     pub username: String,
+    #[validate(length(min = 1, max = 128, message = "validation.password_required"))]
     pub password: String,
+    /// 登录失败次数较多时需要提交的人机验证令牌
     #[serde(default)]
-    pub two_fa_code: Option<String>,
+    pub captcha_token: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct LoginTwoFaRequest {
+    #[validate(length(min = 1, max = 128, message = "validation.mfa_token_required"))]
+    pub mfa_token: String,
+    /// TOTP 验证码或备用码，使用 WebAuthn 时不需要
+    #[serde(default)]
+    #[validate(length(max = 32, message = "validation.code_required"))]
+    pub code: String,
+    #[serde(default)]
+    pub method: Option<TwoFaMethod>,
+    /// WebAuthn 断言，提交时按 WebAuthn 方式验证
+    #[serde(default)]
+    #[validate(nested)]
+    pub webauthn: Option<WebAuthnAssertionRequest>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct RefreshTokenRequest {
+    #[validate(length(min = 1, message = "validation.refresh_token_required"))]
     pub refresh_token: String,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct LoginResponse {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_in: i64,
+    /// 密码已超过最长使用期限，需要尽快修改
+    pub must_change_password: bool,
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct MfaChallengeResponse {
+    pub mfa_token: String,
+    pub mfa_methods: Vec<TwoFaMethod>,
+    pub expires_in: i64,
+    /// 已注册 WebAuthn 凭证时返回
+    pub webauthn: Option<WebAuthnAssertionOptions>,
 }
 
-#[derive(Debug, Serialize, Default)]
+/// 登录结果：直接签发令牌，或密码正确但仍需完成 2FA 挑战
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum LoginResult {
+    Tokens(LoginResponse),
+    MfaRequired(MfaChallengeResponse),
+}
+
+impl Default for LoginResult {
+    fn default() -> Self {
+        LoginResult::Tokens(LoginResponse::default())
+    }
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct RefreshTokenResponse {
     pub access_token: String,
     pub expires_in: i64,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ChangeOwnPasswordRequest {
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdatePreferencesRequest {
+    /// 偏好语言 zh-CN / en-US，传 null 或空字符串表示恢复按 Accept-Language 协商
+    #[validate(custom(function = validate_locale_or_empty))]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct PasswordResetRequest {
+    #[validate(email(message = "validation.email"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct PasswordResetConfirmRequest {
+    #[validate(length(min = 1, max = 128, message = "validation.reset_token_required"))]
+    pub token: String,
+    #[validate(length(min = 1, max = 128, message = "validation.new_password_length"))]
     pub new_password: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, max = 64, message = "validation.username_required"))]
     pub username: String,
+    #[validate(length(equal = 6, message = "validation.two_fa_code"))]
     pub two_fa_code: String,
+    #[validate(length(min = 1, max = 128, message = "validation.new_password_length"))]
     pub new_password: String,
 }
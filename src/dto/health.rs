@@ -0,0 +1,22 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    pub version: String,
+    /// 进程已运行的秒数
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    /// 数据库是否可以执行查询
+    pub database: bool,
+    /// 最近一次已执行的迁移，数据库不可用时为空
+    pub migration: Option<String>,
+    /// 尚未执行的迁移，非空时不就绪
+    pub pending_migrations: Vec<String>,
+    /// 已收到退出信号，正在等待处理中的请求完成
+    pub shutting_down: bool,
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::validation::{validate_cidr, validate_ip_rule_type};
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateIpRuleRequest {
+    /// allow / deny
+    #[validate(custom(function = validate_ip_rule_type))]
+    pub rule_type: String,
+    /// CIDR 网段或单个 IP
+    #[validate(custom(function = validate_cidr))]
+    pub cidr: String,
+    /// 为空表示全局规则
+    pub admin_id: Option<Uuid>,
+    #[validate(length(max = 255, message = "validation.description_length"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateIpRuleRequest {
+    #[validate(custom(function = validate_ip_rule_type))]
+    pub rule_type: Option<String>,
+    #[validate(custom(function = validate_cidr))]
+    pub cidr: Option<String>,
+    #[validate(length(max = 255, message = "validation.description_length"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IpRuleListQuery {
+    #[validate(range(min = 1, max = 10000, message = "validation.page"))]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100, message = "validation.page_size"))]
+    pub page_size: Option<u64>,
+    pub admin_id: Option<Uuid>,
+    /// true 时只返回全局规则
+    pub global: Option<bool>,
+    #[validate(custom(function = validate_ip_rule_type))]
+    pub rule_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct IpRuleResponse {
+    pub id: Uuid,
+    pub admin_id: Option<Uuid>,
+    pub rule_type: String,
+    pub cidr: String,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct IpRuleListResponse {
+    pub total: u64,
+    pub page: u64,
+    pub page_size: u64,
+    pub list: Vec<IpRuleResponse>,
+}
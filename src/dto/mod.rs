@@ -1,13 +1,20 @@
 pub mod admin;
 pub mod auth;
+pub mod health;
+pub mod ip_rule;
 pub mod permission;
+pub mod rbac;
 pub mod role;
 pub mod system_info;
 pub mod two_fa;
+pub mod validation;
 
 pub use admin::*;
 pub use auth::*;
+pub use health::*;
+pub use ip_rule::*;
 pub use permission::*;
+pub use rbac::*;
 pub use role::*;
 pub use system_info::*;
 pub use two_fa::*;
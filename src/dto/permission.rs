@@ -1,39 +1,85 @@
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize)]
+use crate::dto::validation::{
+    CODE_PATTERN, validate_http_method, validate_resource_path, validate_resource_type,
+};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = validate_api_permission))]
 pub struct CreatePermissionRequest {
+    #[validate(
+        length(min = 2, max = 64, message = "validation.permission_code_length"),
+        regex(path = *CODE_PATTERN, message = "validation.permission_code_chars")
+    )]
     pub code: String,
+    #[validate(length(min = 1, max = 128, message = "validation.permission_name_length"))]
     pub name: String,
+    #[validate(length(max = 255, message = "validation.description_length"))]
     pub description: Option<String>,
+    #[validate(custom(function = validate_resource_type))]
     pub resource_type: String,
+    #[validate(custom(function = validate_http_method))]
     pub http_method: Option<String>,
+    #[validate(
+        length(max = 255, message = "validation.resource_path_length"),
+        custom(function = validate_resource_path)
+    )]
     pub resource_path: Option<String>,
     pub parent_id: Option<Uuid>,
+    #[validate(range(min = 0, message = "validation.sort_order"))]
     pub sort_order: Option<i32>,
 }
 
-#[derive(Debug, Deserialize)]
+/// api 类型的权限按请求方法和路径匹配，两者缺一不可
+fn validate_api_permission(payload: &CreatePermissionRequest) -> Result<(), ValidationError> {
+    if payload.resource_type == "api"
+        && (payload.http_method.is_none() || payload.resource_path.is_none())
+    {
+        return Err(
+            ValidationError::new("api_permission").with_message("validation.api_permission".into())
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdatePermissionRequest {
+    #[validate(length(min = 1, max = 128, message = "validation.permission_name_length"))]
     pub name: Option<String>,
+    #[validate(length(max = 255, message = "validation.description_length"))]
     pub description: Option<String>,
+    #[validate(custom(function = validate_resource_type))]
     pub resource_type: Option<String>,
+    #[validate(custom(function = validate_http_method))]
     pub http_method: Option<String>,
+    #[validate(
+        length(max = 255, message = "validation.resource_path_length"),
+        custom(function = validate_resource_path)
+    )]
     pub resource_path: Option<String>,
     pub parent_id: Option<Uuid>,
+    #[validate(range(min = 0, message = "validation.sort_order"))]
     pub sort_order: Option<i32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PermissionListQuery {
+    #[validate(range(min = 1, max = 10000, message = "validation.page"))]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100, message = "validation.page_size"))]
     pub page_size: Option<u64>,
+    #[validate(custom(function = validate_resource_type))]
     pub resource_type: Option<String>,
+    #[validate(length(max = 64, message = "validation.keyword_length"))]
     pub keyword: Option<String>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct PermissionResponse {
     pub id: Uuid,
     pub code: String,
@@ -49,7 +95,7 @@ pub struct PermissionResponse {
     pub updated_at: DateTime<Local>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct PermissionTreeResponse {
     pub id: Uuid,
     pub code: String,
@@ -60,10 +106,11 @@ pub struct PermissionTreeResponse {
     pub resource_path: Option<String>,
     pub sort_order: i32,
     pub is_system: bool,
+    #[schema(no_recursion)]
     pub children: Vec<PermissionTreeResponse>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct PermissionListResponse {
     pub total: u64,
     pub page: u64,
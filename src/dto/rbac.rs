@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::dto::validation::{
+    CODE_PATTERN, validate_http_method, validate_resource_path, validate_resource_type,
+};
+
+/// 声明式的权限与角色定义，可用 YAML 或 JSON 描述；权限之间、角色与权限之间都按代码引用，
+/// 便于在不同环境之间迁移和纳入版本管理
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RbacDocument {
+    #[serde(default)]
+    pub permissions: Vec<PermissionDefinition>,
+    #[serde(default)]
+    pub roles: Vec<RoleDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = validate_api_definition))]
+pub struct PermissionDefinition {
+    #[validate(
+        length(min = 2, max = 64, message = "validation.permission_code_length"),
+        regex(path = *CODE_PATTERN, message = "validation.permission_code_chars")
+    )]
+    pub code: String,
+    #[validate(length(min = 1, max = 128, message = "validation.permission_name_length"))]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 255, message = "validation.description_length"))]
+    pub description: Option<String>,
+    /// api / menu / button
+    #[validate(custom(function = validate_resource_type))]
+    pub resource_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = validate_http_method))]
+    pub http_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(
+        length(max = 255, message = "validation.resource_path_length"),
+        custom(function = validate_resource_path)
+    )]
+    pub resource_path: Option<String>,
+    /// 父权限代码，可引用本文件或数据库中已有的权限
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0, message = "validation.sort_order"))]
+    pub sort_order: Option<i32>,
+}
+
+fn validate_api_definition(definition: &PermissionDefinition) -> Result<(), ValidationError> {
+    if definition.resource_type == "api"
+        && (definition.http_method.is_none() || definition.resource_path.is_none())
+    {
+        return Err(
+            ValidationError::new("api_permission").with_message("validation.api_permission".into())
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoleDefinition {
+    #[validate(
+        length(min = 2, max = 32, message = "validation.role_code_length"),
+        regex(path = *CODE_PATTERN, message = "validation.role_code_chars")
+    )]
+    pub code: String,
+    #[validate(length(min = 1, max = 64, message = "validation.role_name_length"))]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 255, message = "validation.description_length"))]
+    pub description: Option<String>,
+    /// 权限代码，导入时全量替换角色现有的权限
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+/// 导入模式：`create` 只新增缺少的条目；`update` 同时更新已有条目；
+/// `prune` 在 `update` 的基础上删除文件中没有的非内置条目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RbacImportMode {
+    Create,
+    #[default]
+    Update,
+    Prune,
+}
+
+impl FromStr for RbacImportMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create" => Ok(Self::Create),
+            "update" => Ok(Self::Update),
+            "prune" => Ok(Self::Prune),
+            other => Err(format!(
+                "未知的导入模式: {}（可选 create、update、prune）",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RbacImportRequest {
+    /// 默认 update
+    #[serde(default)]
+    pub mode: RbacImportMode,
+    /// 只返回变更清单，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+    pub document: RbacDocument,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RbacEntryKind {
+    Permission,
+    Role,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RbacChangeAction {
+    Create,
+    Update,
+    Delete,
+    /// 系统内置条目与文件不一致，但不允许修改
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RbacChange {
+    pub kind: RbacEntryKind,
+    pub code: String,
+    pub action: RbacChangeAction,
+    /// 字段级差异，如 `name: "旧" -> "新"`、`permissions: +a -b`
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RbacImportReport {
+    pub mode: RbacImportMode,
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub skipped: usize,
+    /// 只列出有变化的条目，与数据库一致的条目不出现
+    pub changes: Vec<RbacChange>,
+}
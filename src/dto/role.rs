@@ -1,30 +1,52 @@
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Clone, Deserialize)]
+use crate::dto::validation::CODE_PATTERN;
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct CreateRoleRequest {
+    #[validate(
+        length(min = 2, max = 32, message = "validation.role_code_length"),
+        regex(path = *CODE_PATTERN, message = "validation.role_code_chars")
+    )]
     pub code: String,
+    #[validate(length(min = 1, max = 64, message = "validation.role_name_length"))]
     pub name: String,
+    #[validate(length(max = 255, message = "validation.description_length"))]
     pub description: Option<String>,
     pub permission_ids: Option<Vec<Uuid>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateRoleRequest {
+    #[validate(length(min = 1, max = 64, message = "validation.role_name_length"))]
     pub name: Option<String>,
+    #[validate(length(max = 255, message = "validation.description_length"))]
     pub description: Option<String>,
     pub permission_ids: Option<Vec<Uuid>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AssignPermissionsRequest {
+    /// 全量替换角色的权限
+    pub permission_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RoleListQuery {
+    #[validate(range(min = 1, max = 10000, message = "validation.page"))]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100, message = "validation.page_size"))]
     pub page_size: Option<u64>,
+    #[validate(length(max = 64, message = "validation.keyword_length"))]
     pub keyword: Option<String>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct RoleResponse {
     pub id: Uuid,
     pub code: String,
@@ -35,7 +57,7 @@ pub struct RoleResponse {
     pub updated_at: DateTime<Local>,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct RoleDetailResponse {
     pub id: Uuid,
     pub code: String,
@@ -47,14 +69,14 @@ pub struct RoleDetailResponse {
     pub updated_at: DateTime<Local>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PermissionSimple {
     pub id: Uuid,
     pub code: String,
     pub name: String,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct RoleListResponse {
     pub total: u64,
     pub page: u64,
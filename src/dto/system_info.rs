@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SystemInfoQuery {
+    #[validate(range(min = 1, max = 100, message = "validation.limit"))]
     pub limit: Option<i64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SystemInfoResponse {
     pub id: uuid::Uuid,
     pub cpu_count: i32,
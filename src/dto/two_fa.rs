@@ -1,23 +1,101 @@
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TwoFaMethod {
+    Totp,
+    BackupCode,
+    #[serde(rename = "webauthn")]
+    WebAuthn,
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct TwoFaSetupResponse {
     pub secret: String,
     pub qr_code_url: String,
     pub backup_codes: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct TwoFaVerifyRequest {
+    #[validate(length(equal = 6, message = "validation.two_fa_code"))]
     pub code: String,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct TwoFaVerifyResponse {
     pub verified: bool,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, ToSchema)]
 pub struct TwoFaDisableResponse {
     pub disabled: bool,
 }
+
+/// 调用 `navigator.credentials.create()` 所需的参数，二进制字段均为 base64url 编码
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct WebAuthnRegisterOptionsResponse {
+    pub challenge: String,
+    pub rp_id: String,
+    pub rp_name: String,
+    /// 对应 `user.id`
+    pub user_id: String,
+    pub user_name: String,
+    /// 支持的 COSE 算法，按优先顺序填入 `pubKeyCredParams`
+    pub algorithms: Vec<i32>,
+    /// 已注册的凭证 ID，填入 `excludeCredentials` 避免重复注册
+    pub exclude_credentials: Vec<String>,
+    pub timeout_ms: u64,
+}
+
+/// 注册结果，取自 `AuthenticatorAttestationResponse`，二进制字段均为 base64url 编码
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct WebAuthnRegisterRequest {
+    #[validate(length(min = 1, max = 64, message = "validation.webauthn_name"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub client_data_json: String,
+    /// `getAuthenticatorData()` 的返回值
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub authenticator_data: String,
+    /// `getPublicKey()` 返回的 SPKI 公钥
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub public_key: String,
+    /// `getPublicKeyAlgorithm()` 的返回值
+    pub public_key_algorithm: i32,
+}
+
+/// 登录时 `navigator.credentials.get()` 的结果，二进制字段均为 base64url 编码
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct WebAuthnAssertionRequest {
+    #[validate(length(min = 1, max = 1400, message = "validation.webauthn_field"))]
+    pub credential_id: String,
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub client_data_json: String,
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub authenticator_data: String,
+    #[validate(length(min = 1, max = 4096, message = "validation.webauthn_field"))]
+    pub signature: String,
+}
+
+/// 调用 `navigator.credentials.get()` 所需的参数，`challenge` 即登录返回的 `mfa_token`
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct WebAuthnAssertionOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub allow_credentials: Vec<String>,
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Default, ToSchema)]
+pub struct WebAuthnCredentialResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub algorithm: i32,
+    pub created_at: DateTime<Local>,
+    pub last_used_at: Option<DateTime<Local>>,
+}
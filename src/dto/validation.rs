@@ -0,0 +1,135 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use validator::{ValidateEmail, ValidationError};
+
+use crate::i18n::Locale;
+use crate::service::ip_rule_service::parse_cidr;
+
+/// 用户名：字母、数字、下划线、点和连字符
+pub static USERNAME_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_.-]+$").unwrap());
+
+/// 角色和权限代码：字母开头，可包含字母、数字、下划线、冒号、点和连字符
+pub static CODE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9_:.-]*$").unwrap());
+
+pub const RESOURCE_TYPES: &[&str] = &["api", "menu", "button"];
+pub const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+fn invalid(code: &'static str, message: &'static str) -> ValidationError {
+    ValidationError::new(code).with_message(message.into())
+}
+
+pub fn validate_resource_type(value: &str) -> Result<(), ValidationError> {
+    if RESOURCE_TYPES.contains(&value) {
+        Ok(())
+    } else {
+        Err(invalid("resource_type", "validation.resource_type"))
+    }
+}
+
+/// 权限检查时忽略大小写，这里同样不区分大小写
+pub fn validate_http_method(value: &str) -> Result<(), ValidationError> {
+    if HTTP_METHODS.contains(&value.to_uppercase().as_str()) {
+        Ok(())
+    } else {
+        Err(invalid("http_method", "validation.http_method"))
+    }
+}
+
+/// 资源路径以 `/` 开头，或为匹配全部路径的 `*`
+pub fn validate_resource_path(value: &str) -> Result<(), ValidationError> {
+    if value == "*" || (value.starts_with('/') && !value.chars().any(char::is_whitespace)) {
+        Ok(())
+    } else {
+        Err(invalid("resource_path", "validation.resource_path"))
+    }
+}
+
+/// 空字符串表示解绑邮箱，其余值必须是合法邮箱
+pub fn validate_email_or_empty(value: &str) -> Result<(), ValidationError> {
+    let value = value.trim();
+    if value.is_empty() || value.validate_email() {
+        Ok(())
+    } else {
+        Err(invalid("email", "validation.email"))
+    }
+}
+
+/// 空字符串表示清除偏好语言，其余值必须是支持的语言标签
+pub fn validate_locale_or_empty(value: &str) -> Result<(), ValidationError> {
+    let value = value.trim();
+    if value.is_empty() || value.parse::<Locale>().is_ok() {
+        Ok(())
+    } else {
+        Err(invalid("locale", "validation.locale"))
+    }
+}
+
+pub fn validate_ip_rule_type(value: &str) -> Result<(), ValidationError> {
+    if matches!(value, "allow" | "deny") {
+        Ok(())
+    } else {
+        Err(invalid("rule_type", "validation.ip_rule_type"))
+    }
+}
+
+pub fn validate_cidr(value: &str) -> Result<(), ValidationError> {
+    parse_cidr(value)
+        .map(|_| ())
+        .map_err(|_| invalid("cidr", "validation.cidr"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        assert!(USERNAME_PATTERN.is_match("guardian.admin-01"));
+        assert!(!USERNAME_PATTERN.is_match("guardian admin"));
+        assert!(CODE_PATTERN.is_match("ADMIN_READ"));
+        assert!(CODE_PATTERN.is_match("admin:read"));
+        assert!(!CODE_PATTERN.is_match("1ADMIN"));
+        assert!(!CODE_PATTERN.is_match("ADMIN READ"));
+    }
+
+    #[test]
+    fn test_custom_validators() {
+        assert!(validate_resource_type("menu").is_ok());
+        assert!(validate_resource_type("page").is_err());
+        assert!(validate_http_method("get").is_ok());
+        assert!(validate_http_method("TRACE").is_err());
+        assert!(validate_resource_path("/guardian-auth/v1/admins/{id}").is_ok());
+        assert!(validate_resource_path("*").is_ok());
+        assert!(validate_resource_path("admins").is_err());
+        assert!(validate_email_or_empty("").is_ok());
+        assert!(validate_email_or_empty("a@example.com").is_ok());
+        assert!(validate_email_or_empty("not-an-email").is_err());
+        assert!(validate_locale_or_empty("en-US").is_ok());
+        assert!(validate_locale_or_empty("").is_ok());
+        assert!(validate_locale_or_empty("fr-FR").is_err());
+        assert!(validate_cidr("10.0.0.0/8").is_ok());
+        assert!(validate_cidr("office").is_err());
+    }
+
+    #[test]
+    fn test_page_range() {
+        use crate::dto::IpRuleListQuery;
+        use validator::Validate;
+
+        let query = |page| IpRuleListQuery {
+            page: Some(page),
+            page_size: Some(100),
+            admin_id: None,
+            global: None,
+            rule_type: None,
+        };
+        assert!(query(1).validate().is_ok());
+        assert!(query(10000).validate().is_ok());
+        // 页码过大时偏移量会溢出，直接拒绝
+        assert!(query(0).validate().is_err());
+        assert!(query(u64::MAX).validate().is_err());
+    }
+}
@@ -10,6 +10,8 @@ pub struct Model {
     pub id: Uuid,
     #[sea_orm(unique)]
     pub username: String,
+    #[sea_orm(unique)]
+    pub email: Option<String>,
     pub password_hash: String,
     #[sea_orm(column_type = "Text", nullable)]
     pub two_fa_secret: Option<String>,
@@ -18,6 +20,9 @@ pub struct Model {
     pub last_login_at: Option<DateTimeWithTimeZone>,
     pub login_attempts: Option<i32>,
     pub locked_until: Option<DateTimeWithTimeZone>,
+    pub password_changed_at: Option<DateTimeWithTimeZone>,
+    pub must_change_password: Option<bool>,
+    pub locale: Option<String>,
     pub created_at: Option<DateTimeWithTimeZone>,
     pub updated_at: Option<DateTimeWithTimeZone>,
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use crate::entities::{admin_roles, admins, audit_logs, permissions, role_permissions, roles};
 use sea_orm::{Linked, RelationDef, RelationTrait};
 
@@ -2,12 +2,19 @@
 
 pub mod prelude;
 
+pub mod admin_backup_codes;
 pub mod admin_roles;
 pub mod admins;
 pub mod audit_logs;
 pub mod guardian_systeminfo;
+pub mod ip_rules;
 pub mod links;
+pub mod mfa_challenges;
+pub mod password_history;
+pub mod password_reset_tokens;
 pub mod permissions;
 pub mod role_permissions;
 pub mod roles;
 pub mod token_blacklist;
+pub mod webauthn_challenges;
+pub mod webauthn_credentials;
@@ -1,9 +1,18 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+#![allow(unused_imports)]
+
+pub use super::admin_backup_codes::Entity as AdminBackupCodes;
 pub use super::admin_roles::Entity as AdminRoles;
 pub use super::admins::Entity as Admins;
 pub use super::audit_logs::Entity as AuditLogs;
+pub use super::ip_rules::Entity as IpRules;
+pub use super::mfa_challenges::Entity as MfaChallenges;
+pub use super::password_history::Entity as PasswordHistory;
+pub use super::password_reset_tokens::Entity as PasswordResetTokens;
 pub use super::permissions::Entity as Permissions;
 pub use super::role_permissions::Entity as RolePermissions;
 pub use super::roles::Entity as Roles;
 pub use super::token_blacklist::Entity as TokenBlacklist;
+pub use super::webauthn_challenges::Entity as WebauthnChallenges;
+pub use super::webauthn_credentials::Entity as WebauthnCredentials;
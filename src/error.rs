@@ -1,15 +1,260 @@
-use std::io;
+use std::collections::BTreeMap;
 
+use axum::{
+    Json,
+    extract::rejection::{JsonRejection, QueryRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response as AxumResponse},
+};
+use log::error;
+use sea_orm::{DbErr, SqlErr, TransactionError};
 use thiserror::Error;
+use validator::ValidationErrors;
 
+use crate::i18n::{t, translate_current};
+use crate::response::{Response, ResponseCode};
+
+pub type Result<T, E = GuardianError> = std::result::Result<T, E>;
+
+/// 业务层统一错误类型，经 `IntoResponse` 转为对应的 HTTP 状态码和业务错误码
 #[derive(Error, Debug)]
-pub enum DataStoreError {
-    #[error("data store disconnected")]
-    Disconnect(#[from] io::Error),
-    #[error("the data for key `{0}` is not available")]
-    Redaction(String),
-    #[error("invalid header (expected {expected:?}, found {found:?})")]
-    InvalidHeader { expected: String, found: String },
-    #[error("unknown data store error")]
-    Unknown,
+pub enum GuardianError {
+    /// 资源不存在，404
+    #[error("{0}")]
+    NotFound(String),
+    /// 唯一约束冲突，409
+    #[error("{0}")]
+    Conflict(String),
+    /// 参数校验失败，422
+    #[error("{0}")]
+    Validation(String),
+    /// 认证失败，401
+    #[error("{0}")]
+    Unauthorized(String),
+    /// 无权执行该操作，403
+    #[error("{0}")]
+    Forbidden(String),
+    /// 请求过于频繁，429
+    #[error("{0}")]
+    RateLimited(String),
+    /// 不满足业务规则的请求，400
+    #[error("{0}")]
+    BadRequest(String),
+    /// 请求体或查询参数无法解析，沿用 axum 给出的 HTTP 状态码
+    #[error("{1}")]
+    Rejected(StatusCode, String),
+    /// 字段校验失败，响应的 data 中按字段返回错误信息
+    #[error("{}", summarize_field_errors(.0))]
+    InvalidFields(ValidationErrors),
+    /// 携带指定业务错误码的失败，HTTP 状态码由错误码决定
+    #[error("{}", .1.clone().unwrap_or_else(|| .0.msg()))]
+    Code(ResponseCode, Option<String>),
+    /// 数据库错误，详情只写日志
+    #[error("数据库错误: {0}")]
+    Database(#[from] DbErr),
+    /// 其他内部错误，详情只写日志
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl GuardianError {
+    pub fn code(&self) -> ResponseCode {
+        match self {
+            GuardianError::NotFound(_) => ResponseCode::ResourceNotFound,
+            GuardianError::Conflict(_) => ResponseCode::ResourceConflict,
+            GuardianError::Validation(_)
+            | GuardianError::Rejected(..)
+            | GuardianError::InvalidFields(_) => ResponseCode::ValidationError,
+            GuardianError::Unauthorized(_) => ResponseCode::AuthError,
+            GuardianError::Forbidden(_) => ResponseCode::PermissionDenied,
+            GuardianError::RateLimited(_) => ResponseCode::RateLimitExceeded,
+            GuardianError::BadRequest(_) => ResponseCode::GenericError,
+            GuardianError::Code(code, _) => *code,
+            GuardianError::Database(_) | GuardianError::Internal(_) => ResponseCode::InternalError,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            GuardianError::Rejected(status, _) => *status,
+            other => other.code().status(),
+        }
+    }
+
+    /// 返回给客户端的提示信息，内部错误不暴露细节
+    pub fn public_message(&self) -> Option<String> {
+        match self {
+            GuardianError::Database(_) | GuardianError::Internal(_) => None,
+            GuardianError::Code(_, msg) => msg.clone(),
+            other => Some(other.to_string()),
+        }
+    }
+}
+
+/// 把数据库约束冲突转为业务错误，`constraints` 为约束名到消息 key 的映射。
+///
+/// 唯一约束冲突返回 409，外键约束冲突说明引用的记录不存在，返回 422；
+/// 未列出的约束使用通用提示，其他数据库错误保持为内部错误。
+pub fn constraint_error(err: DbErr, constraints: &[(&str, &str)]) -> GuardianError {
+    let message = |detail: &str, fallback: &str| {
+        let key = constraints
+            .iter()
+            .find(|(constraint, _)| detail.contains(constraint))
+            .map_or(fallback, |(_, key)| key);
+        t!(key)
+    };
+
+    match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(detail)) => {
+            GuardianError::Conflict(message(&detail, "code.resource_conflict"))
+        }
+        Some(SqlErr::ForeignKeyConstraintViolation(detail)) => {
+            GuardianError::Validation(message(&detail, "validation.reference_not_found"))
+        }
+        _ => err.into(),
+    }
+}
+
+/// 事务失败时的错误转换，规则同 [`constraint_error`]
+pub fn transaction_error(
+    err: TransactionError<DbErr>,
+    constraints: &[(&str, &str)],
+) -> GuardianError {
+    let (TransactionError::Connection(err) | TransactionError::Transaction(err)) = err;
+    constraint_error(err, constraints)
+}
+
+// 不用 `#[from]`：那样会把 ValidationErrors 作为 source，`{:#}` 输出时字段错误重复一遍
+impl From<ValidationErrors> for GuardianError {
+    fn from(errors: ValidationErrors) -> Self {
+        GuardianError::InvalidFields(errors)
+    }
+}
+
+impl From<JsonRejection> for GuardianError {
+    fn from(rejection: JsonRejection) -> Self {
+        GuardianError::Rejected(
+            rejection.status(),
+            t!("request.invalid_body", detail = rejection.body_text()),
+        )
+    }
+}
+
+impl From<QueryRejection> for GuardianError {
+    fn from(rejection: QueryRejection) -> Self {
+        GuardianError::Rejected(
+            rejection.status(),
+            t!("request.invalid_query", detail = rejection.body_text()),
+        )
+    }
+}
+
+/// 字段名到错误信息列表，message 视为消息 key 按当前语言渲染，未设置 message 的规则使用规则代码
+pub fn field_error_messages(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errors)| {
+            let messages = errors
+                .iter()
+                .map(|error| match &error.message {
+                    Some(key) => translate_current(key, &[]),
+                    None => error.code.to_string(),
+                })
+                .collect();
+            (field.to_string(), messages)
+        })
+        .collect()
+}
+
+fn summarize_field_errors(errors: &ValidationErrors) -> String {
+    field_error_messages(errors)
+        .into_iter()
+        .map(|(field, messages)| format!("{}: {}", field, messages.join("，")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl IntoResponse for GuardianError {
+    fn into_response(self) -> AxumResponse {
+        if matches!(
+            self,
+            GuardianError::Database(_) | GuardianError::Internal(_)
+        ) {
+            error!("请求处理失败: {:?}", self);
+        }
+
+        if let GuardianError::InvalidFields(errors) = &self {
+            let body = self
+                .code()
+                .to_response_with_data(self.public_message(), field_error_messages(errors));
+            return (self.status(), Json(body)).into_response();
+        }
+
+        let body: Response<()> = self.code().to_response(self.public_message());
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_mapping() {
+        assert_eq!(
+            GuardianError::NotFound("x".into()).status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            GuardianError::Conflict("x".into()).status(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            GuardianError::Validation("x".into()).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            GuardianError::Code(ResponseCode::IpNotAllowed, None).status(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[test]
+    fn test_internal_message_hidden() {
+        let err = GuardianError::Database(DbErr::Custom("relation does not exist".into()));
+        assert_eq!(err.code(), ResponseCode::InternalError);
+        assert_eq!(err.public_message(), None);
+
+        let err = constraint_error(
+            DbErr::Custom("connection reset".into()),
+            &[("guardian_admins_username_key", "admin.username_taken")],
+        );
+        assert!(matches!(err, GuardianError::Database(_)));
+
+        let err = GuardianError::Conflict("用户名已存在".into());
+        assert_eq!(err.public_message().as_deref(), Some("用户名已存在"));
+    }
+
+    #[test]
+    fn test_field_errors() {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "username",
+            validator::ValidationError::new("length")
+                .with_message("用户名长度为 3-32 个字符".into()),
+        );
+        errors.add("code", validator::ValidationError::new("regex"));
+
+        let messages = field_error_messages(&errors);
+        assert_eq!(messages["username"], vec!["用户名长度为 3-32 个字符"]);
+        assert_eq!(messages["code"], vec!["regex"]);
+
+        let err = GuardianError::from(errors);
+        assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            err.to_string(),
+            "code: regex; username: 用户名长度为 3-32 个字符"
+        );
+    }
 }
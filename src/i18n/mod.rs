@@ -0,0 +1,238 @@
+//! 响应消息的多语言支持
+//!
+//! 消息目录按 key 存放在 `locales/*.json` 中，编译时嵌入二进制。每个请求的语言由
+//! `locale_middleware` 根据 `Accept-Language` 协商，认证后再由管理员的偏好语言覆盖，
+//! 业务代码通过 [`t!`] 按当前请求的语言渲染消息。
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    future::Future,
+    str::FromStr,
+    sync::{LazyLock, OnceLock},
+};
+
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    ZhCn,
+    EnUs,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::ZhCn, Locale::EnUs];
+
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// 按主语言匹配，zh、zh-CN、zh-Hans 都视为简体中文，en 开头的都视为英文
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?;
+        if primary.eq_ignore_ascii_case("zh") {
+            Some(Locale::ZhCn)
+        } else if primary.eq_ignore_ascii_case("en") {
+            Some(Locale::EnUs)
+        } else {
+            None
+        }
+    }
+
+    /// 解析 `Accept-Language`，按 q 值从高到低选出第一个支持的语言
+    pub fn negotiate(header: &str) -> Option<Self> {
+        let mut candidates: Vec<(f32, Locale)> = header
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let locale = Locale::from_tag(parts.next()?)?;
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((quality, locale))
+            })
+            .collect();
+
+        // 稳定排序，q 值相同时保留请求头中的先后顺序
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates.first().map(|(_, locale)| *locale)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// 严格匹配支持的语言标签，用于校验管理员保存的偏好
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("不支持的语言: {}", s))
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// 规范化管理员保存的偏好语言，空字符串或不支持的值视为未设置
+pub fn normalize_locale(value: &str) -> Option<String> {
+    value
+        .parse::<Locale>()
+        .ok()
+        .map(|locale| locale.tag().to_string())
+}
+
+static DEFAULT_LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// 启动时按 `server.default_locale` 设置一次，之后的调用不生效
+pub fn set_default_locale(locale: Locale) {
+    let _ = DEFAULT_LOCALE.set(locale);
+}
+
+/// 未协商出语言时使用的默认语言，未设置时为 zh-CN
+pub fn default_locale() -> Locale {
+    DEFAULT_LOCALE.get().copied().unwrap_or_default()
+}
+
+static CATALOGS: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    let load = |source: &str| -> HashMap<String, String> {
+        serde_json::from_str(source).expect("消息目录不是合法的 JSON")
+    };
+    HashMap::from([
+        (Locale::ZhCn, load(include_str!("../../locales/zh-CN.json"))),
+        (Locale::EnUs, load(include_str!("../../locales/en-US.json"))),
+    ])
+});
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Cell<Locale>;
+}
+
+/// 当前请求的语言，不在请求上下文中（后台任务、单元测试）时返回默认语言
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE
+        .try_with(Cell::get)
+        .unwrap_or_else(|_| default_locale())
+}
+
+/// 覆盖当前请求的语言，不在请求上下文中时忽略
+pub fn set_current_locale(locale: Locale) {
+    let _ = CURRENT_LOCALE.try_with(|current| current.set(locale));
+}
+
+/// 在指定语言下执行 future
+pub async fn scope_locale<F: Future>(locale: Locale, f: F) -> F::Output {
+    CURRENT_LOCALE.scope(Cell::new(locale), f).await
+}
+
+/// 按指定语言渲染消息，目录缺失时回退到 zh-CN，仍找不到则原样返回 key，
+/// 参数以 `{name}` 形式替换
+pub fn translate(locale: Locale, key: &str, args: &[(&str, String)]) -> String {
+    let template = CATALOGS
+        .get(&locale)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| CATALOGS.get(&Locale::ZhCn)?.get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// 按当前请求的语言渲染消息
+pub fn translate_current(key: &str, args: &[(&str, String)]) -> String {
+    translate(current_locale(), key, args)
+}
+
+/// 按当前请求的语言渲染消息：`t!("admin.not_found")`、`t!("request.too_many", secs = 30)`
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate_current($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate_current($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Locale::negotiate("en-US,en;q=0.9"), Some(Locale::EnUs));
+        assert_eq!(Locale::negotiate("zh-Hans-CN"), Some(Locale::ZhCn));
+        assert_eq!(
+            Locale::negotiate("fr-FR, en;q=0.5, zh-CN;q=0.8"),
+            Some(Locale::ZhCn)
+        );
+        assert_eq!(Locale::negotiate("en;q=0, zh;q=0.1"), Some(Locale::ZhCn));
+        assert_eq!(Locale::negotiate("fr-FR, de"), None);
+        assert_eq!(Locale::negotiate(""), None);
+
+        assert_eq!("en-us".parse::<Locale>(), Ok(Locale::EnUs));
+        assert!("en".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(
+            translate(Locale::EnUs, "request.too_many", &[("secs", "30".into())]),
+            "Too many requests, retry in 30 seconds"
+        );
+        assert_eq!(
+            translate(Locale::ZhCn, "request.too_many", &[("secs", "30".into())]),
+            "请求过于频繁，请在30秒后重试"
+        );
+        assert_eq!(translate(Locale::EnUs, "no.such.key", &[]), "no.such.key");
+    }
+
+    #[tokio::test]
+    async fn test_scoped_locale() {
+        let text = scope_locale(Locale::EnUs, async { t!("admin.not_found") }).await;
+        assert_eq!(text, "Admin not found");
+
+        let text = scope_locale(Locale::EnUs, async {
+            set_current_locale(Locale::ZhCn);
+            t!("admin.not_found")
+        })
+        .await;
+        assert_eq!(text, "管理员不存在");
+    }
+
+    /// 两份目录的 key 和占位符必须一致
+    #[test]
+    fn test_catalogs_consistent() {
+        let placeholders = |text: &str| -> Vec<String> {
+            let mut names: Vec<String> = text
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+                .collect();
+            names.sort();
+            names
+        };
+
+        let zh = &CATALOGS[&Locale::ZhCn];
+        let en = &CATALOGS[&Locale::EnUs];
+        assert_eq!(zh.len(), en.len());
+        for (key, text) in zh {
+            let other = en.get(key).unwrap_or_else(|| panic!("en-US 缺少 {}", key));
+            assert_eq!(placeholders(text), placeholders(other), "{}", key);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! 运行日志
+//!
+//! 默认输出文本；JSON 格式每行输出一个对象，附带所在请求 span 的追踪 ID、路由和管理员 ID，
+//! 便于日志平台解析。`log` 宏输出的日志同样经由 tracing 订阅者输出。
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("未知的日志格式: {}", s)),
+        }
+    }
+}
+
+pub fn init_logging(format: LogFormat) {
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt().init(),
+        LogFormat::Json => json_subscriber(std::io::stdout).init(),
+    }
+}
+
+/// 只输出当前 span 的字段，请求 span 已包含追踪 ID 和路由，不需要完整的 span 链
+fn json_subscriber<W>(writer: W) -> impl Subscriber + Send + Sync + 'static
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_span_list(false)
+        .with_writer(writer)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Router, body::Body, http::Request, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::middleware::middleware_api::trace_middleware;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn message(line: &serde_json::Value) -> &str {
+        line["fields"]["message"].as_str().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse(), Ok(LogFormat::Json));
+        assert_eq!("TEXT".parse(), Ok(LogFormat::Text));
+        assert!("logfmt".parse::<LogFormat>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_log_includes_trace_fields() {
+        let captured = Captured::default();
+        let _guard = json_subscriber({
+            let captured = captured.clone();
+            move || captured.clone()
+        })
+        .set_default();
+
+        let router = Router::new()
+            .route(
+                "/ping",
+                get(|| async {
+                    log::info!("pong");
+                    "pong"
+                }),
+            )
+            .layer(middleware::from_fn(trace_middleware));
+        let request = Request::builder()
+            .uri("/ping")
+            .header("x-request-id", "log-test-1")
+            .body(Body::empty())
+            .unwrap();
+        router.oneshot(request).await.unwrap();
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // 处理函数和中间件输出的日志都带有请求 span 的字段
+        let handler = lines
+            .iter()
+            .find(|line| message(line) == "pong")
+            .expect("缺少处理函数的日志");
+        assert_eq!(handler["span"]["trace_id"], "log-test-1");
+        assert_eq!(handler["span"]["method"], "GET");
+        assert_eq!(handler["span"]["route"], "/ping");
+
+        let finished = lines
+            .iter()
+            .find(|line| message(line).starts_with("GET /ping -> 200"))
+            .expect("缺少请求结束的日志");
+        assert_eq!(finished["span"]["trace_id"], "log-test-1");
+    }
+}
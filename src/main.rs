@@ -1,33 +1,190 @@
+mod cli;
+mod config;
 mod controller;
 mod dto;
 mod entities;
 mod error;
+mod extract;
+mod i18n;
+mod logging;
+mod metrics;
 mod middleware;
+mod migration;
+mod openapi;
 mod response;
 mod router;
 mod service;
+mod store;
+mod tls;
+mod trace;
 mod utils;
 
-use crate::{router::get_router, utils::hash_password};
+use std::{future::Future, future::IntoFuture, net::SocketAddr, pin::Pin};
+
+use crate::{
+    cli::{Cli, Command},
+    config::Config,
+    logging::init_logging,
+    router::{build_state, get_router},
+    service::maintenance_service::MaintenanceScheduler,
+    tls::{TlsAcceptor, load_server_config, spawn_reloader},
+};
+use anyhow::{Context, Result};
+use axum_server::tls_rustls::RustlsConfig;
+use clap::Parser;
 use dotenv::dotenv;
-use log::info;
+use log::{error, info, warn};
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
-    tracing_subscriber::fmt().init();
-    println!("{}", hash_password("123456"));
+    let cli = Cli::parse();
+
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => {
+            // 日志格式来自配置，配置有误时日志尚未初始化，直接输出到标准错误
+            let config = match Config::load() {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("错误：{:#}", e);
+                    std::process::exit(1);
+                }
+            };
+            init_logging(config.logging.format);
+            if let Err(e) = serve(config).await {
+                error!("错误：{:#}", e);
+                std::process::exit(1);
+            }
+        }
+        command => {
+            // 命令行输出留给结果，日志只保留警告以上并写到标准错误
+            tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::WARN)
+                .with_writer(std::io::stderr)
+                .init();
+            if let Err(e) = cli::run(command).await {
+                eprintln!("错误：{:#}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+async fn serve(config: Config) -> Result<()> {
+    let addr = config.server.bind_addr();
+    let shutdown_grace = config.server.shutdown_grace();
+    let shutdown_timeout = config.server.shutdown_timeout();
+    let tls_config = config.tls.clone();
+    // 证书有问题时在连接数据库之前就退出
+    let tls = if tls_config.enabled() {
+        Some(RustlsConfig::from_config(load_server_config(&tls_config)?))
+    } else {
+        None
+    };
+
+    let state = build_state(config).await?;
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("监听 {} 失败", addr))?;
+
+    let scheduler = MaintenanceScheduler::start(state.clone());
+    let lifecycle = state.lifecycle.clone();
+    let app = get_router(state).into_make_service_with_connect_info::<SocketAddr>();
+
+    let (draining_tx, draining_rx) = tokio::sync::oneshot::channel();
+    let shutdown = async move {
+        shutdown_signal().await;
+        lifecycle.begin_shutdown();
+        // 就绪检查已返回 503，等负载均衡摘除本实例后再停止接受新连接，期间的新请求照常处理
+        if !shutdown_grace.is_zero() {
+            info!("{} 秒后停止接受新连接", shutdown_grace.as_secs());
+            tokio::time::sleep(shutdown_grace).await;
+        }
+        let _ = draining_tx.send(());
+    };
+
+    let mut reloader = None;
+    let server: Pin<Box<dyn Future<Output = std::io::Result<()>> + Send>> = match tls {
+        Some(rustls) => {
+            info!("listening to https://{}", addr);
+            if tls_config.client_auth_enabled() {
+                info!(
+                    "已启用客户端证书校验: {}",
+                    tls_config.client_cert_paths.join(", ")
+                );
+            }
+            reloader = spawn_reloader(tls_config, rustls.clone());
+
+            let handle = axum_server::Handle::new();
+            tokio::spawn({
+                let handle = handle.clone();
+                async move {
+                    shutdown.await;
+                    handle.graceful_shutdown(None);
+                }
+            });
+            let listener = listener.into_std().context("转换监听套接字失败")?;
+            let server = axum_server::from_tcp(listener)
+                .acceptor(TlsAcceptor::new(rustls))
+                .handle(handle);
+            Box::pin(server.serve(app))
+        }
+        None => {
+            info!("listening to {}", addr);
+            Box::pin(
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(shutdown)
+                    .into_future(),
+            )
+        }
+    };
+
+    // 收到信号后不再接受新连接，处理中的请求超过时限仍未完成则放弃等待
+    let drain_deadline = async {
+        if draining_rx.await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        tokio::time::sleep(shutdown_timeout).await;
+    };
+    let result = tokio::select! {
+        result = server => result.context("服务异常退出"),
+        _ = drain_deadline => {
+            warn!("等待处理中的请求超时（{} 秒），强制停止", shutdown_timeout.as_secs());
+            Ok(())
+        }
+    };
+
+    if let Some(reloader) = reloader {
+        reloader.abort();
+    }
+    scheduler.shutdown().await;
+    result?;
+    info!("服务已停止");
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
 
-    let app = get_router().await.unwrap();
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = std::env::var("SERVER_PORT")
-        .unwrap_or_else(|_| "6123".to_string())
-        .parse::<u16>()
-        .unwrap_or(6123);
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 
-    let addr = format!("{}:{}", host, port);
-    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    info!("listening to {}", addr);
-    axum::serve(listener, app).await.unwrap();
+    info!("收到退出信号，正在停止服务");
 }
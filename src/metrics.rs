@@ -0,0 +1,235 @@
+//! Prometheus 指标
+//!
+//! 指标注册在进程内的独立 registry 中，启用 `metrics.enabled` 后由 `GET /metrics` 以文本格式导出。
+//! 路由标签使用路由模板（如 `/guardian-auth/v1/admins/{id}`），避免按实际路径产生大量序列。
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+use crate::dto::TwoFaMethod;
+use crate::router::AppState;
+
+/// 导出格式的 Content-Type
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 未匹配到路由的请求统一使用的标签
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// 登录请求的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginOutcome {
+    /// 签发了令牌
+    Success,
+    /// 密码正确，等待两步验证
+    MfaRequired,
+    /// 用户名或密码错误
+    Failure,
+    /// 因账户锁定或 IP 失败次数过多被拒绝，包括本次失败触发的锁定
+    Locked,
+    /// 需要先完成人机验证
+    CaptchaRequired,
+    /// 账户已禁用或 IP 规则不允许
+    Denied,
+}
+
+impl LoginOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoginOutcome::Success => "success",
+            LoginOutcome::MfaRequired => "mfa_required",
+            LoginOutcome::Failure => "failure",
+            LoginOutcome::Locked => "locked",
+            LoginOutcome::CaptchaRequired => "captcha_required",
+            LoginOutcome::Denied => "denied",
+        }
+    }
+}
+
+struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    logins: IntCounterVec,
+    two_fa_verifications: IntCounterVec,
+    permission_checks: IntCounterVec,
+    permission_check_duration: Histogram,
+    rate_limit_rejections: IntCounterVec,
+    db_connections: IntGaugeVec,
+    db_max_connections: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("guardian".to_string()), None)?;
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP 请求数"),
+            &["method", "route", "status"],
+        )?;
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP 请求处理耗时"),
+            &["method", "route", "status"],
+        )?;
+        let logins = IntCounterVec::new(
+            Opts::new("login_attempts_total", "登录请求数，按结果区分"),
+            &["outcome"],
+        )?;
+        let two_fa_verifications = IntCounterVec::new(
+            Opts::new("two_fa_verifications_total", "两步验证次数"),
+            &["method", "outcome"],
+        )?;
+        let permission_checks = IntCounterVec::new(
+            Opts::new("permission_checks_total", "接口权限检查次数"),
+            &["outcome"],
+        )?;
+        let permission_check_duration = Histogram::with_opts(
+            HistogramOpts::new("permission_check_duration_seconds", "接口权限检查耗时").buckets(
+                vec![
+                    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+                ],
+            ),
+        )?;
+        let rate_limit_rejections = IntCounterVec::new(
+            Opts::new("rate_limit_rejections_total", "被限流拒绝的请求数"),
+            &["policy"],
+        )?;
+        let db_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "数据库连接池中的连接数"),
+            &["state"],
+        )?;
+        let db_max_connections = IntGauge::new("db_pool_max_connections", "数据库连接池上限")?;
+
+        registry.register(Box::new(http_requests.clone()))?;
+        registry.register(Box::new(http_request_duration.clone()))?;
+        registry.register(Box::new(logins.clone()))?;
+        registry.register(Box::new(two_fa_verifications.clone()))?;
+        registry.register(Box::new(permission_checks.clone()))?;
+        registry.register(Box::new(permission_check_duration.clone()))?;
+        registry.register(Box::new(rate_limit_rejections.clone()))?;
+        registry.register(Box::new(db_connections.clone()))?;
+        registry.register(Box::new(db_max_connections.clone()))?;
+
+        Ok(Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            logins,
+            two_fa_verifications,
+            permission_checks,
+            permission_check_duration,
+            rate_limit_rejections,
+            db_connections,
+            db_max_connections,
+        })
+    }
+}
+
+// 指标名称和标签都是常量，创建失败只可能是代码错误
+static METRICS: LazyLock<Metrics> =
+    LazyLock::new(|| Metrics::new().expect("Prometheus 指标定义有误"));
+
+pub fn record_http_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    let status = status.to_string();
+    let labels = [method, route, status.as_str()];
+    METRICS.http_requests.with_label_values(&labels).inc();
+    METRICS
+        .http_request_duration
+        .with_label_values(&labels)
+        .observe(elapsed.as_secs_f64());
+}
+
+pub fn record_login(outcome: LoginOutcome) {
+    METRICS.logins.with_label_values(&[outcome.as_str()]).inc();
+}
+
+pub fn record_two_fa(method: TwoFaMethod, verified: bool) {
+    let method = match method {
+        TwoFaMethod::Totp => "totp",
+        TwoFaMethod::BackupCode => "backup_code",
+        TwoFaMethod::WebAuthn => "webauthn",
+    };
+    let outcome = if verified { "success" } else { "failure" };
+    METRICS
+        .two_fa_verifications
+        .with_label_values(&[method, outcome])
+        .inc();
+}
+
+pub fn record_permission_check(allowed: bool, elapsed: Duration) {
+    let outcome = if allowed { "allow" } else { "deny" };
+    METRICS
+        .permission_checks
+        .with_label_values(&[outcome])
+        .inc();
+    METRICS
+        .permission_check_duration
+        .observe(elapsed.as_secs_f64());
+}
+
+pub fn record_rate_limit_rejection(policy: &str) {
+    METRICS
+        .rate_limit_rejections
+        .with_label_values(&[policy])
+        .inc();
+}
+
+/// 刷新连接池状态后按 Prometheus 文本格式导出全部指标
+pub fn render(state: &AppState) -> String {
+    if let sea_orm::DatabaseConnection::SqlxPostgresPoolConnection(_) = &state.conn {
+        let pool = state.conn.get_postgres_connection_pool();
+        let idle = pool.num_idle() as i64;
+        METRICS
+            .db_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        METRICS
+            .db_connections
+            .with_label_values(&["active"])
+            .set(pool.size() as i64 - idle);
+    }
+    METRICS
+        .db_max_connections
+        .set(state.config.database.max_connections as i64);
+
+    encode(&METRICS.registry)
+}
+
+fn encode(registry: &Registry) -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&registry.gather(), &mut buffer) {
+        log::warn!("导出 Prometheus 指标失败: {}", e);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_exported() {
+        record_http_request("GET", "/guardian-auth/v1/admins/{id}", 200, Duration::ZERO);
+        record_login(LoginOutcome::Locked);
+        record_two_fa(TwoFaMethod::BackupCode, false);
+        record_permission_check(false, Duration::from_millis(2));
+        record_rate_limit_rejection("login");
+
+        let output = encode(&METRICS.registry);
+        for expected in [
+            r#"guardian_http_requests_total{method="GET",route="/guardian-auth/v1/admins/{id}",status="200"}"#,
+            "guardian_http_request_duration_seconds_bucket{",
+            r#"guardian_login_attempts_total{outcome="locked"}"#,
+            r#"guardian_two_fa_verifications_total{method="backup_code",outcome="failure"}"#,
+            r#"guardian_permission_checks_total{outcome="deny"}"#,
+            "guardian_permission_check_duration_seconds_count",
+            r#"guardian_rate_limit_rejections_total{policy="login"}"#,
+        ] {
+            assert!(output.contains(expected), "缺少 {}", expected);
+        }
+    }
+}
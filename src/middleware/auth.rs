@@ -1,6 +1,16 @@
+use crate::entities::admins;
+use crate::error::GuardianError;
+use crate::i18n::{set_current_locale, t};
 use crate::response::ResponseCode;
+use crate::router::{API_PREFIX, AppState};
+use crate::service::is_token_revoked;
 use crate::utils::verify_token;
-use axum::{Json, extract::Request, http::StatusCode, middleware::Next, response::Response};
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use sea_orm::EntityTrait;
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -8,45 +18,133 @@ pub struct AuthContext {
     pub admin_id: Uuid,
     pub username: String,
     pub is_super_admin: bool,
+    /// 访问令牌的 jti 和过期时间（Unix 秒），登出时用于吊销
+    pub token_id: String,
+    pub token_expires_at: usize,
 }
 
+/// 需要修改密码时，令牌仅允许访问的接口
+const PASSWORD_CHANGE_ALLOWED_PATHS: &[&str] = &["/auth/change-password", "/auth/logout"];
+
 pub(crate) async fn auth_middleware(
+    State(state): State<AppState>,
     mut request: Request,
     next: Next,
-) -> std::result::Result<Response, (StatusCode, Json<crate::response::Response<()>>)> {
+) -> Result<Response, GuardianError> {
     let headers = request.headers();
 
-    let token = headers.get("Authorization").and_then(|h| h.to_str().ok());
-
-    if token.is_none() {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(ResponseCode::AuthError.to_response(Some("缺少认证令牌".to_string()))),
-        ));
-    }
+    let Some(authorization) = headers.get("Authorization").and_then(|h| h.to_str().ok()) else {
+        return Err(GuardianError::Unauthorized(t!("auth.missing_token")));
+    };
 
-    let token = token.unwrap();
-    let (_, token) = token.split_at(7);
+    let Some(token) = authorization.strip_prefix("Bearer ") else {
+        return Err(GuardianError::Unauthorized(t!("auth.invalid_token")));
+    };
 
-    let claims = match verify_token(token) {
+    let claims = match verify_token(&state.config.jwt, token) {
         Ok(claims) => claims,
         Err(_) => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ResponseCode::AuthError.to_response(Some("无效的token".to_string()))),
-            ));
+            return Err(GuardianError::Unauthorized(t!("auth.invalid_token")));
         }
     };
 
     let admin_id = Uuid::parse_str(&claims.sub).unwrap_or_default();
 
+    let admin = admins::Entity::find_by_id(admin_id)
+        .one(&state.conn)
+        .await?;
+
+    let Some(admin) = admin.filter(|admin| !is_token_revoked(admin, claims.iat)) else {
+        return Err(GuardianError::Code(
+            ResponseCode::TokenExpired,
+            Some(t!("auth.token_revoked")),
+        ));
+    };
+
+    // 管理员设置了偏好语言时，覆盖按 Accept-Language 协商出的语言
+    if let Some(locale) = admin.locale.as_deref().and_then(|tag| tag.parse().ok()) {
+        set_current_locale(locale);
+    }
+
+    let revoked = state.revocations.is_revoked(&claims.jti).await?;
+    if revoked {
+        return Err(GuardianError::Code(
+            ResponseCode::TokenExpired,
+            Some(t!("auth.token_revoked")),
+        ));
+    }
+
+    let must_change_password =
+        claims.must_change_password || admin.must_change_password.unwrap_or(false);
+    let path = request.uri().path();
+    if must_change_password
+        && !PASSWORD_CHANGE_ALLOWED_PATHS
+            .iter()
+            .any(|allowed| path == format!("{}{}", API_PREFIX, allowed))
+    {
+        return Err(GuardianError::Code(
+            ResponseCode::PasswordChangeRequired,
+            None,
+        ));
+    }
+
     let auth_context = AuthContext {
         admin_id,
         username: claims.username,
         is_super_admin: claims.is_super_admin,
+        token_id: claims.jti,
+        token_expires_at: claims.exp,
     };
 
+    tracing::Span::current().record("admin_id", tracing::field::display(admin_id));
     request.extensions_mut().insert(auth_context);
 
-    Result::Ok(next.run(request).await)
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode, header},
+        middleware,
+        routing::get,
+    };
+    use sea_orm::DatabaseConnection;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::config::Config;
+
+    #[tokio::test]
+    async fn test_malformed_authorization_rejected() {
+        let mut config = Config::default();
+        config.jwt.secret = "x".repeat(32);
+        let state = AppState::new(config, DatabaseConnection::Disconnected).unwrap();
+        let router = Router::new()
+            .route("/me", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+
+        // 格式不对的 Authorization 头返回 401，而不是在切分时 panic
+        for value in [
+            "",
+            "Bearer",
+            "Token",
+            "bearer abc",
+            "Basic YWRtaW46YWRtaW4=",
+        ] {
+            let request = Request::builder()
+                .uri("/me")
+                .header(header::AUTHORIZATION, value)
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", value);
+        }
+    }
 }
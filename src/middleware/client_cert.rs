@@ -0,0 +1,34 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use log::warn;
+
+use crate::error::GuardianError;
+use crate::response::ResponseCode;
+use crate::router::AppState;
+use crate::tls::ClientCertificate;
+
+/// 配置了客户端 CA 时，`tls.client_cert_paths` 下的接口只接受出示了有效客户端证书的连接。
+/// 客户端证书只是额外的一层校验，接口原有的认证和权限检查不变
+pub async fn client_cert_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.config.tls.requires_client_cert(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let verified = request
+        .extensions()
+        .get::<ClientCertificate>()
+        .is_some_and(|cert| cert.verified);
+    if verified {
+        return next.run(request).await;
+    }
+
+    warn!("拒绝未出示客户端证书的请求: {}", request.uri().path());
+    GuardianError::Code(ResponseCode::ClientCertificateRequired, None).into_response()
+}
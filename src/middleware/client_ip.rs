@@ -0,0 +1,222 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+
+use crate::router::AppState;
+
+/// 解析后的客户端真实 IP，由 `client_ip_middleware` 写入请求扩展
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// 解析受信任代理条目，支持 CIDR 和单个 IP
+pub fn parse_proxy_network(value: &str) -> Option<IpNet> {
+    let value = value.trim();
+    value
+        .parse::<IpNet>()
+        .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+        .ok()
+}
+
+/// 受信任的反向代理网段，只有来自这些地址的转发头才会被采信
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Arc<Vec<IpNet>>,
+}
+
+impl TrustedProxies {
+    pub fn new(networks: Vec<IpNet>) -> Self {
+        Self {
+            networks: Arc::new(networks),
+        }
+    }
+
+    /// 按 `server.trusted_proxies` 构建，条目已在启动时校验，未配置时不信任任何转发头
+    pub fn from_config(proxies: &[String]) -> Self {
+        Self::new(
+            proxies
+                .iter()
+                .filter_map(|proxy| parse_proxy_network(proxy))
+                .collect(),
+        )
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|network| network.contains(&ip))
+    }
+
+    /// 从连接地址开始，沿转发链从右向左跳过受信任代理，第一个不受信任的地址即客户端 IP
+    pub fn resolve(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let peer = peer.to_canonical();
+        if !self.contains(&peer) {
+            return peer;
+        }
+
+        let chain = forwarded_chain(headers);
+        let mut client = peer;
+        for hop in chain.iter().rev() {
+            let Some(ip) = hop else {
+                break;
+            };
+            client = *ip;
+            if !self.contains(ip) {
+                break;
+            }
+        }
+
+        client
+    }
+}
+
+/// 转发链，优先使用标准 `Forwarded` 头，其次 `X-Forwarded-For`，最后 `X-Real-IP`。
+/// 无法解析的条目记为 `None`，解析时遇到即停止继续向左信任。
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let forwarded: Vec<Option<IpAddr>> = headers
+        .get_all("Forwarded")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_node(value))
+        })
+        .collect();
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+
+    let x_forwarded_for: Vec<Option<IpAddr>> = headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(parse_node)
+        .collect();
+    if !x_forwarded_for.is_empty() {
+        return x_forwarded_for;
+    }
+
+    headers
+        .get("X-Real-IP")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| vec![parse_node(value)])
+        .unwrap_or_default()
+}
+
+/// 解析 `1.2.3.4`、`1.2.3.4:80`、`"[2001:db8::1]:4711"`、`2001:db8::1` 等形式
+fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+
+    value
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .map(|ip| ip.to_canonical())
+}
+
+/// 解析客户端 IP 并写入请求扩展，需要以 `into_make_service_with_connect_info` 启动服务
+pub async fn client_ip_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // 直接调用 Router（例如测试中）时没有连接信息，按本机处理
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    let client_ip = state.trusted_proxies.resolve(peer, request.headers());
+    request.extensions_mut().insert(ClientIp(client_ip));
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_headers() {
+        let proxies = TrustedProxies::new(vec!["10.0.0.0/8".parse().unwrap()]);
+        let headers = headers(&[("X-Forwarded-For", "1.2.3.4")]);
+
+        assert_eq!(
+            proxies.resolve(ip("203.0.113.9"), &headers),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            TrustedProxies::default().resolve(ip("10.0.0.1"), &headers),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_x_forwarded_for_chain() {
+        let proxies = TrustedProxies::new(vec!["10.0.0.0/8".parse().unwrap()]);
+
+        // 客户端伪造的最左侧地址不会被采信
+        let spoofed = headers(&[("X-Forwarded-For", "6.6.6.6, 198.51.100.7, 10.0.0.2")]);
+        assert_eq!(
+            proxies.resolve(ip("10.0.0.1"), &spoofed),
+            ip("198.51.100.7")
+        );
+
+        let all_trusted = headers(&[("X-Forwarded-For", "10.0.0.3")]);
+        assert_eq!(
+            proxies.resolve(ip("10.0.0.1"), &all_trusted),
+            ip("10.0.0.3")
+        );
+    }
+
+    #[test]
+    fn test_forwarded_header() {
+        let proxies = TrustedProxies::new(vec!["10.0.0.1/32".parse().unwrap()]);
+        let headers = headers(&[
+            ("Forwarded", "for=6.6.6.6"),
+            ("Forwarded", r#"for="[2001:db8::1]:4711";proto=https"#),
+            ("X-Forwarded-For", "7.7.7.7"),
+        ]);
+
+        assert_eq!(proxies.resolve(ip("10.0.0.1"), &headers), ip("2001:db8::1"));
+    }
+
+    #[test]
+    fn test_ipv4_mapped_peer() {
+        let proxies = TrustedProxies::new(vec!["127.0.0.1/32".parse().unwrap()]);
+        let headers = headers(&[("X-Real-IP", "198.51.100.7")]);
+
+        assert_eq!(
+            proxies.resolve(ip("::ffff:127.0.0.1"), &headers),
+            ip("198.51.100.7")
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::error::GuardianError;
+use crate::middleware::client_ip::ClientIp;
+use crate::response::ResponseCode;
+use crate::router::{API_PREFIX, AppState};
+use crate::service::ip_rule_service::record_ip_denial;
+use crate::utils::verify_token;
+
+/// 除 API 外受黑白名单约束的路径
+const FILTERED_PATHS: &[&str] = &["/metrics"];
+
+/// 按 IP 黑白名单过滤 API 和指标请求，需要在认证中间件之前执行。
+///
+/// 携带有效访问令牌时同时检查该管理员的规则；登录接口的管理员规则在登录流程中检查。
+pub async fn ip_filter_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !path.starts_with(API_PREFIX) && !FILTERED_PATHS.contains(&path) {
+        return next.run(request).await;
+    }
+
+    let rules = match state.ip_rules.rules(&state.conn).await {
+        Ok(rules) => rules,
+        Err(e) => return e.into_response(),
+    };
+
+    let client_ip = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| *ip)
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    // 令牌无效时只检查全局规则，由认证中间件返回认证错误
+    let admin = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| verify_token(&state.config.jwt, token).ok())
+        .and_then(|claims| Some((uuid::Uuid::parse_str(&claims.sub).ok()?, claims.username)));
+
+    let Some(denial) = rules.check(client_ip, admin.as_ref().map(|(admin_id, _)| *admin_id)) else {
+        return next.run(request).await;
+    };
+
+    let user_agent = request
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    record_ip_denial(
+        &state,
+        &denial,
+        client_ip,
+        admin,
+        request.method().as_str(),
+        request
+            .uri()
+            .path()
+            .strip_prefix(API_PREFIX)
+            .unwrap_or(request.uri().path()),
+        user_agent,
+    )
+    .await;
+
+    GuardianError::Code(ResponseCode::IpNotAllowed, None).into_response()
+}
@@ -0,0 +1,29 @@
+use axum::{
+    extract::Request,
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::i18n::{Locale, current_locale, default_locale, scope_locale};
+
+/// 根据 `Accept-Language` 协商本次请求的语言，并在响应中回写 `Content-Language`。
+/// 认证中间件会在此基础上用管理员的偏好语言覆盖
+pub(crate) async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Locale::negotiate)
+        .unwrap_or_else(default_locale);
+
+    scope_locale(locale, async move {
+        let mut response = next.run(request).await;
+        response.headers_mut().insert(
+            header::CONTENT_LANGUAGE,
+            HeaderValue::from_static(current_locale().tag()),
+        );
+        response
+    })
+    .await
+}
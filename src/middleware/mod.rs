@@ -1,9 +1,23 @@
 pub mod auth;
+pub mod client_cert;
+pub mod client_ip;
+pub mod ip_filter;
+pub mod locale;
+pub mod metrics;
 pub mod permission;
 pub mod rate_limit;
+pub mod security_headers;
+pub mod trace;
 
 pub(crate) mod middleware_api {
     pub(crate) use super::auth::*;
+    pub(crate) use super::client_cert::*;
+    pub(crate) use super::client_ip::*;
+    pub(crate) use super::ip_filter::*;
+    pub(crate) use super::locale::*;
+    pub(crate) use super::metrics::*;
     pub(crate) use super::permission::*;
     pub(crate) use super::rate_limit::*;
+    pub(crate) use super::security_headers::*;
+    pub(crate) use super::trace::*;
 }
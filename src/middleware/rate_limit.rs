@@ -1,108 +1,593 @@
-use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
-use dashmap::DashMap;
-use std::net::IpAddr;
+use anyhow::Result;
+use axum::{
+    Json,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use log::warn;
+use serde::Deserialize;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 
-#[derive(Clone)]
-pub struct RateLimiter {
-    clients: Arc<DashMap<IpAddr, RateLimitState>>,
-    max_requests: u64,
-    window_secs: u64,
+use crate::config::{RateLimitConfig, RateLimitPolicyConfig};
+use crate::i18n::t;
+use crate::metrics::record_rate_limit_rejection;
+use crate::middleware::auth::AuthContext;
+use crate::middleware::client_ip::ClientIp;
+use crate::response::ResponseCode;
+use crate::router::{API_PREFIX, AppState};
+use crate::store::CounterStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    /// 固定窗口计数
+    FixedWindow,
+    /// 滑动窗口计数（按上一窗口剩余比例加权）
+    SlidingWindow,
+    /// 令牌桶，允许短时突发
+    TokenBucket,
+}
+
+impl FromStr for RateLimitAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fixed_window" => Ok(Self::FixedWindow),
+            "sliding_window" => Ok(Self::SlidingWindow),
+            "token_bucket" => Ok(Self::TokenBucket),
+            other => Err(format!("未知的限流算法: {}", other)),
+        }
+    }
+}
+
+/// 限流计数的归属维度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    Ip,
+    /// 按登录账户计数，未登录时退化为按 IP
+    Admin,
+    /// IP 与账户组合计数
+    #[serde(rename = "ip_admin")]
+    IpAndAdmin,
+}
+
+impl FromStr for RateLimitKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ip" => Ok(Self::Ip),
+            "admin" => Ok(Self::Admin),
+            "ip_admin" => Ok(Self::IpAndAdmin),
+            other => Err(format!("未知的限流维度: {}", other)),
+        }
+    }
+}
+
+impl RateLimitKey {
+    fn identity(&self, ip: IpAddr, admin_id: Option<uuid::Uuid>) -> String {
+        match (self, admin_id) {
+            (RateLimitKey::Admin, Some(admin_id)) => format!("admin:{}", admin_id),
+            (RateLimitKey::IpAndAdmin, Some(admin_id)) => format!("ip:{}|admin:{}", ip, admin_id),
+            _ => format!("ip:{}", ip),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    pub name: &'static str,
+    /// 匹配的 (方法, 路由模板)，路由相对 `API_PREFIX`，`*` 表示任意方法或以该前缀开头的路由
+    pub routes: &'static [(&'static str, &'static str)],
+    pub key: RateLimitKey,
+    pub algorithm: RateLimitAlgorithm,
+    pub limit: u64,
+    pub window_secs: u64,
+}
+
+impl RateLimitPolicy {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        self.routes.iter().any(|(route_method, route_path)| {
+            let method_matches = *route_method == "*" || route_method.eq_ignore_ascii_case(method);
+            let path_matches = match route_path.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => *route_path == path,
+            };
+            method_matches && path_matches
+        })
+    }
+
+    fn window_ms(&self) -> i64 {
+        self.window_secs.clamp(1, i64::MAX as u64 / 1000) as i64 * 1000
+    }
+
+    /// 用配置中同名策略的字段覆盖默认值
+    fn with_overrides(mut self, overrides: Option<&RateLimitPolicyConfig>) -> Self {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        self.limit = overrides.limit.unwrap_or(self.limit);
+        self.window_secs = overrides.window_secs.unwrap_or(self.window_secs);
+        self.algorithm = overrides.algorithm.unwrap_or(self.algorithm);
+        self.key = overrides.key.unwrap_or(self.key);
+        self
+    }
+}
+
+/// 默认限流策略，按顺序匹配，命中第一条即停止
+pub fn default_policies(config: &RateLimitConfig) -> Vec<RateLimitPolicy> {
+    vec![
+        RateLimitPolicy {
+            name: "login",
+            routes: &[("POST", "/auth/login"), ("POST", "/auth/login/2fa")],
+            key: RateLimitKey::Ip,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            limit: 10,
+            window_secs: 60,
+        },
+        RateLimitPolicy {
+            name: "refresh",
+            routes: &[("POST", "/auth/refresh")],
+            key: RateLimitKey::Ip,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            limit: 30,
+            window_secs: 60,
+        },
+        RateLimitPolicy {
+            name: "reset_password",
+            routes: &[
+                ("POST", "/auth/reset-password"),
+                ("POST", "/auth/password-reset/*"),
+            ],
+            key: RateLimitKey::Ip,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            limit: 5,
+            window_secs: 300,
+        },
+        RateLimitPolicy {
+            name: "write",
+            routes: &[("POST", "*"), ("PUT", "*"), ("DELETE", "*")],
+            key: RateLimitKey::Admin,
+            algorithm: RateLimitAlgorithm::TokenBucket,
+            limit: 60,
+            window_secs: 60,
+        },
+        RateLimitPolicy {
+            name: "default",
+            routes: &[("*", "*")],
+            key: RateLimitKey::Ip,
+            algorithm: RateLimitAlgorithm::FixedWindow,
+            limit: config.max_requests,
+            window_secs: config.window_secs,
+        },
+    ]
+    .into_iter()
+    .map(|policy| {
+        let overrides = config.policies.get(policy.name);
+        policy.with_overrides(overrides)
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    /// 配额完全恢复还需的秒数
+    pub reset_secs: u64,
+    /// 被拒绝时建议的重试等待秒数
+    pub retry_after_secs: u64,
+}
+
+fn ceil_secs(millis: f64) -> u64 {
+    (millis.max(0.0) / 1000.0).ceil() as u64
+}
+
+/// 当前 Unix 毫秒时间戳，各实例以此对齐窗口
+pub fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
 }
 
 #[derive(Clone)]
-struct RateLimitState {
-    count: u64,
-    window_start: Instant,
+pub struct RateLimiter {
+    policies: Arc<Vec<RateLimitPolicy>>,
+    store: Arc<dyn CounterStore>,
 }
 
 impl RateLimiter {
-    pub fn new(max_requests: u64, window_secs: u64) -> Self {
+    pub fn new(policies: Vec<RateLimitPolicy>, store: Arc<dyn CounterStore>) -> Self {
         Self {
-            clients: Arc::new(DashMap::new()),
-            max_requests,
-            window_secs,
+            policies: Arc::new(policies),
+            store,
         }
     }
 
-    pub fn is_allowed(&self, client_ip: IpAddr) -> bool {
-        let now = Instant::now();
-        let window_duration = Duration::from_secs(self.window_secs);
+    pub fn from_config(config: &RateLimitConfig, store: Arc<dyn CounterStore>) -> Self {
+        Self::new(default_policies(config), store)
+    }
 
-        let mut entry = self.clients.entry(client_ip).or_insert(RateLimitState {
-            count: 0,
-            window_start: now,
-        });
+    pub fn policy_for(&self, method: &str, path: &str) -> Option<&RateLimitPolicy> {
+        self.policies
+            .iter()
+            .find(|policy| policy.matches(method, path))
+    }
 
-        let state = entry.value_mut();
+    pub async fn check(
+        &self,
+        policy: &RateLimitPolicy,
+        identity: String,
+        now_ms: i64,
+    ) -> Result<RateLimitDecision> {
+        let window_ms = policy.window_ms();
+        let limit = policy.limit;
+        let key = format!("{}:{}", policy.name, identity);
 
-        if now.duration_since(state.window_start) >= window_duration {
-            state.count = 1;
-            state.window_start = now;
-            return true;
+        if limit == 0 {
+            let until_next_window = window_ms - now_ms.rem_euclid(window_ms);
+            return Ok(RateLimitDecision {
+                allowed: false,
+                limit,
+                remaining: 0,
+                reset_secs: ceil_secs(until_next_window as f64),
+                retry_after_secs: ceil_secs(until_next_window as f64).max(1),
+            });
         }
 
-        if state.count >= self.max_requests {
-            return false;
+        match policy.algorithm {
+            RateLimitAlgorithm::TokenBucket => {
+                let capacity = limit as f64;
+                let refill_per_ms = capacity / window_ms as f64;
+                let (allowed, tokens) = self
+                    .store
+                    .take_token(&key, capacity, refill_per_ms, now_ms)
+                    .await?;
+
+                Ok(RateLimitDecision {
+                    allowed,
+                    limit,
+                    remaining: tokens.floor() as u64,
+                    reset_secs: ceil_secs((capacity - tokens) / refill_per_ms),
+                    retry_after_secs: if allowed {
+                        0
+                    } else {
+                        ceil_secs((1.0 - tokens) / refill_per_ms).max(1)
+                    },
+                })
+            }
+            algorithm => {
+                // 窗口按 Unix 时间对齐，多个实例共享同一组窗口编号
+                let window = now_ms.div_euclid(window_ms);
+                let position = now_ms - window * window_ms;
+                let until_next_window = (window_ms - position) as f64;
+
+                let previous_count = if algorithm == RateLimitAlgorithm::SlidingWindow {
+                    self.store.window_count(&key, window - 1).await?
+                } else {
+                    0
+                };
+                let previous_weight = 1.0 - position as f64 / window_ms as f64;
+                let weighted_previous = previous_count as f64 * previous_weight;
+                let cap = (limit as f64 - weighted_previous).floor().max(0.0) as u64;
+
+                // 滑动窗口在下一个窗口仍需要读取本窗口计数
+                let expires_at_ms = (window + 2) * window_ms;
+                match self
+                    .store
+                    .increment_window(&key, window, cap, expires_at_ms)
+                    .await?
+                {
+                    Some(count) => Ok(RateLimitDecision {
+                        allowed: true,
+                        limit,
+                        remaining: (limit as f64 - weighted_previous - count as f64).max(0.0)
+                            as u64,
+                        reset_secs: ceil_secs(until_next_window),
+                        retry_after_secs: 0,
+                    }),
+                    None => {
+                        let count = self.store.window_count(&key, window).await?;
+                        // 滑动窗口需要等待上一窗口的权重衰减到足以容纳一次请求
+                        let retry_after = if count < limit && previous_count > 0 {
+                            let needed_weight = (limit - count - 1) as f64 / previous_count as f64;
+                            (1.0 - needed_weight) * window_ms as f64 - position as f64
+                        } else {
+                            until_next_window
+                        };
+
+                        Ok(RateLimitDecision {
+                            allowed: false,
+                            limit,
+                            remaining: 0,
+                            reset_secs: ceil_secs(until_next_window),
+                            retry_after_secs: ceil_secs(retry_after).max(1),
+                        })
+                    }
+                }
+            }
         }
+    }
 
-        state.count += 1;
-        true
+    pub async fn cleanup_expired(&self) -> Result<u64> {
+        self.store.cleanup_expired(now_millis()).await
     }
+}
 
-    pub fn cleanup_expired(&self) {
-        let now = Instant::now();
-        let window_duration = Duration::from_secs(self.window_secs);
+fn set_rate_limit_headers(
+    headers: &mut HeaderMap,
+    policy: &RateLimitPolicy,
+    decision: &RateLimitDecision,
+) {
+    let values = [
+        ("ratelimit-limit", decision.limit.to_string()),
+        ("ratelimit-remaining", decision.remaining.to_string()),
+        ("ratelimit-reset", decision.reset_secs.to_string()),
+        (
+            "ratelimit-policy",
+            format!("{};w={}", policy.limit, policy.window_secs),
+        ),
+    ];
 
-        self.clients
-            .retain(|_, state| now.duration_since(state.window_start) < window_duration);
+    for (name, value) in values {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
     }
 }
 
-fn create_rate_limiter() -> RateLimiter {
-    let max_requests = std::env::var("RATE_LIMIT_MAX_REQUESTS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(100);
+/// 需要在认证中间件之后执行，才能按账户计数
+pub async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().as_str().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let path = path.strip_prefix(API_PREFIX).unwrap_or(&path);
 
-    let window_secs = std::env::var("RATE_LIMIT_WINDOW_SECS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(60);
+    let Some(policy) = state.rate_limiter.policy_for(&method, path) else {
+        return next.run(request).await;
+    };
 
-    RateLimiter::new(max_requests, window_secs)
+    let client_ip = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| *ip)
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let admin_id = request
+        .extensions()
+        .get::<AuthContext>()
+        .map(|auth_context| auth_context.admin_id);
+
+    let decision = match state
+        .rate_limiter
+        .check(
+            policy,
+            policy.key.identity(client_ip, admin_id),
+            now_millis(),
+        )
+        .await
+    {
+        Ok(decision) => decision,
+        Err(e) => {
+            // 计数存储不可用时放行，避免限流故障导致整个服务不可用
+            warn!("限流计数失败，已放行请求: {}", e);
+            return next.run(request).await;
+        }
+    };
+
+    if !decision.allowed {
+        record_rate_limit_rejection(policy.name);
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ResponseCode::RateLimitExceeded.to_response::<()>(Some(t!(
+                "request.too_many",
+                secs = decision.retry_after_secs
+            )))),
+        )
+            .into_response();
+        set_rate_limit_headers(response.headers_mut(), policy, &decision);
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(decision.retry_after_secs),
+        );
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    set_rate_limit_headers(response.headers_mut(), policy, &decision);
+    response
 }
 
-pub async fn rate_limit_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
-    use std::sync::OnceLock;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryCounterStore;
 
-    static RATE_LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    fn memory_limiter(policies: Vec<RateLimitPolicy>) -> RateLimiter {
+        RateLimiter::new(policies, Arc::new(MemoryCounterStore::default()))
+    }
 
-    let rate_limiter = RATE_LIMITER.get_or_init(create_rate_limiter);
+    fn policy(algorithm: RateLimitAlgorithm, limit: u64, window_secs: u64) -> RateLimitPolicy {
+        RateLimitPolicy {
+            name: "test",
+            routes: &[("*", "*")],
+            key: RateLimitKey::Ip,
+            algorithm,
+            limit,
+            window_secs,
+        }
+    }
 
-    let client_ip = extract_client_ip(&request);
+    #[test]
+    fn test_policy_matching() {
+        let limiter = memory_limiter(default_policies(&RateLimitConfig::default()));
 
-    if !rate_limiter.is_allowed(client_ip) {
-        return Err(StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            limiter.policy_for("POST", "/auth/login").unwrap().name,
+            "login"
+        );
+        assert_eq!(
+            limiter
+                .policy_for("POST", "/auth/password-reset/confirm")
+                .unwrap()
+                .name,
+            "reset_password"
+        );
+        assert_eq!(
+            limiter.policy_for("PUT", "/admins/{id}").unwrap().name,
+            "write"
+        );
+        assert_eq!(
+            limiter.policy_for("GET", "/admins").unwrap().name,
+            "default"
+        );
     }
 
-    Ok(next.run(request).await)
-}
+    #[test]
+    fn test_identity() {
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let admin_id = uuid::Uuid::nil();
 
-fn extract_client_ip(request: &Request) -> IpAddr {
-    request
-        .headers()
-        .get("X-Forwarded-For")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|s| s.split(',').next())
-        .and_then(|s| s.trim().parse().ok())
-        .or_else(|| {
-            request
-                .headers()
-                .get("X-Real-IP")
-                .and_then(|h| h.to_str().ok())
-                .and_then(|s| s.parse().ok())
-        })
-        .unwrap_or_else(|| "127.0.0.1".parse().unwrap())
+        assert_eq!(RateLimitKey::Ip.identity(ip, Some(admin_id)), "ip:10.0.0.1");
+        assert_eq!(
+            RateLimitKey::Admin.identity(ip, Some(admin_id)),
+            format!("admin:{}", admin_id)
+        );
+        assert_eq!(RateLimitKey::Admin.identity(ip, None), "ip:10.0.0.1");
+        assert_eq!(
+            "ip_admin"
+                .parse::<RateLimitKey>()
+                .unwrap()
+                .identity(ip, Some(admin_id)),
+            format!("ip:10.0.0.1|admin:{}", admin_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fixed_window() {
+        let limiter = memory_limiter(vec![]);
+        let policy = policy(RateLimitAlgorithm::FixedWindow, 2, 10);
+        let start = 0;
+
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+
+        let rejected = limiter
+            .check(&policy, "a".into(), start + 4_000)
+            .await
+            .unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after_secs, 6);
+        assert!(
+            limiter
+                .check(&policy, "b".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start + 10_000)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window() {
+        let limiter = memory_limiter(vec![]);
+        let policy = policy(RateLimitAlgorithm::SlidingWindow, 2, 10);
+        let start = 0;
+
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+
+        // 进入下一窗口初期，上一窗口的请求仍占大部分配额
+        let rejected = limiter
+            .check(&policy, "a".into(), start + 11_000)
+            .await
+            .unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after_secs, 4);
+
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start + 15_000)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket() {
+        let limiter = memory_limiter(vec![]);
+        let policy = policy(RateLimitAlgorithm::TokenBucket, 2, 10);
+        let start = 0;
+
+        assert_eq!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .remaining,
+            1
+        );
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start)
+                .await
+                .unwrap()
+                .allowed
+        );
+
+        let rejected = limiter.check(&policy, "a".into(), start).await.unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after_secs, 5);
+
+        assert!(
+            limiter
+                .check(&policy, "a".into(), start + 5_000)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
 }
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::config::SecurityHeadersConfig;
+use crate::router::API_PREFIX;
+
+/// 启动时根据配置生成的响应头，请求处理时只做复制
+#[derive(Clone, Default)]
+pub(crate) struct SecurityHeaders {
+    common: Vec<(HeaderName, HeaderValue)>,
+    api_csp: Option<HeaderValue>,
+    docs_csp: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    pub(crate) fn from_config(config: &SecurityHeadersConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+
+        let mut common = vec![(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        )];
+        if config.hsts_max_age_secs > 0 {
+            let mut hsts = format!("max-age={}", config.hsts_max_age_secs);
+            if config.hsts_include_subdomains {
+                hsts.push_str("; includeSubDomains");
+            }
+            common.extend(header_value(&hsts).map(|v| (header::STRICT_TRANSPORT_SECURITY, v)));
+        }
+        common.extend(
+            header_value(&config.frame_options.to_ascii_uppercase())
+                .map(|v| (header::X_FRAME_OPTIONS, v)),
+        );
+        common.extend(header_value(&config.referrer_policy).map(|v| (header::REFERRER_POLICY, v)));
+
+        Self {
+            common,
+            api_csp: header_value(&config.content_security_policy),
+            docs_csp: header_value(&config.docs_content_security_policy),
+        }
+    }
+
+    fn apply(&self, path: &str, headers: &mut HeaderMap) {
+        for (name, value) in &self.common {
+            headers.entry(name).or_insert_with(|| value.clone());
+        }
+
+        // Swagger UI 需要加载自身的脚本和样式，接口响应则不需要加载任何资源
+        let csp = if path.starts_with(&format!("{}/docs", API_PREFIX)) {
+            &self.docs_csp
+        } else {
+            &self.api_csp
+        };
+        if let Some(csp) = csp {
+            headers
+                .entry(header::CONTENT_SECURITY_POLICY)
+                .or_insert_with(|| csp.clone());
+        }
+    }
+}
+
+/// 配置已在启动时校验，空字符串表示不返回该响应头
+fn header_value(value: &str) -> Option<HeaderValue> {
+    if value.is_empty() {
+        return None;
+    }
+    HeaderValue::from_str(value).ok()
+}
+
+/// 为所有响应附加安全响应头，处理函数已设置的同名响应头保持不变
+pub(crate) async fn security_headers_middleware(
+    State(headers): State<Arc<SecurityHeaders>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+    headers.apply(&path, response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(config: &SecurityHeadersConfig, path: &str, headers: &mut HeaderMap) {
+        SecurityHeaders::from_config(config).apply(path, headers);
+    }
+
+    #[test]
+    fn test_default_headers() {
+        let config = SecurityHeadersConfig::default();
+        let mut headers = HeaderMap::new();
+        applied(&config, "/guardian-auth/v1/admins", &mut headers);
+
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+        assert_eq!(
+            headers[header::STRICT_TRANSPORT_SECURITY],
+            "max-age=31536000"
+        );
+        assert_eq!(
+            headers[header::CONTENT_SECURITY_POLICY],
+            config.content_security_policy.as_str()
+        );
+
+        let mut headers = HeaderMap::new();
+        applied(&config, "/guardian-auth/v1/docs/index.html", &mut headers);
+        assert_eq!(
+            headers[header::CONTENT_SECURITY_POLICY],
+            config.docs_content_security_policy.as_str()
+        );
+    }
+
+    #[test]
+    fn test_configured_headers() {
+        let config = SecurityHeadersConfig {
+            hsts_max_age_secs: 0,
+            frame_options: "sameorigin".to_string(),
+            referrer_policy: String::new(),
+            ..SecurityHeadersConfig::default()
+        };
+        // 处理函数已设置的响应头不被覆盖
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'self'"),
+        );
+        applied(&config, "/", &mut headers);
+
+        assert_eq!(headers[header::X_FRAME_OPTIONS], "SAMEORIGIN");
+        assert_eq!(
+            headers[header::CONTENT_SECURITY_POLICY],
+            "default-src 'self'"
+        );
+        assert!(!headers.contains_key(header::STRICT_TRANSPORT_SECURITY));
+        assert!(!headers.contains_key(header::REFERRER_POLICY));
+
+        let config = SecurityHeadersConfig {
+            enabled: false,
+            ..SecurityHeadersConfig::default()
+        };
+        let mut headers = HeaderMap::new();
+        applied(&config, "/", &mut headers);
+        assert!(headers.is_empty());
+    }
+}
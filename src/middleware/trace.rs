@@ -0,0 +1,66 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use log::info;
+use tracing::Instrument;
+
+use crate::trace::{TraceContext, scope_trace};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+pub const TRACEPARENT_HEADER: HeaderName = HeaderName::from_static("traceparent");
+
+/// 确定本次请求的追踪 ID，请求处理期间的日志都归入带追踪 ID 和路由的 span，
+/// 响应中回写 `X-Request-Id` 和 `traceparent`
+pub(crate) async fn trace_middleware(request: Request, next: Next) -> Response {
+    let headers = request.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let trace = TraceContext::from_headers(header(TRACEPARENT_HEADER), header(REQUEST_ID_HEADER));
+
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    // admin_id 由认证中间件在认证通过后填入
+    let span = tracing::info_span!(
+        "request",
+        trace_id = %trace.trace_id,
+        method = %method,
+        route = %route,
+        admin_id = tracing::field::Empty,
+    );
+
+    let trace_id = trace.trace_id.clone();
+    let traceparent = trace.traceparent();
+    let started = Instant::now();
+
+    let mut response = scope_trace(trace, next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    span.in_scope(|| {
+        info!(
+            "{} {} -> {} ({} ms)",
+            method,
+            route,
+            response.status().as_u16(),
+            started.elapsed().as_millis()
+        )
+    });
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&trace_id) {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
+    if let Some(value) = traceparent.and_then(|value| HeaderValue::from_str(&value).ok()) {
+        headers.insert(TRACEPARENT_HEADER, value);
+    }
+
+    response
+}
@@ -0,0 +1,17 @@
+use sea_orm_migration::prelude::*;
+
+/// 基线表结构，覆盖此前由已删除的 `scripts/public.sql` 和 `scripts/init_db.py` 创建的
+/// 所有表、索引和触发器。语句均可重复执行，已有数据库执行后只补齐缺少的部分
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("sql/m20261019_000001_baseline_schema.sql"))
+            .await?;
+        Ok(())
+    }
+}
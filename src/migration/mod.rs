@@ -0,0 +1,69 @@
+//! 内置的数据库迁移
+//!
+//! 迁移按文件名中的时间戳顺序执行，已执行的版本记录在 `seaql_migrations` 表中。
+//! 新增表或字段时添加新的迁移文件并在 [`Migrator::migrations`] 中登记，不要修改已发布的迁移。
+
+mod m20261019_000001_baseline_schema;
+
+use anyhow::Result;
+use log::info;
+use sea_orm::DatabaseConnection;
+use sea_orm_migration::{MigrationStatus, MigrationTrait, MigratorTrait};
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![Box::new(m20261019_000001_baseline_schema::Migration)]
+    }
+}
+
+/// 执行所有未执行的迁移
+pub async fn run_migrations(conn: &DatabaseConnection) -> Result<()> {
+    let pending = Migrator::get_pending_migrations(conn).await?;
+    if pending.is_empty() {
+        info!("数据库结构已是最新版本");
+        return Ok(());
+    }
+
+    for migration in &pending {
+        info!("执行数据库迁移: {}", migration.name());
+    }
+    Migrator::up(conn, None).await?;
+    info!("数据库迁移完成，共 {} 个", pending.len());
+    Ok(())
+}
+
+/// 各迁移的名称和是否已执行
+pub async fn migration_status(conn: &DatabaseConnection) -> Result<Vec<(String, bool)>> {
+    Ok(Migrator::get_migration_with_status(conn)
+        .await?
+        .iter()
+        .map(|migration| {
+            (
+                migration.name().to_string(),
+                migration.status() == MigrationStatus::Applied,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 迁移名称必须唯一且按时间戳递增，否则执行顺序与登记顺序不一致
+    #[test]
+    fn test_migrations_ordered() {
+        let names: Vec<String> = Migrator::migrations()
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect();
+
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+    }
+}
@@ -0,0 +1,426 @@
+-- 基线表结构
+-- 所有语句均可重复执行：已通过旧版 scripts/public.sql / scripts/init_db.py 建好的数据库执行后只会补齐缺少的部分。
+-- 所有表不使用外键，只通过逻辑关联。
+
+-- ========== 辅助函数 ==========
+
+-- UUIDv7：前 48 位为毫秒时间戳，随机部分取自 gen_random_uuid()（PostgreSQL 13+ 内置，无需 pgcrypto）
+CREATE OR REPLACE FUNCTION uuid_v7() RETURNS uuid AS $$
+DECLARE
+    unix_ts_ms bytea;
+    uuid_bytes bytea;
+BEGIN
+    unix_ts_ms := substring(int8send((EXTRACT(EPOCH FROM clock_timestamp()) * 1000)::bigint) FROM 3);
+    uuid_bytes := overlay(uuid_send(gen_random_uuid()) PLACING unix_ts_ms FROM 1 FOR 6);
+    -- 版本号 7，变体位沿用 v4 的 10xx
+    uuid_bytes := set_byte(uuid_bytes, 6, (get_byte(uuid_bytes, 6) & 15) | 112);
+    RETURN encode(uuid_bytes, 'hex')::uuid;
+END;
+$$ LANGUAGE plpgsql VOLATILE;
+
+CREATE OR REPLACE FUNCTION update_updated_at_column() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql VOLATILE;
+
+-- ========== 管理员 ==========
+
+CREATE TABLE IF NOT EXISTS guardian_admins (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    username varchar(64) NOT NULL,
+    email varchar(255),
+    password_hash varchar(255) NOT NULL,
+    two_fa_secret text,
+    is_super_admin bool DEFAULT false,
+    status int2 DEFAULT 1,
+    last_login_at timestamptz(6),
+    login_attempts int4 DEFAULT 0,
+    locked_until timestamptz(6),
+    password_changed_at timestamptz(6) DEFAULT now(),
+    must_change_password bool DEFAULT false,
+    locale varchar(16),
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    updated_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_admins_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_admins_username_key UNIQUE (username),
+    CONSTRAINT guardian_admins_email_key UNIQUE (email)
+);
+-- 早期版本的数据库缺少的字段
+ALTER TABLE guardian_admins ADD COLUMN IF NOT EXISTS password_changed_at timestamptz(6) DEFAULT now();
+ALTER TABLE guardian_admins ADD COLUMN IF NOT EXISTS must_change_password bool DEFAULT false;
+ALTER TABLE guardian_admins ADD COLUMN IF NOT EXISTS email varchar(255) CONSTRAINT guardian_admins_email_key UNIQUE;
+ALTER TABLE guardian_admins ADD COLUMN IF NOT EXISTS locale varchar(16);
+CREATE INDEX IF NOT EXISTS idx_guardian_admins_status ON guardian_admins (status);
+CREATE INDEX IF NOT EXISTS idx_guardian_admins_username ON guardian_admins (username);
+DROP TRIGGER IF EXISTS guardian_admins_updated_at ON guardian_admins;
+CREATE TRIGGER guardian_admins_updated_at BEFORE UPDATE ON guardian_admins
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+COMMENT ON TABLE guardian_admins IS '管理员表';
+COMMENT ON COLUMN guardian_admins.id IS '管理员ID（UUIDv7）';
+COMMENT ON COLUMN guardian_admins.username IS '用户名';
+COMMENT ON COLUMN guardian_admins.email IS '邮箱（小写存储，用于找回密码）';
+COMMENT ON COLUMN guardian_admins.password_hash IS 'argon2密码哈希';
+COMMENT ON COLUMN guardian_admins.two_fa_secret IS '2FA密钥（ChaCha20加密存储）';
+COMMENT ON COLUMN guardian_admins.is_super_admin IS '是否超级管理员';
+COMMENT ON COLUMN guardian_admins.status IS '状态：1=正常，0=禁用';
+COMMENT ON COLUMN guardian_admins.last_login_at IS '最后登录时间';
+COMMENT ON COLUMN guardian_admins.login_attempts IS '登录失败尝试次数';
+COMMENT ON COLUMN guardian_admins.locked_until IS '锁定截止时间';
+COMMENT ON COLUMN guardian_admins.password_changed_at IS '密码最后修改时间';
+COMMENT ON COLUMN guardian_admins.must_change_password IS '是否必须修改密码后才能使用系统';
+COMMENT ON COLUMN guardian_admins.locale IS '偏好语言：zh-CN / en-US，为空时按 Accept-Language 协商';
+COMMENT ON COLUMN guardian_admins.created_at IS '创建时间';
+COMMENT ON COLUMN guardian_admins.updated_at IS '更新时间';
+
+CREATE TABLE IF NOT EXISTS guardian_admin_backup_codes (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    code_hash varchar(255) NOT NULL,
+    used_at timestamptz(6),
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_admin_backup_codes_pkey PRIMARY KEY (id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_admin_backup_codes_admin_id ON guardian_admin_backup_codes (admin_id);
+COMMENT ON TABLE guardian_admin_backup_codes IS '2FA备用码表';
+COMMENT ON COLUMN guardian_admin_backup_codes.id IS '记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_admin_backup_codes.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_admin_backup_codes.code_hash IS '备用码argon2哈希';
+COMMENT ON COLUMN guardian_admin_backup_codes.used_at IS '使用时间（为空表示未使用）';
+COMMENT ON COLUMN guardian_admin_backup_codes.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_password_history (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    password_hash varchar(255) NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_password_history_pkey PRIMARY KEY (id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_password_history_admin_id ON guardian_password_history (admin_id, created_at DESC);
+COMMENT ON TABLE guardian_password_history IS '密码历史表';
+COMMENT ON COLUMN guardian_password_history.id IS '记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_password_history.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_password_history.password_hash IS '历史密码argon2哈希';
+COMMENT ON COLUMN guardian_password_history.created_at IS '设置时间';
+
+CREATE TABLE IF NOT EXISTS guardian_password_reset_tokens (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    token_hash varchar(64) NOT NULL,
+    expires_at timestamptz(6) NOT NULL,
+    used_at timestamptz(6),
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_password_reset_tokens_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_password_reset_tokens_token_hash_key UNIQUE (token_hash)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_password_reset_tokens_admin_id ON guardian_password_reset_tokens (admin_id);
+COMMENT ON TABLE guardian_password_reset_tokens IS '密码重置令牌表';
+COMMENT ON COLUMN guardian_password_reset_tokens.id IS '记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_password_reset_tokens.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_password_reset_tokens.token_hash IS '重置令牌SHA-256摘要';
+COMMENT ON COLUMN guardian_password_reset_tokens.expires_at IS '过期时间';
+COMMENT ON COLUMN guardian_password_reset_tokens.used_at IS '使用时间（为空表示未使用）';
+COMMENT ON COLUMN guardian_password_reset_tokens.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_mfa_challenges (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    challenge_hash varchar(64) NOT NULL,
+    attempts int4 NOT NULL DEFAULT 0,
+    expires_at timestamptz(6) NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_mfa_challenges_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_mfa_challenges_challenge_hash_key UNIQUE (challenge_hash)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_mfa_challenges_admin_id ON guardian_mfa_challenges (admin_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_mfa_challenges_expires_at ON guardian_mfa_challenges (expires_at);
+COMMENT ON TABLE guardian_mfa_challenges IS '登录2FA挑战表';
+COMMENT ON COLUMN guardian_mfa_challenges.id IS '挑战ID（UUIDv7）';
+COMMENT ON COLUMN guardian_mfa_challenges.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_mfa_challenges.challenge_hash IS '挑战令牌SHA-256摘要';
+COMMENT ON COLUMN guardian_mfa_challenges.attempts IS '已尝试验证次数';
+COMMENT ON COLUMN guardian_mfa_challenges.expires_at IS '过期时间';
+COMMENT ON COLUMN guardian_mfa_challenges.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_webauthn_credentials (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    credential_id varchar(1400) NOT NULL,
+    public_key bytea NOT NULL,
+    algorithm int4 NOT NULL,
+    sign_count int8 NOT NULL DEFAULT 0,
+    name varchar(64) NOT NULL,
+    last_used_at timestamptz(6),
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_webauthn_credentials_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_webauthn_credentials_credential_id_key UNIQUE (credential_id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_webauthn_credentials_admin_id ON guardian_webauthn_credentials (admin_id);
+COMMENT ON TABLE guardian_webauthn_credentials IS 'WebAuthn凭证表';
+COMMENT ON COLUMN guardian_webauthn_credentials.id IS '凭证记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_webauthn_credentials.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_webauthn_credentials.credential_id IS '认证器凭证ID（base64url）';
+COMMENT ON COLUMN guardian_webauthn_credentials.public_key IS '公钥（ES256 为未压缩点，EdDSA 为原始公钥，RS256 为 PKCS#1）';
+COMMENT ON COLUMN guardian_webauthn_credentials.algorithm IS 'COSE 签名算法标识';
+COMMENT ON COLUMN guardian_webauthn_credentials.sign_count IS '认证器签名计数';
+COMMENT ON COLUMN guardian_webauthn_credentials.name IS '凭证名称';
+COMMENT ON COLUMN guardian_webauthn_credentials.last_used_at IS '最后使用时间';
+COMMENT ON COLUMN guardian_webauthn_credentials.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_webauthn_challenges (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid NOT NULL,
+    challenge varchar(64) NOT NULL,
+    expires_at timestamptz(6) NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_webauthn_challenges_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_webauthn_challenges_admin_id_key UNIQUE (admin_id)
+);
+COMMENT ON TABLE guardian_webauthn_challenges IS 'WebAuthn凭证注册挑战表';
+COMMENT ON COLUMN guardian_webauthn_challenges.id IS '挑战ID（UUIDv7）';
+COMMENT ON COLUMN guardian_webauthn_challenges.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键），每人最多一个未完成的注册';
+COMMENT ON COLUMN guardian_webauthn_challenges.challenge IS '注册挑战（base64url）';
+COMMENT ON COLUMN guardian_webauthn_challenges.expires_at IS '过期时间';
+COMMENT ON COLUMN guardian_webauthn_challenges.created_at IS '创建时间';
+
+-- ========== 角色与权限 ==========
+
+CREATE TABLE IF NOT EXISTS guardian_roles (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    code varchar(32) NOT NULL,
+    name varchar(64) NOT NULL,
+    description text,
+    is_system bool DEFAULT false,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    updated_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_roles_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_roles_code_key UNIQUE (code)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_roles_code ON guardian_roles (code);
+DROP TRIGGER IF EXISTS guardian_roles_updated_at ON guardian_roles;
+CREATE TRIGGER guardian_roles_updated_at BEFORE UPDATE ON guardian_roles
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+COMMENT ON TABLE guardian_roles IS '角色表';
+COMMENT ON COLUMN guardian_roles.id IS '角色ID（UUIDv7）';
+COMMENT ON COLUMN guardian_roles.code IS '角色代码（唯一）';
+COMMENT ON COLUMN guardian_roles.name IS '角色名称';
+COMMENT ON COLUMN guardian_roles.description IS '角色描述';
+COMMENT ON COLUMN guardian_roles.is_system IS '是否系统内置角色（不可删除）';
+COMMENT ON COLUMN guardian_roles.created_at IS '创建时间';
+COMMENT ON COLUMN guardian_roles.updated_at IS '更新时间';
+
+CREATE TABLE IF NOT EXISTS guardian_permissions (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    code varchar(64) NOT NULL,
+    name varchar(128) NOT NULL,
+    description text,
+    resource_type varchar(32) NOT NULL,
+    http_method varchar(10),
+    resource_path varchar(255),
+    parent_id uuid,
+    sort_order int4 DEFAULT 0,
+    is_system bool DEFAULT false,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    updated_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_permissions_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_permissions_code_key UNIQUE (code)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_permissions_code ON guardian_permissions (code);
+CREATE INDEX IF NOT EXISTS idx_guardian_permissions_parent ON guardian_permissions (parent_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_permissions_resource ON guardian_permissions (resource_type, resource_path);
+DROP TRIGGER IF EXISTS guardian_permissions_updated_at ON guardian_permissions;
+CREATE TRIGGER guardian_permissions_updated_at BEFORE UPDATE ON guardian_permissions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+COMMENT ON TABLE guardian_permissions IS '权限表';
+COMMENT ON COLUMN guardian_permissions.id IS '权限ID（UUIDv7）';
+COMMENT ON COLUMN guardian_permissions.code IS '权限代码（唯一）';
+COMMENT ON COLUMN guardian_permissions.name IS '权限名称';
+COMMENT ON COLUMN guardian_permissions.description IS '权限描述';
+COMMENT ON COLUMN guardian_permissions.resource_type IS '资源类型：api/menu/button';
+COMMENT ON COLUMN guardian_permissions.http_method IS 'HTTP方法：GET/POST/PUT/DELETE等';
+COMMENT ON COLUMN guardian_permissions.resource_path IS '资源路径';
+COMMENT ON COLUMN guardian_permissions.parent_id IS '父权限ID（逻辑关联，不使用外键）';
+COMMENT ON COLUMN guardian_permissions.sort_order IS '排序序号';
+COMMENT ON COLUMN guardian_permissions.is_system IS '是否系统内置权限（不可删除）';
+COMMENT ON COLUMN guardian_permissions.created_at IS '创建时间';
+COMMENT ON COLUMN guardian_permissions.updated_at IS '更新时间';
+
+CREATE TABLE IF NOT EXISTS guardian_admin_roles (
+    admin_id uuid NOT NULL,
+    role_id uuid NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_admin_roles_pkey PRIMARY KEY (admin_id, role_id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_admin_roles_admin_id ON guardian_admin_roles (admin_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_admin_roles_role_id ON guardian_admin_roles (role_id);
+COMMENT ON TABLE guardian_admin_roles IS '管理员角色关联表';
+COMMENT ON COLUMN guardian_admin_roles.admin_id IS '管理员ID（逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_admin_roles.role_id IS '角色ID（逻辑关联 guardian_roles.id，不使用外键）';
+COMMENT ON COLUMN guardian_admin_roles.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_role_permissions (
+    role_id uuid NOT NULL,
+    permission_id uuid NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_role_permissions_pkey PRIMARY KEY (role_id, permission_id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_role_permissions_permission_id ON guardian_role_permissions (permission_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_role_permissions_role_id ON guardian_role_permissions (role_id);
+COMMENT ON TABLE guardian_role_permissions IS '角色权限关联表';
+COMMENT ON COLUMN guardian_role_permissions.role_id IS '角色ID（逻辑关联 guardian_roles.id，不使用外键）';
+COMMENT ON COLUMN guardian_role_permissions.permission_id IS '权限ID（逻辑关联 guardian_permissions.id，不使用外键）';
+COMMENT ON COLUMN guardian_role_permissions.created_at IS '创建时间';
+
+-- ========== 安全 ==========
+
+CREATE TABLE IF NOT EXISTS guardian_token_blacklist (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    token_id varchar(128) NOT NULL,
+    expires_at timestamptz(6) NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_token_blacklist_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_token_blacklist_token_id_key UNIQUE (token_id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_token_blacklist_expires_at ON guardian_token_blacklist (expires_at);
+CREATE INDEX IF NOT EXISTS idx_guardian_token_blacklist_token_id ON guardian_token_blacklist (token_id);
+COMMENT ON TABLE guardian_token_blacklist IS '令牌黑名单表';
+COMMENT ON COLUMN guardian_token_blacklist.id IS '记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_token_blacklist.token_id IS '令牌标识（JWT的jti）';
+COMMENT ON COLUMN guardian_token_blacklist.expires_at IS '过期时间';
+COMMENT ON COLUMN guardian_token_blacklist.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_rate_limit_counters (
+    key varchar(255) NOT NULL,
+    window_index int8 NOT NULL,
+    count int8 NOT NULL DEFAULT 0,
+    expires_at timestamptz(6) NOT NULL,
+    CONSTRAINT guardian_rate_limit_counters_pkey PRIMARY KEY (key, window_index)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_rate_limit_counters_expires_at ON guardian_rate_limit_counters (expires_at);
+COMMENT ON TABLE guardian_rate_limit_counters IS '限流窗口计数表（多实例共享）';
+COMMENT ON COLUMN guardian_rate_limit_counters.key IS '限流键（策略名:计数维度）';
+COMMENT ON COLUMN guardian_rate_limit_counters.window_index IS '窗口编号（Unix毫秒 / 窗口毫秒数）';
+COMMENT ON COLUMN guardian_rate_limit_counters.count IS '窗口内请求数';
+COMMENT ON COLUMN guardian_rate_limit_counters.expires_at IS '过期时间';
+
+CREATE TABLE IF NOT EXISTS guardian_rate_limit_buckets (
+    key varchar(255) NOT NULL,
+    tokens float8 NOT NULL,
+    refilled_at_ms int8 NOT NULL,
+    last_allowed bool NOT NULL DEFAULT true,
+    expires_at timestamptz(6) NOT NULL,
+    CONSTRAINT guardian_rate_limit_buckets_pkey PRIMARY KEY (key)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_rate_limit_buckets_expires_at ON guardian_rate_limit_buckets (expires_at);
+COMMENT ON TABLE guardian_rate_limit_buckets IS '限流令牌桶表（多实例共享）';
+COMMENT ON COLUMN guardian_rate_limit_buckets.key IS '限流键（策略名:计数维度）';
+COMMENT ON COLUMN guardian_rate_limit_buckets.tokens IS '剩余令牌数';
+COMMENT ON COLUMN guardian_rate_limit_buckets.refilled_at_ms IS '上次补充令牌的时间（Unix毫秒）';
+COMMENT ON COLUMN guardian_rate_limit_buckets.last_allowed IS '最近一次取令牌是否成功';
+COMMENT ON COLUMN guardian_rate_limit_buckets.expires_at IS '令牌补满的时间，之后可删除';
+
+CREATE TABLE IF NOT EXISTS guardian_rate_limit_failures (
+    key varchar(255) NOT NULL,
+    failures int8 NOT NULL DEFAULT 0,
+    last_failure_ms int8 NOT NULL,
+    expires_at timestamptz(6) NOT NULL,
+    CONSTRAINT guardian_rate_limit_failures_pkey PRIMARY KEY (key)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_rate_limit_failures_expires_at ON guardian_rate_limit_failures (expires_at);
+COMMENT ON TABLE guardian_rate_limit_failures IS '连续失败计数表（登录节流的 IP 维度，多实例共享）';
+COMMENT ON COLUMN guardian_rate_limit_failures.key IS '计数键（用途:计数维度）';
+COMMENT ON COLUMN guardian_rate_limit_failures.failures IS '连续失败次数';
+COMMENT ON COLUMN guardian_rate_limit_failures.last_failure_ms IS '最近一次失败的时间（Unix毫秒）';
+COMMENT ON COLUMN guardian_rate_limit_failures.expires_at IS '计数清零的时间，之后可删除';
+
+CREATE TABLE IF NOT EXISTS guardian_ip_rules (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    admin_id uuid,
+    rule_type varchar(16) NOT NULL,
+    cidr varchar(64) NOT NULL,
+    description text,
+    created_by uuid,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    updated_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_ip_rules_pkey PRIMARY KEY (id),
+    CONSTRAINT guardian_ip_rules_rule_type_check CHECK (rule_type IN ('allow', 'deny'))
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_ip_rules_admin_id ON guardian_ip_rules (admin_id);
+DROP TRIGGER IF EXISTS guardian_ip_rules_updated_at ON guardian_ip_rules;
+CREATE TRIGGER guardian_ip_rules_updated_at BEFORE UPDATE ON guardian_ip_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+COMMENT ON TABLE guardian_ip_rules IS 'IP黑白名单表';
+COMMENT ON COLUMN guardian_ip_rules.id IS '规则ID（UUIDv7）';
+COMMENT ON COLUMN guardian_ip_rules.admin_id IS '作用的管理员ID（为空表示全局规则，逻辑关联 guardian_admins.id，不使用外键）';
+COMMENT ON COLUMN guardian_ip_rules.rule_type IS '规则类型：allow/deny';
+COMMENT ON COLUMN guardian_ip_rules.cidr IS 'CIDR网段（单个IP存为/32或/128）';
+COMMENT ON COLUMN guardian_ip_rules.description IS '备注';
+COMMENT ON COLUMN guardian_ip_rules.created_by IS '创建者管理员ID';
+COMMENT ON COLUMN guardian_ip_rules.created_at IS '创建时间';
+COMMENT ON COLUMN guardian_ip_rules.updated_at IS '更新时间';
+
+-- ========== 审计与监控 ==========
+
+CREATE TABLE IF NOT EXISTS guardian_audit_logs (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    trace_id varchar(64),
+    admin_id uuid,
+    username varchar(64),
+    action varchar(32) NOT NULL,
+    resource varchar(255) NOT NULL,
+    method varchar(10) NOT NULL,
+    params jsonb,
+    result jsonb,
+    status_code int4 NOT NULL,
+    ip_address inet,
+    user_agent text,
+    duration_ms int4 NOT NULL,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_audit_logs_pkey PRIMARY KEY (id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_audit_logs_admin_id ON guardian_audit_logs (admin_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_audit_logs_created_at ON guardian_audit_logs (created_at);
+CREATE INDEX IF NOT EXISTS idx_guardian_audit_logs_trace_id ON guardian_audit_logs (trace_id);
+CREATE INDEX IF NOT EXISTS idx_guardian_audit_logs_username ON guardian_audit_logs (username);
+COMMENT ON TABLE guardian_audit_logs IS '审计日志表';
+COMMENT ON COLUMN guardian_audit_logs.id IS '日志ID（UUIDv7）';
+COMMENT ON COLUMN guardian_audit_logs.trace_id IS '请求追踪ID（用于关联同一请求的多个操作）';
+COMMENT ON COLUMN guardian_audit_logs.admin_id IS '操作管理员ID（逻辑关联，不使用外键）';
+COMMENT ON COLUMN guardian_audit_logs.username IS '操作用户名';
+COMMENT ON COLUMN guardian_audit_logs.action IS '操作类型：login/logout/create/update/delete等';
+COMMENT ON COLUMN guardian_audit_logs.resource IS '操作资源：如/admins/123';
+COMMENT ON COLUMN guardian_audit_logs.method IS 'HTTP方法：GET/POST/PUT/DELETE';
+COMMENT ON COLUMN guardian_audit_logs.params IS '请求参数（JSONB格式）';
+COMMENT ON COLUMN guardian_audit_logs.result IS '操作结果（JSONB格式）';
+COMMENT ON COLUMN guardian_audit_logs.status_code IS 'HTTP状态码';
+COMMENT ON COLUMN guardian_audit_logs.ip_address IS '客户端IP地址';
+COMMENT ON COLUMN guardian_audit_logs.user_agent IS '客户端User-Agent';
+COMMENT ON COLUMN guardian_audit_logs.duration_ms IS '请求耗时（毫秒）';
+COMMENT ON COLUMN guardian_audit_logs.created_at IS '创建时间';
+
+CREATE TABLE IF NOT EXISTS guardian_systeminfo (
+    id uuid NOT NULL DEFAULT uuid_v7(),
+    cpu_count int4,
+    cpu_total_load numeric(5,2),
+    memory_used int8,
+    memory_total int8,
+    disk_used int8,
+    disk_total int8,
+    network_upload int8,
+    network_download int8,
+    created_at timestamptz(6) NOT NULL DEFAULT now(),
+    CONSTRAINT guardian_systeminfo_pkey PRIMARY KEY (id)
+);
+CREATE INDEX IF NOT EXISTS idx_guardian_systeminfo_created_at ON guardian_systeminfo (created_at);
+COMMENT ON TABLE guardian_systeminfo IS '系统监控信息表';
+COMMENT ON COLUMN guardian_systeminfo.id IS '记录ID（UUIDv7）';
+COMMENT ON COLUMN guardian_systeminfo.cpu_count IS 'CPU核心数';
+COMMENT ON COLUMN guardian_systeminfo.cpu_total_load IS 'CPU总使用率（%）';
+COMMENT ON COLUMN guardian_systeminfo.memory_used IS '已使用内存（字节）';
+COMMENT ON COLUMN guardian_systeminfo.memory_total IS '总内存（字节）';
+COMMENT ON COLUMN guardian_systeminfo.disk_used IS '已使用磁盘空间（字节）';
+COMMENT ON COLUMN guardian_systeminfo.disk_total IS '总磁盘空间（字节）';
+COMMENT ON COLUMN guardian_systeminfo.network_upload IS '网络上传量（字节）';
+COMMENT ON COLUMN guardian_systeminfo.network_download IS '网络下载量（字节）';
+COMMENT ON COLUMN guardian_systeminfo.created_at IS '记录创建时间';
@@ -0,0 +1,261 @@
+use axum::Router;
+use utoipa::{
+    Modify, OpenApi, PartialSchema, ToSchema,
+    openapi::{
+        ContentBuilder, OpenApi as OpenApiDoc, Ref, ResponseBuilder, Server,
+        path::Operation,
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    },
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::controller::{
+    admin_controller, auth_controller, ip_rule_controller, permission_controller, rbac_controller,
+    role_controller, system_info_controller, webauthn_controller,
+};
+use crate::response::ErrorResponse;
+use crate::router::API_PREFIX;
+
+/// 由控制器上的 `#[utoipa::path]` 和 DTO 上的 `ToSchema` 生成的 OpenAPI 文档，
+/// 路径相对于 `API_PREFIX`
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Guardian Auth API",
+        description = "Guardian 认证与权限管理接口。所有响应使用统一格式，失败时同时返回对应的 HTTP 状态码。"
+    ),
+    paths(
+        auth_controller::login,
+        auth_controller::login_2fa,
+        auth_controller::refresh_token,
+        auth_controller::reset_password,
+        auth_controller::request_password_reset,
+        auth_controller::confirm_password_reset,
+        auth_controller::logout,
+        auth_controller::change_password,
+        auth_controller::update_preferences,
+        auth_controller::setup_2fa,
+        auth_controller::verify_2fa,
+        auth_controller::disable_2fa,
+        webauthn_controller::webauthn_register_options,
+        webauthn_controller::webauthn_register,
+        webauthn_controller::list_webauthn_credentials,
+        webauthn_controller::delete_webauthn_credential,
+        admin_controller::list_admin,
+        admin_controller::create_admin,
+        admin_controller::get_admin,
+        admin_controller::update_admin,
+        admin_controller::delete_admin,
+        admin_controller::assign_roles,
+        admin_controller::unlock_admin,
+        role_controller::list_role,
+        role_controller::create_role,
+        role_controller::get_role,
+        role_controller::update_role,
+        role_controller::delete_role,
+        role_controller::assign_permissions,
+        permission_controller::get_permission_tree,
+        permission_controller::list_permission,
+        permission_controller::create_permission,
+        permission_controller::get_permission,
+        permission_controller::update_permission,
+        permission_controller::delete_permission,
+        rbac_controller::export_rbac_document,
+        rbac_controller::import_rbac_document,
+        ip_rule_controller::list_ip_rule,
+        ip_rule_controller::create_ip_rule,
+        ip_rule_controller::get_ip_rule,
+        ip_rule_controller::update_ip_rule,
+        ip_rule_controller::delete_ip_rule,
+        system_info_controller::list_system_info,
+    ),
+    modifiers(&ServerPrefix, &BearerAuth, &ErrorResponses),
+    tags(
+        (name = "认证", description = "登录、令牌和当前账户自助操作"),
+        (name = "管理员", description = "管理员账户管理"),
+        (name = "角色", description = "角色管理与权限分配"),
+        (name = "权限", description = "权限管理"),
+        (name = "IP 规则", description = "IP 黑白名单"),
+        (name = "系统信息", description = "系统监控数据"),
+    )
+)]
+pub struct ApiDoc;
+
+struct ServerPrefix;
+
+impl Modify for ServerPrefix {
+    fn modify(&self, openapi: &mut OpenApiDoc) {
+        openapi.servers = Some(vec![Server::new(API_PREFIX)]);
+    }
+}
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut OpenApiDoc) {
+        openapi
+            .components
+            .get_or_insert_default()
+            .add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+    }
+}
+
+/// 补充由提取器和中间件产生、所有接口共有的失败响应。
+///
+/// 接口自身的业务失败在各自的 `#[utoipa::path]` 中声明，同一状态码以接口上的说明为准
+struct ErrorResponses;
+
+impl ErrorResponses {
+    fn add(operation: &mut Operation, status: &str, description: &str) {
+        let response = ResponseBuilder::new()
+            .description(description)
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name(ErrorResponse::name())))
+                    .build(),
+            )
+            .build();
+        operation
+            .responses
+            .responses
+            .entry(status.to_string())
+            .or_insert(response.into());
+    }
+}
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut OpenApiDoc) {
+        openapi
+            .components
+            .get_or_insert_default()
+            .schemas
+            .insert(ErrorResponse::name().into_owned(), ErrorResponse::schema());
+
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.patch,
+                &mut item.delete,
+            ];
+            for operation in operations.into_iter().flatten() {
+                if operation.request_body.is_some() {
+                    Self::add(operation, "400", "请求体格式错误");
+                }
+                if operation.security.as_ref().is_some_and(|s| !s.is_empty()) {
+                    Self::add(operation, "401", "认证失败或令牌已失效");
+                    Self::add(operation, "403", "权限不足或 IP 不允许访问");
+                }
+                Self::add(operation, "422", "参数验证失败，data 中按字段列出错误信息");
+                Self::add(operation, "429", "请求频率过高");
+                Self::add(operation, "500", "系统内部错误");
+            }
+        }
+    }
+}
+
+/// `openapi.json` 和内置的 Swagger UI（静态资源编译时嵌入）
+pub fn api_docs_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    SwaggerUi::new(format!("{}/docs", API_PREFIX))
+        .url(format!("{}/openapi.json", API_PREFIX), ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use utoipa::openapi::RefOr;
+    use utoipa::openapi::path::HttpMethod;
+
+    use super::*;
+    use crate::router::api_routes;
+
+    /// 路由表中的每个接口都要有文档，文档中也不能有未注册的接口
+    #[test]
+    fn test_all_routes_documented() {
+        let spec = ApiDoc::openapi();
+        let routes = api_routes();
+        for route in &routes {
+            let method = match route.method {
+                Method::GET => HttpMethod::Get,
+                Method::POST => HttpMethod::Post,
+                Method::PUT => HttpMethod::Put,
+                Method::PATCH => HttpMethod::Patch,
+                _ => HttpMethod::Delete,
+            };
+            let documented = spec.paths.get_path_operation(route.path, method).is_some();
+            assert!(
+                documented,
+                "{} {} 缺少 OpenAPI 文档",
+                route.method, route.path
+            );
+        }
+
+        let operations: usize = spec
+            .paths
+            .paths
+            .values()
+            .map(|item| {
+                [&item.get, &item.post, &item.put, &item.patch, &item.delete]
+                    .iter()
+                    .filter(|op| op.is_some())
+                    .count()
+            })
+            .sum();
+        assert_eq!(operations, routes.len(), "文档中存在未注册的接口");
+    }
+
+    #[test]
+    fn test_error_responses() {
+        let spec = ApiDoc::openapi();
+        let statuses = |path: &str, method: HttpMethod| -> Vec<String> {
+            let operation = spec.paths.get_path_operation(path, method).unwrap();
+            operation.responses.responses.keys().cloned().collect()
+        };
+
+        let login = statuses("/auth/login", HttpMethod::Post);
+        for status in ["400", "401", "403", "422", "429", "500"] {
+            assert!(login.iter().any(|s| s == status), "登录接口缺少 {}", status);
+        }
+        for path in ["/auth/login/2fa", "/auth/refresh"] {
+            assert!(statuses(path, HttpMethod::Post).iter().any(|s| s == "401"));
+        }
+
+        let list_admin = statuses("/admins", HttpMethod::Get);
+        assert!(list_admin.iter().any(|s| s == "401"));
+        assert!(!list_admin.iter().any(|s| s == "400"));
+        let get_admin = statuses("/admins/{id}", HttpMethod::Get);
+        assert!(get_admin.iter().any(|s| s == "404"));
+
+        // 接口上声明的说明不被通用说明覆盖
+        let login = spec
+            .paths
+            .get_path_operation("/auth/login", HttpMethod::Post)
+            .unwrap();
+        let RefOr::T(unauthorized) = &login.responses.responses["401"] else {
+            panic!("401 响应应直接声明");
+        };
+        assert_eq!(unauthorized.description, "用户名或密码错误");
+
+        assert!(
+            spec.components
+                .as_ref()
+                .unwrap()
+                .schemas
+                .contains_key("ErrorResponse")
+        );
+    }
+}
@@ -1,6 +1,13 @@
+use axum::http::StatusCode;
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+use utoipa::openapi::{RefOr, schema::Schema};
+use utoipa::{PartialSchema, ToSchema};
 
+use crate::i18n::t;
+use crate::trace::current_trace_id;
+
+#[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResponseCode {
     Success = 200,
@@ -17,6 +24,15 @@ pub enum ResponseCode {
     InvalidTwoFaCode = 17008,
     TwoFaNotEnabled = 17009,
     TwoFaAlreadyEnabled = 17010,
+    TwoFaRequired = 17011,
+    MfaChallengeInvalid = 17012,
+    PasswordChangeRequired = 17013,
+    ResetTokenInvalid = 17014,
+    CaptchaRequired = 17015,
+    IpNotAllowed = 17016,
+    ResourceConflict = 17017,
+    ServiceUnavailable = 17018,
+    ClientCertificateRequired = 17019,
 }
 
 impl ResponseCode {
@@ -24,37 +40,100 @@ impl ResponseCode {
         *self as u32
     }
 
-    pub fn msg(&self) -> &'static str {
+    /// 消息目录中的 key
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            ResponseCode::Success => "code.success",
+            ResponseCode::GenericError => "code.generic_error",
+            ResponseCode::UnknownError => "code.unknown_error",
+            ResponseCode::InternalError => "code.internal_error",
+            ResponseCode::ValidationError => "code.validation_error",
+            ResponseCode::AuthError => "code.auth_error",
+            ResponseCode::TokenExpired => "code.token_expired",
+            ResponseCode::PermissionDenied => "code.permission_denied",
+            ResponseCode::ResourceNotFound => "code.resource_not_found",
+            ResponseCode::RateLimitExceeded => "code.rate_limit_exceeded",
+            ResponseCode::TwoFaVerificationFailed => "code.two_fa_verification_failed",
+            ResponseCode::InvalidTwoFaCode => "code.invalid_two_fa_code",
+            ResponseCode::TwoFaNotEnabled => "code.two_fa_not_enabled",
+            ResponseCode::TwoFaAlreadyEnabled => "code.two_fa_already_enabled",
+            ResponseCode::TwoFaRequired => "code.two_fa_required",
+            ResponseCode::MfaChallengeInvalid => "code.mfa_challenge_invalid",
+            ResponseCode::PasswordChangeRequired => "code.password_change_required",
+            ResponseCode::ResetTokenInvalid => "code.reset_token_invalid",
+            ResponseCode::CaptchaRequired => "code.captcha_required",
+            ResponseCode::IpNotAllowed => "code.ip_not_allowed",
+            ResponseCode::ResourceConflict => "code.resource_conflict",
+            ResponseCode::ServiceUnavailable => "code.service_unavailable",
+            ResponseCode::ClientCertificateRequired => "code.client_certificate_required",
+        }
+    }
+
+    /// 按当前请求的语言渲染的默认提示信息
+    pub fn msg(&self) -> String {
+        t!(self.message_key())
+    }
+
+    /// 业务错误码对应的 HTTP 状态码
+    pub fn status(&self) -> StatusCode {
         match self {
-            ResponseCode::Success => "success",
-            ResponseCode::GenericError => "通用请求失败",
-            ResponseCode::UnknownError => "未知错误",
-            ResponseCode::InternalError => "系统内部错误",
-            ResponseCode::ValidationError => "参数验证失败",
-            ResponseCode::AuthError => "认证失败",
-            ResponseCode::TokenExpired => "令牌过期",
-            ResponseCode::PermissionDenied => "权限不足",
-            ResponseCode::ResourceNotFound => "资源不存在",
-            ResponseCode::RateLimitExceeded => "请求频率过高",
-            ResponseCode::TwoFaVerificationFailed => "2FA验证失败",
-            ResponseCode::InvalidTwoFaCode => "无效的2FA验证码",
-            ResponseCode::TwoFaNotEnabled => "未启用2FA",
-            ResponseCode::TwoFaAlreadyEnabled => "已启用2FA",
+            ResponseCode::Success | ResponseCode::TwoFaRequired => StatusCode::OK,
+            ResponseCode::GenericError
+            | ResponseCode::TwoFaNotEnabled
+            | ResponseCode::ResetTokenInvalid
+            | ResponseCode::CaptchaRequired => StatusCode::BAD_REQUEST,
+            ResponseCode::UnknownError | ResponseCode::InternalError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ResponseCode::ValidationError => StatusCode::UNPROCESSABLE_ENTITY,
+            ResponseCode::AuthError
+            | ResponseCode::TokenExpired
+            | ResponseCode::TwoFaVerificationFailed
+            | ResponseCode::InvalidTwoFaCode
+            | ResponseCode::MfaChallengeInvalid => StatusCode::UNAUTHORIZED,
+            ResponseCode::PermissionDenied
+            | ResponseCode::PasswordChangeRequired
+            | ResponseCode::IpNotAllowed
+            | ResponseCode::ClientCertificateRequired => StatusCode::FORBIDDEN,
+            ResponseCode::ResourceNotFound => StatusCode::NOT_FOUND,
+            ResponseCode::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            ResponseCode::TwoFaAlreadyEnabled | ResponseCode::ResourceConflict => {
+                StatusCode::CONFLICT
+            }
+            ResponseCode::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct Response<T>
 where
     T: Serialize,
 {
+    /// 业务状态码，200 表示成功
     pub code: u32,
+    /// 提示信息，按请求语言返回
     pub msg: Option<String>,
     pub data: Option<T>,
+    /// 毫秒时间戳
     pub timestamp: i64,
+    /// 请求追踪 ID，与响应头 `X-Request-Id` 及审计日志一致，排查问题时提供给管理员
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+/// 失败响应在 OpenAPI 文档中的结构，与 `Response<()>` 相同；字段校验失败时 `data` 中按字段列出错误信息
+pub struct ErrorResponse;
+
+impl PartialSchema for ErrorResponse {
+    fn schema() -> RefOr<Schema> {
+        Response::<()>::schema()
+    }
 }
 
+impl ToSchema for ErrorResponse {}
+
+#[allow(dead_code)]
 impl<T> Response<T>
 where
     T: Serialize,
@@ -65,6 +144,7 @@ where
             msg,
             data,
             timestamp: Local::now().timestamp_millis(),
+            trace_id: current_trace_id(),
         }
     }
 
@@ -108,16 +188,17 @@ where
     where
         T: Default,
     {
-        let final_msg = msg.or_else(|| Some(code.msg().to_string()));
+        let final_msg = msg.or_else(|| Some(code.msg()));
         Self::new(code.code(), final_msg, None)
     }
 
     pub fn from_code_with_data(code: ResponseCode, msg: Option<String>, data: T) -> Self {
-        let final_msg = msg.or_else(|| Some(code.msg().to_string()));
+        let final_msg = msg.or_else(|| Some(code.msg()));
         Self::new(code.code(), final_msg, Some(data))
     }
 }
 
+#[allow(dead_code)]
 impl ResponseCode {
     pub fn to_response<T>(self, msg: Option<String>) -> Response<T>
     where
@@ -143,6 +224,7 @@ where
     }
 }
 
+#[allow(dead_code)]
 impl Response<()> {
     pub fn ok_msg_only(msg: String) -> Self {
         Self::new(ResponseCode::Success.code(), Some(msg), None)
@@ -163,6 +245,7 @@ where
             msg: None,
             data: Some(T::default()),
             timestamp: Local::now().timestamp_millis(),
+            trace_id: current_trace_id(),
         }
     }
 }
@@ -1,120 +1,535 @@
-use anyhow::{Ok, Result};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Ok, Result};
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    handler::Handler,
+    http::{HeaderName, HeaderValue, Method},
+    routing::{MethodFilter, MethodRouter, get, on},
 };
-use sea_orm::{Database, DatabaseConnection};
-use tower_http::cors::{Any, CorsLayer};
+use log::warn;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
 
+use crate::config::{Config, CorsConfig, DatabaseConfig};
 use crate::controller::{
     admin_controller::*,
     auth_controller::{disable_2fa, *},
+    health_controller::{healthz, readyz},
+    ip_rule_controller::*,
+    metrics_controller::metrics,
     permission_controller::*,
+    rbac_controller::*,
     role_controller::*,
     root,
     system_info_controller::*,
+    webauthn_controller::*,
 };
+use crate::i18n::set_default_locale;
 use crate::middleware::middleware_api::{
-    auth_middleware, permission_middleware, rate_limit_middleware,
+    RateLimiter, SecurityHeaders, TrustedProxies, auth_middleware, client_cert_middleware,
+    client_ip_middleware, ip_filter_middleware, locale_middleware, metrics_middleware,
+    permission_middleware, rate_limit_middleware, security_headers_middleware, trace_middleware,
 };
+use crate::migration::run_migrations;
+use crate::openapi::api_docs_router;
+use crate::service::builtin_permission_service::sync_builtin_permissions;
+use crate::service::health_service::Lifecycle;
+use crate::service::ip_rule_service::IpRuleCache;
+use crate::service::login_throttle_service::LoginThrottle;
+use crate::store::{RevocationStore, counter_store, revocation_store};
+use crate::utils::captcha::{CaptchaVerifier, captcha_verifier_from_config};
+use crate::utils::mail::{Mailer, mailer_from_config};
 
-const API_PREFIX: &str = "/guardian-auth/v1";
+pub(crate) const API_PREFIX: &str = "/guardian-auth/v1";
 
 #[derive(Clone)]
 pub(crate) struct AppState {
+    pub(crate) config: Arc<Config>,
     pub(crate) conn: DatabaseConnection,
+    pub(crate) mailer: Option<Arc<dyn Mailer>>,
+    pub(crate) login_throttle: LoginThrottle,
+    pub(crate) captcha: Option<Arc<dyn CaptchaVerifier>>,
+    pub(crate) rate_limiter: RateLimiter,
+    pub(crate) revocations: Arc<dyn RevocationStore>,
+    pub(crate) trusted_proxies: TrustedProxies,
+    pub(crate) ip_rules: IpRuleCache,
+    pub(crate) lifecycle: Arc<Lifecycle>,
 }
 
-pub(crate) async fn get_router() -> Result<Router> {
-    let db_connection_str = std::env::var("DATABASE_URL").unwrap();
+impl AppState {
+    pub(crate) fn new(config: Config, conn: DatabaseConnection) -> Result<Self> {
+        let counters = counter_store(&config.rate_limit.store, &conn)?;
+        let rate_limiter = RateLimiter::from_config(&config.rate_limit, counters.clone());
+        let revocations = revocation_store(&config.jwt.revocation_store, &conn)?;
 
-    let conn = Database::connect(db_connection_str)
-        .await
-        .expect("Database connection failed");
+        let mailer = mailer_from_config(&config.mail)?;
+        let login_throttle = LoginThrottle::new(config.login_throttle.clone(), counters);
+        let captcha = captcha_verifier_from_config(&config.captcha);
+        let trusted_proxies = TrustedProxies::from_config(&config.server.trusted_proxies);
+        let ip_rules = IpRuleCache::new(Duration::from_secs(config.ip_rules.cache_secs));
 
-    let state = AppState { conn };
+        Ok(Self {
+            config: Arc::new(config),
+            conn,
+            mailer,
+            login_throttle,
+            captcha,
+            rate_limiter,
+            revocations,
+            trusted_proxies,
+            ip_rules,
+            lifecycle: Arc::default(),
+        })
+    }
+}
 
-    // 公开路由（无需认证）
-    let public_routes = Router::new()
-        .route("/", get(root))
-        .route(&format!("{}/auth/login", API_PREFIX), post(login))
-        .route(&format!("{}/auth/refresh", API_PREFIX), post(refresh_token))
-        .route(
-            &format!("{}/auth/reset-password", API_PREFIX),
-            post(reset_password),
-        );
+pub(crate) async fn build_state(config: Config) -> Result<AppState> {
+    set_default_locale(config.server.default_locale);
+    let conn = connect_database(&config.database).await?;
+    if config.database.auto_migrate {
+        run_migrations(&conn).await?;
+    }
+    // 内置权限同步失败不影响启动，超级管理员仍可正常访问
+    if let Err(e) = sync_builtin_permissions(&conn).await {
+        warn!("内置接口权限同步失败: {}", e);
+    }
 
-    // 受保护路由（需要认证）
-    let protected_routes = Router::new()
-        .route(&format!("{}/auth/logout", API_PREFIX), post(logout))
-        .route(
-            &format!("{}/auth/change-password", API_PREFIX),
-            post(change_password),
-        )
-        .route(&format!("{}/auth/2fa/setup", API_PREFIX), post(setup_2fa))
-        .route(&format!("{}/auth/2fa/verify", API_PREFIX), post(verify_2fa))
-        .route(
-            &format!("{}/auth/2fa/disable", API_PREFIX),
-            post(disable_2fa),
-        )
-        .route(&format!("{}/admins", API_PREFIX), get(list_admin))
-        .route(&format!("{}/admins", API_PREFIX), post(create_admin))
-        .route(&format!("{}/admins/{{id}}", API_PREFIX), get(get_admin))
-        .route(&format!("{}/admins/{{id}}", API_PREFIX), put(update_admin))
-        .route(
-            &format!("{}/admins/{{id}}", API_PREFIX),
-            delete(delete_admin),
-        )
-        .route(
-            &format!("{}/admins/{{id}}/roles", API_PREFIX),
-            post(assign_roles),
-        )
-        .route(&format!("{}/roles", API_PREFIX), get(list_role))
-        .route(&format!("{}/roles", API_PREFIX), post(create_role))
-        .route(&format!("{}/roles/{{id}}", API_PREFIX), get(get_role))
-        .route(&format!("{}/roles/{{id}}", API_PREFIX), put(update_role))
-        .route(&format!("{}/roles/{{id}}", API_PREFIX), delete(delete_role))
-        .route(
-            &format!("{}/roles/{{id}}/permissions", API_PREFIX),
-            post(assign_permissions),
-        )
-        .route(
-            &format!("{}/permissions/tree", API_PREFIX),
-            get(get_permission_tree),
-        )
-        .route(&format!("{}/permissions", API_PREFIX), get(list_permission))
-        .route(
-            &format!("{}/permissions", API_PREFIX),
-            post(create_permission),
-        )
-        .route(
-            &format!("{}/permissions/{{id}}", API_PREFIX),
-            get(get_permission),
-        )
-        .route(
-            &format!("{}/permissions/{{id}}", API_PREFIX),
-            put(update_permission),
+    AppState::new(config, conn)
+}
+
+pub(crate) async fn connect_database(database: &DatabaseConfig) -> Result<DatabaseConnection> {
+    let mut options = ConnectOptions::new(database.url.clone());
+    options
+        .max_connections(database.max_connections)
+        .min_connections(database.min_connections)
+        .connect_timeout(database.connect_timeout());
+    if let Some(idle_timeout) = database.idle_timeout() {
+        options.idle_timeout(idle_timeout);
+    }
+
+    Database::connect(options).await.context("数据库连接失败")
+}
+
+/// 配置中包含 `*` 时允许任意来源、方法或请求头，否则只放行列出的值。
+/// 允许携带凭据时浏览器不接受通配符，方法和请求头改为回显预检请求中的值
+fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let origin = if config.allow_any_origin() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            config
+                .allowed_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
         )
-        .route(
-            &format!("{}/permissions/{{id}}", API_PREFIX),
-            delete(delete_permission),
+    };
+    let methods = match (config.allow_any_method(), config.allow_credentials) {
+        (true, false) => AllowMethods::any(),
+        (true, true) => AllowMethods::mirror_request(),
+        (false, _) => AllowMethods::list(
+            config
+                .allowed_methods
+                .iter()
+                .filter_map(|method| Method::from_bytes(method.as_bytes()).ok()),
+        ),
+    };
+    let headers = match (config.allow_any_header(), config.allow_credentials) {
+        (true, false) => AllowHeaders::any(),
+        (true, true) => AllowHeaders::mirror_request(),
+        (false, _) => AllowHeaders::list(
+            config
+                .allowed_headers
+                .iter()
+                .filter_map(|header| HeaderName::from_bytes(header.as_bytes()).ok()),
+        ),
+    };
+
+    let mut layer = CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .expose_headers(
+            config
+                .expose_headers
+                .iter()
+                .filter_map(|header| HeaderName::from_bytes(header.as_bytes()).ok())
+                .collect::<Vec<_>>(),
         )
-        .route(&format!("{}/systeminfo", API_PREFIX), get(list_system_info))
+        .allow_credentials(config.allow_credentials);
+    if config.max_age_secs > 0 {
+        layer = layer.max_age(Duration::from_secs(config.max_age_secs));
+    }
+    layer
+}
+
+/// 一条业务路由，路径相对于 `API_PREFIX`。
+///
+/// 路由表同时用于注册路由和校验 OpenAPI 文档、内置权限是否齐全
+pub(crate) struct ApiRoute {
+    // 注册时方法已包含在 `handler` 中，单独保存供测试核对
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) method: Method,
+    pub(crate) path: &'static str,
+    handler: MethodRouter<AppState>,
+}
+
+fn api<H, T>(method: Method, path: &'static str, handler: H) -> ApiRoute
+where
+    H: Handler<T, AppState>,
+    T: 'static,
+{
+    let filter = MethodFilter::try_from(method.clone()).expect("路由表中的 HTTP 方法无效");
+    ApiRoute {
+        method,
+        path,
+        handler: on(filter, handler),
+    }
+}
+
+/// 公开路由（无需认证）
+pub(crate) fn public_api_routes() -> Vec<ApiRoute> {
+    vec![
+        api(Method::POST, "/auth/login", login),
+        api(Method::POST, "/auth/login/2fa", login_2fa),
+        api(Method::POST, "/auth/refresh", refresh_token),
+        api(Method::POST, "/auth/reset-password", reset_password),
+        api(
+            Method::POST,
+            "/auth/password-reset/request",
+            request_password_reset,
+        ),
+        api(
+            Method::POST,
+            "/auth/password-reset/confirm",
+            confirm_password_reset,
+        ),
+    ]
+}
+
+/// 账户自助路由（需要认证，只操作当前登录账户，不做 RBAC 权限检查）
+pub(crate) fn self_service_api_routes() -> Vec<ApiRoute> {
+    vec![
+        api(Method::POST, "/auth/logout", logout),
+        api(Method::POST, "/auth/change-password", change_password),
+        api(Method::PUT, "/auth/preferences", update_preferences),
+        api(Method::POST, "/auth/2fa/setup", setup_2fa),
+        api(Method::POST, "/auth/2fa/verify", verify_2fa),
+        api(Method::POST, "/auth/2fa/disable", disable_2fa),
+        api(
+            Method::POST,
+            "/auth/webauthn/register/options",
+            webauthn_register_options,
+        ),
+        api(Method::POST, "/auth/webauthn/register", webauthn_register),
+        api(
+            Method::GET,
+            "/auth/webauthn/credentials",
+            list_webauthn_credentials,
+        ),
+        api(
+            Method::DELETE,
+            "/auth/webauthn/credentials/{id}",
+            delete_webauthn_credential,
+        ),
+    ]
+}
+
+/// 受保护路由（需要认证和权限检查）
+pub(crate) fn protected_api_routes() -> Vec<ApiRoute> {
+    vec![
+        api(Method::GET, "/admins", list_admin),
+        api(Method::POST, "/admins", create_admin),
+        api(Method::GET, "/admins/{id}", get_admin),
+        api(Method::PUT, "/admins/{id}", update_admin),
+        api(Method::DELETE, "/admins/{id}", delete_admin),
+        api(Method::POST, "/admins/{id}/roles", assign_roles),
+        api(Method::POST, "/admins/{id}/unlock", unlock_admin),
+        api(Method::GET, "/roles", list_role),
+        api(Method::POST, "/roles", create_role),
+        api(Method::GET, "/roles/{id}", get_role),
+        api(Method::PUT, "/roles/{id}", update_role),
+        api(Method::DELETE, "/roles/{id}", delete_role),
+        api(Method::POST, "/roles/{id}/permissions", assign_permissions),
+        api(Method::GET, "/permissions/tree", get_permission_tree),
+        api(Method::GET, "/permissions", list_permission),
+        api(Method::POST, "/permissions", create_permission),
+        api(Method::GET, "/permissions/{id}", get_permission),
+        api(Method::PUT, "/permissions/{id}", update_permission),
+        api(Method::DELETE, "/permissions/{id}", delete_permission),
+        api(Method::GET, "/rbac/export", export_rbac_document),
+        api(Method::POST, "/rbac/import", import_rbac_document),
+        api(Method::GET, "/ip-rules", list_ip_rule),
+        api(Method::POST, "/ip-rules", create_ip_rule),
+        api(Method::GET, "/ip-rules/{id}", get_ip_rule),
+        api(Method::PUT, "/ip-rules/{id}", update_ip_rule),
+        api(Method::DELETE, "/ip-rules/{id}", delete_ip_rule),
+        api(Method::GET, "/systeminfo", list_system_info),
+    ]
+}
+
+/// 所有业务路由
+#[cfg(test)]
+pub(crate) fn api_routes() -> Vec<ApiRoute> {
+    let mut routes = public_api_routes();
+    routes.extend(self_service_api_routes());
+    routes.extend(protected_api_routes());
+    routes
+}
+
+fn mount(routes: Vec<ApiRoute>) -> Router<AppState> {
+    routes.into_iter().fold(Router::new(), |router, route| {
+        router.route(&format!("{}{}", API_PREFIX, route.path), route.handler)
+    })
+}
+
+pub(crate) fn get_router(state: AppState) -> Router {
+    let public_routes = mount(public_api_routes())
+        .route("/", get(root))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ));
+
+    let self_service_routes = mount(self_service_api_routes())
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    let protected_routes = mount(protected_api_routes())
         .route_layer(axum::middleware::from_fn_with_state(
             state.clone(),
             permission_middleware,
         ))
-        .route_layer(axum::middleware::from_fn(auth_middleware));
-    let app = Router::new()
+        // 限流在认证之后、权限检查之前执行，以便按账户计数
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+    let mut router = Router::new()
         .merge(public_routes)
-        .merge(protected_routes)
-        .route_layer(axum::middleware::from_fn(rate_limit_middleware))
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
-        )
-        .with_state(state);
-    Ok(app)
+        .merge(self_service_routes)
+        .merge(protected_routes);
+    if state.config.server.api_docs_enabled {
+        router = router.merge(api_docs_router());
+    }
+    // 指标可能暴露访问量等信息，默认不提供；启用后与 API 一样受 IP 黑白名单约束
+    if state.config.metrics.enabled {
+        router = router.route("/metrics", get(metrics));
+    }
+
+    router
+        // 服务间调用的接口要求客户端证书，未启用客户端证书校验时直接放行
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            client_cert_middleware,
+        ))
+        // IP 黑白名单作用于 API 和 /metrics，在所有分组的认证之前执行，依赖外层解析出的客户端 IP
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            ip_filter_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            client_ip_middleware,
+        ))
+        // 探针在 IP 黑白名单之外，不受名单配置影响
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        // 语言协商放在最外层，IP 拦截等早期失败也按请求语言返回
+        .layer(axum::middleware::from_fn(locale_middleware))
+        .layer(axum::middleware::from_fn(metrics_middleware))
+        .layer(axum::middleware::from_fn(trace_middleware))
+        .layer(cors_layer(&state.config.cors))
+        // 预检响应同样带上安全响应头
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(SecurityHeaders::from_config(&state.config.security_headers)),
+            security_headers_middleware,
+        ))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header},
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::entities::ip_rules;
+    use crate::service::ip_rule_service::IpRuleSet;
+
+    async fn preflight(
+        config: &CorsConfig,
+        origin: &str,
+        method: &str,
+    ) -> axum::response::Response {
+        let router = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(cors_layer(config));
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/ping")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+            .body(Body::empty())
+            .unwrap();
+        router.oneshot(request).await.unwrap()
+    }
+
+    fn header_str(response: &axum::response::Response, name: HeaderName) -> Option<&str> {
+        response.headers().get(name).and_then(|v| v.to_str().ok())
+    }
+
+    #[tokio::test]
+    async fn test_default_cors_allows_any_origin() {
+        let response = preflight(&CorsConfig::default(), "https://any.example.com", "DELETE").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("*")
+        );
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_METHODS),
+            Some("*")
+        );
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_MAX_AGE),
+            Some("600")
+        );
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restricted_cors_preflight() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://admin.example.com".to_string()],
+            allowed_methods: vec!["GET".to_string(), "POST".to_string()],
+            allow_credentials: true,
+            max_age_secs: 0,
+            ..CorsConfig::default()
+        };
+
+        let response = preflight(&config, "https://admin.example.com", "POST").await;
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("https://admin.example.com")
+        );
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+            Some("true")
+        );
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_METHODS),
+            Some("GET,POST")
+        );
+        // 携带凭据时请求头回显预检中的值，而不是返回 `*`
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_HEADERS),
+            Some("authorization")
+        );
+        assert_eq!(header_str(&response, header::ACCESS_CONTROL_MAX_AGE), None);
+
+        // 未列出的来源拿不到 Allow-Origin，浏览器会拦截
+        let response = preflight(&config, "https://evil.example.com", "POST").await;
+        assert_eq!(
+            header_str(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cors_exposes_trace_headers() {
+        let router = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(cors_layer(&CorsConfig::default()));
+        let request = Request::builder()
+            .uri("/ping")
+            .header(header::ORIGIN, "https://any.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+
+        let exposed = header_str(&response, header::ACCESS_CONTROL_EXPOSE_HEADERS).unwrap();
+        assert!(exposed.contains("x-request-id"), "{}", exposed);
+        assert!(exposed.contains("retry-after"), "{}", exposed);
+    }
+
+    async fn get_status(router: &Router, uri: &str) -> StatusCode {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    /// 不连接数据库的状态，IP 规则直接写入缓存
+    fn test_state(config: Config, rules: &[ip_rules::Model]) -> AppState {
+        let state = AppState::new(config, DatabaseConnection::Disconnected).unwrap();
+        state.ip_rules.preload(IpRuleSet::from_models(rules));
+        // 本机 IP 的拒绝审计视为刚记录过，测试中不写库
+        state.ip_rules.should_audit(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        state
+    }
+
+    #[tokio::test]
+    async fn test_metrics_disabled_by_default() {
+        let router = get_router(test_state(Config::default(), &[]));
+
+        assert_eq!(get_status(&router, "/metrics").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_ip_filtered() {
+        let mut config = Config::default();
+        config.metrics.enabled = true;
+        let router = get_router(test_state(config.clone(), &[]));
+        assert_eq!(get_status(&router, "/metrics").await, StatusCode::OK);
+
+        // 直接调用 Router 时客户端 IP 按本机处理
+        let deny_local = ip_rules::Model {
+            id: uuid::Uuid::now_v7(),
+            admin_id: None,
+            rule_type: "deny".to_string(),
+            cidr: "127.0.0.1".to_string(),
+            description: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let router = get_router(test_state(config, &[deny_local]));
+        assert_eq!(get_status(&router, "/metrics").await, StatusCode::FORBIDDEN);
+        // 探针不受名单约束
+        assert_eq!(get_status(&router, "/healthz").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_unavailable_without_mailer() {
+        let router = get_router(test_state(Config::default(), &[]));
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("{}/auth/password-reset/request", API_PREFIX))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"email":"admin@example.com"}"#))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
@@ -1,7 +1,6 @@
-use anyhow::{Result, anyhow};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, ModelTrait, NotSet,
-    PaginatorTrait, QueryFilter, QueryOrder, RelationTrait, Set, TransactionTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
 };
 
 use crate::dto::{
@@ -10,17 +9,48 @@ use crate::dto::{
 };
 use crate::entities::prelude::*;
 use crate::entities::{admin_roles, admins};
+use crate::error::{GuardianError, Result, transaction_error};
+use crate::i18n::{normalize_locale, t};
 use crate::response::Response;
 use crate::router::AppState;
+use crate::service::auth_service::{clear_two_fa, update_password};
+use crate::service::password_policy_service::{check_new_password, record_password_history};
+use crate::service::webauthn_service::delete_webauthn_credentials;
 use crate::utils::hash_password;
 
+/// 邮箱统一去除首尾空白并转为小写，格式不合法时返回 `None`
+pub fn normalize_email(email: &str) -> Option<String> {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.split_once('@')?;
+
+    let valid = !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@')
+        && !email.chars().any(char::is_whitespace);
+
+    valid.then_some(email)
+}
+
+async fn email_taken(
+    state: &AppState,
+    email: &str,
+    exclude_id: Option<uuid::Uuid>,
+) -> Result<bool> {
+    let mut select = Admins::find().filter(admins::Column::Email.eq(email));
+    if let Some(id) = exclude_id {
+        select = select.filter(admins::Column::Id.ne(id));
+    }
+    Ok(select.one(&state.conn).await?.is_some())
+}
+
 pub async fn list_admin_service(
     state: AppState,
     query: AdminListQuery,
 ) -> Result<Response<AdminListResponse>> {
     let page = query.page.unwrap_or(1);
     let page_size = query.page_size.unwrap_or(20);
-    let offset = (page - 1) * page_size;
 
     let mut select = Admins::find();
 
@@ -39,17 +69,19 @@ pub async fn list_admin_service(
     let total = paginator
         .num_items_and_pages()
         .await
-        .map(|(items)| items.number_of_items)
+        .map(|items| items.number_of_items)
         .unwrap_or(0);
-    let admins_list = paginator.fetch_page(page - 1).await?;
+    let admins_list = paginator.fetch_page(page.saturating_sub(1)).await?;
 
     let list: Vec<AdminResponse> = admins_list
         .into_iter()
         .map(|admin| AdminResponse {
             id: admin.id,
             username: admin.username,
+            email: admin.email,
             is_super_admin: admin.is_super_admin.unwrap_or(false),
             status: admin.status.unwrap_or(1),
+            locale: admin.locale,
             last_login_at: admin.last_login_at.map(|dt| dt.into()),
             created_at: admin
                 .created_at
@@ -77,7 +109,7 @@ pub async fn get_admin_service(
     let admin = Admins::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
     let roles = admin.find_related(Roles).all(&state.conn).await?;
 
@@ -93,8 +125,10 @@ pub async fn get_admin_service(
     Ok(Response::ok_data(AdminDetailResponse {
         id: admin.id,
         username: admin.username,
+        email: admin.email,
         is_super_admin: admin.is_super_admin.unwrap_or(false),
         status: admin.status.unwrap_or(1),
+        locale: admin.locale,
         last_login_at: admin.last_login_at.map(|dt| dt.into()),
         login_attempts: admin.login_attempts.unwrap_or(0),
         locked_until: admin.locked_until.map(|dt| dt.into()),
@@ -110,6 +144,13 @@ pub async fn get_admin_service(
     }))
 }
 
+/// 管理员写入涉及的唯一约束：并发请求抢先占用了用户名或邮箱，或角色 ID 有重复
+const ADMIN_CONSTRAINTS: &[(&str, &str)] = &[
+    ("guardian_admins_username_key", "admin.username_taken"),
+    ("guardian_admins_email_key", "admin.email_taken"),
+    ("guardian_admin_roles_pkey", "admin.duplicate_role"),
+];
+
 pub async fn create_admin_service(
     state: AppState,
     payload: CreateAdminRequest,
@@ -120,10 +161,31 @@ pub async fn create_admin_service(
         .await?;
 
     if existing.is_some() {
-        return Ok(Response::failed("用户名已存在".to_string()));
+        return Err(GuardianError::Conflict(t!("admin.username_taken")));
+    }
+
+    let email = match payload.email.as_deref().map(normalize_email) {
+        Some(None) => {
+            return Err(GuardianError::Validation(t!("admin.invalid_email")));
+        }
+        Some(Some(email)) => Some(email),
+        None => None,
+    };
+
+    if let Some(email) = &email
+        && email_taken(&state, email, None).await?
+    {
+        return Err(GuardianError::Conflict(t!("admin.email_taken")));
     }
 
-    let password_hash = hash_password(&payload.password);
+    if let Some(msg) =
+        check_new_password(&state, None, &payload.username, &payload.password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
+
+    let password_hash = hash_password(&state.config.password_hash, &payload.password);
+    let policy = state.config.password_policy.clone();
     let payload_clone = payload.clone();
 
     state
@@ -133,6 +195,7 @@ pub async fn create_admin_service(
                 let admin = admins::ActiveModel {
                     id: Set(uuid::Uuid::new_v4()),
                     username: Set(payload_clone.username.clone()),
+                    email: Set(email),
                     password_hash: Set(password_hash.clone()),
                     is_super_admin: Set(payload_clone.is_super_admin),
                     status: Set(Some(1)),
@@ -140,12 +203,17 @@ pub async fn create_admin_service(
                     last_login_at: Set(None),
                     login_attempts: Set(Some(0)),
                     locked_until: Set(None),
+                    password_changed_at: Set(Some(chrono::Local::now().into())),
+                    must_change_password: Set(Some(true)),
+                    locale: Set(payload_clone.locale.as_deref().and_then(normalize_locale)),
                     created_at: Set(Some(chrono::Local::now().into())),
                     updated_at: Set(Some(chrono::Local::now().into())),
                 };
 
                 let admin = admin.insert(txn).await?;
 
+                record_password_history(txn, &policy, admin.id, &password_hash).await?;
+
                 if let Some(role_ids) = payload_clone.role_ids.clone() {
                     for role_id in role_ids {
                         let admin_role = admin_roles::ActiveModel {
@@ -161,21 +229,23 @@ pub async fn create_admin_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("创建管理员失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ADMIN_CONSTRAINTS))?;
 
     let admin = Admins::find()
         .filter(admins::Column::Username.eq(&payload.username))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员创建后查询失败"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
     Ok(Response::ok(
-        Some("创建成功".to_string()),
+        Some(t!("common.created")),
         AdminResponse {
             id: admin.id,
             username: admin.username,
+            email: admin.email,
             is_super_admin: admin.is_super_admin.unwrap_or(false),
             status: admin.status.unwrap_or(1),
+            locale: admin.locale,
             last_login_at: admin.last_login_at.map(|dt| dt.into()),
             created_at: admin
                 .created_at
@@ -197,9 +267,38 @@ pub async fn update_admin_service(
     let admin = Admins::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    if let Some(password) = &payload.password
+        && let Some(msg) =
+            check_new_password(&state, Some(&admin), &admin.username, password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
+
+    // 外层 None 表示不修改，内层 None 表示解绑邮箱
+    let email = match payload.email.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(email) => match normalize_email(email) {
+            Some(email) => Some(Some(email)),
+            None => {
+                return Err(GuardianError::Validation(t!("admin.invalid_email")));
+            }
+        },
+    };
 
-    let password_hash = payload.password.as_ref().map(|p| hash_password(p));
+    if let Some(Some(email)) = &email
+        && email_taken(&state, email, Some(id)).await?
+    {
+        return Err(GuardianError::Conflict(t!("admin.email_taken")));
+    }
+
+    let password_hash = payload
+        .password
+        .as_ref()
+        .map(|p| hash_password(&state.config.password_hash, p));
+    let policy = state.config.password_policy.clone();
 
     state
         .conn
@@ -208,13 +307,23 @@ pub async fn update_admin_service(
                 let mut admin_model: admins::ActiveModel = admin.into_active_model();
 
                 if let Some(hash) = password_hash {
+                    record_password_history(txn, &policy, id, &hash).await?;
                     admin_model.password_hash = Set(hash);
+                    admin_model.password_changed_at = Set(Some(chrono::Local::now().into()));
+                }
+
+                if let Some(email) = email {
+                    admin_model.email = Set(email);
                 }
 
                 if let Some(status) = payload.status {
                     admin_model.status = Set(Some(status));
                 }
 
+                if let Some(locale) = &payload.locale {
+                    admin_model.locale = Set(normalize_locale(locale));
+                }
+
                 if let Some(role_ids) = &payload.role_ids {
                     admin_roles::Entity::delete_many()
                         .filter(admin_roles::Column::AdminId.eq(id))
@@ -238,20 +347,22 @@ pub async fn update_admin_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("更新管理员失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ADMIN_CONSTRAINTS))?;
 
     let admin = Admins::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员更新后查询失败"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
     Ok(Response::ok(
-        Some("更新成功".to_string()),
+        Some(t!("common.updated")),
         AdminResponse {
             id: admin.id,
             username: admin.username,
+            email: admin.email,
             is_super_admin: admin.is_super_admin.unwrap_or(false),
             status: admin.status.unwrap_or(1),
+            locale: admin.locale,
             last_login_at: admin.last_login_at.map(|dt| dt.into()),
             created_at: admin
                 .created_at
@@ -265,8 +376,8 @@ pub async fn update_admin_service(
     ))
 }
 
-pub async fn delete_admin_service(state: AppState, id: uuid::Uuid) -> Result<Response<()>> {
-    Ok(Response::ok_msg(Some("暂不支持".to_string())))
+pub async fn delete_admin_service(_state: AppState, _id: uuid::Uuid) -> Result<Response<()>> {
+    Ok(Response::ok_msg(Some(t!("common.not_supported"))))
 }
 
 pub async fn assign_roles_service(
@@ -277,10 +388,10 @@ pub async fn assign_roles_service(
     let admin = Admins::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
     if admin.is_super_admin.unwrap_or(false) {
-        return Ok(Response::failed("超级管理员不可分配角色".to_string()));
+        return Err(GuardianError::BadRequest(t!("admin.super_admin_roles")));
     }
 
     state
@@ -305,7 +416,75 @@ pub async fn assign_roles_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("分配角色失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ADMIN_CONSTRAINTS))?;
+
+    Ok(Response::ok_msg(Some(t!("admin.roles_assigned"))))
+}
+
+/// 清除登录失败次数和锁定状态
+pub async fn unlock_admin_service(state: AppState, id: uuid::Uuid) -> Result<Response<()>> {
+    let admin = Admins::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    let mut admin_model: admins::ActiveModel = admin.into_active_model();
+    admin_model.login_attempts = Set(Some(0));
+    admin_model.locked_until = Set(None);
+    admin_model.update(&state.conn).await?;
+
+    Ok(Response::ok_msg(Some(t!("admin.unlocked"))))
+}
+
+pub async fn find_admin_by_username(state: &AppState, username: &str) -> Result<admins::Model> {
+    Admins::find()
+        .filter(admins::Column::Username.eq(username))
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))
+}
+
+/// 运维侧重置密码：无需原密码或两步验证，同时解除锁定，旧令牌随密码修改时间一并失效
+pub async fn reset_admin_password_service(
+    state: AppState,
+    id: uuid::Uuid,
+    new_password: &str,
+    must_change_password: bool,
+) -> Result<Response<()>> {
+    let admin = Admins::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    if let Some(msg) =
+        check_new_password(&state, Some(&admin), &admin.username, new_password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
+
+    let admin = update_password(&state, admin, new_password).await?;
+
+    let mut admin_model: admins::ActiveModel = admin.into_active_model();
+    admin_model.must_change_password = Set(Some(must_change_password));
+    admin_model.login_attempts = Set(Some(0));
+    admin_model.locked_until = Set(None);
+    admin_model.update(&state.conn).await?;
+
+    Ok(Response::ok_msg(Some(t!("auth.password_reset"))))
+}
+
+/// 管理员丢失验证器时由运维关闭其两步验证，同时删除 WebAuthn 凭证
+pub async fn disable_admin_2fa_service(state: AppState, id: uuid::Uuid) -> Result<Response<()>> {
+    let admin = Admins::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    let removed = delete_webauthn_credentials(&state.conn, admin.id).await?;
+    // 只注册了 WebAuthn 凭证时不再要求存在 TOTP 密钥
+    if admin.two_fa_secret.is_some() || removed == 0 {
+        clear_two_fa(&state, admin).await?;
+    }
 
-    Ok(Response::ok_msg(Some("角色分配成功".to_string())))
+    Ok(Response::ok_msg(Some(t!("admin.two_fa_disabled"))))
 }
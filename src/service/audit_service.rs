@@ -0,0 +1,78 @@
+use log::warn;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{ConnectionTrait, DbErr, Value};
+
+use crate::entities::audit_logs;
+use crate::error::Result;
+use crate::trace::current_trace_id;
+
+/// 一条审计日志，`action` 使用 snake_case 动作名，例如 `password_reset_requested`；
+/// 在请求中写入时自动带上当前请求的追踪 ID
+#[derive(Debug, Clone, Default)]
+pub struct AuditEntry {
+    pub admin_id: Option<uuid::Uuid>,
+    pub username: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub method: String,
+    pub params: Option<serde_json::Value>,
+    pub result: Option<serde_json::Value>,
+    pub status_code: i32,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+pub async fn record_audit<C: ConnectionTrait>(conn: &C, entry: AuditEntry) -> Result<(), DbErr> {
+    // ip_address 是 inet 类型，文本参数需要显式转换
+    let ip_address = Expr::cust_with_values(
+        "CAST($1 AS inet)",
+        [Value::String(entry.ip_address.map(Box::new))],
+    );
+
+    let mut insert = Query::insert();
+    insert
+        .into_table(audit_logs::Entity)
+        .columns([
+            audit_logs::Column::Id,
+            audit_logs::Column::TraceId,
+            audit_logs::Column::AdminId,
+            audit_logs::Column::Username,
+            audit_logs::Column::Action,
+            audit_logs::Column::Resource,
+            audit_logs::Column::Method,
+            audit_logs::Column::Params,
+            audit_logs::Column::Result,
+            audit_logs::Column::StatusCode,
+            audit_logs::Column::IpAddress,
+            audit_logs::Column::UserAgent,
+            audit_logs::Column::DurationMs,
+        ])
+        .values([
+            uuid::Uuid::now_v7().into(),
+            current_trace_id().into(),
+            entry.admin_id.into(),
+            entry.username.into(),
+            entry.action.into(),
+            entry.resource.into(),
+            entry.method.into(),
+            entry.params.into(),
+            entry.result.into(),
+            entry.status_code.into(),
+            ip_address,
+            entry.user_agent.into(),
+            0.into(),
+        ])
+        .map_err(|e| DbErr::Custom(e.to_string()))?;
+
+    conn.execute(conn.get_database_backend().build(&insert))
+        .await?;
+    Ok(())
+}
+
+/// 审计写入失败不应影响业务流程，只记录告警
+pub async fn record_audit_quietly<C: ConnectionTrait>(conn: &C, entry: AuditEntry) {
+    let action = entry.action.clone();
+    if let Err(e) = record_audit(conn, entry).await {
+        warn!("写入审计日志失败 ({}): {}", action, e);
+    }
+}
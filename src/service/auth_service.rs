@@ -1,126 +1,350 @@
-use anyhow::{Ok, Result, anyhow};
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, NotSet, QueryFilter, Set,
-};
-use totp_rs::{Secret, TOTP};
+use std::net::IpAddr;
+
+use anyhow::anyhow;
+use log::warn;
+use sea_orm::TransactionTrait;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
+use totp_rs::Secret;
 
 use crate::dto::{
-    ChangeOwnPasswordRequest, LoginRequest, LoginResponse, RefreshTokenResponse,
-    ResetPasswordRequest, TwoFaDisableResponse, TwoFaSetupResponse, TwoFaVerifyResponse,
+    ChangePasswordRequest, LoginRequest, LoginResponse, LoginResult, LoginTwoFaRequest,
+    MfaChallengeResponse, RefreshTokenResponse, ResetPasswordRequest, TwoFaDisableResponse,
+    TwoFaMethod, TwoFaSetupResponse, TwoFaVerifyResponse, UpdatePreferencesRequest,
 };
-use crate::entities::{admins, token_blacklist};
+use crate::entities::admins;
+use crate::error::{GuardianError, Result};
+use crate::i18n::{normalize_locale, set_current_locale, t};
+use crate::metrics::{LoginOutcome, record_login, record_two_fa};
 use crate::middleware::auth::AuthContext;
 use crate::response::{Response, ResponseCode};
 use crate::router::AppState;
+use crate::service::ip_rule_service::record_ip_denial;
+use crate::service::mfa_service::{
+    available_mfa_methods, build_totp, consume_backup_code, create_mfa_challenge,
+    delete_backup_codes, detect_two_fa_method, discard_mfa_challenge, generate_backup_codes,
+    mfa_attempts_exhausted, replace_backup_codes, start_mfa_attempt, verify_totp_code,
+};
+use crate::service::password_policy_service::{check_new_password, record_password_history};
+use crate::service::webauthn_service::{
+    assertion_options, credential_ids, verify_webauthn_assertion,
+};
 use crate::utils::{
-    create_token_pair, get_jti, hash_password, refresh_access_token, verify_password,
+    create_token_pair, get_jti, hash_password, password_needs_rehash, refresh_access_token,
+    verify_password, verify_token,
 };
 
+const LOGIN_RESOURCE: &str = "/auth/login";
+
 pub async fn login_service(
     state: AppState,
     payload: LoginRequest,
-) -> Result<Response<LoginResponse>> {
-    let now = chrono::Local::now();
-    let admin = admins::Entity::find()
+    client_ip: IpAddr,
+) -> Result<Response<LoginResult>> {
+    let throttle = &state.login_throttle;
+    let config = throttle.config();
+
+    let ip_state = throttle.ip_state(client_ip).await?;
+    if let Some(retry_after) = ip_state.retry_after {
+        record_login(LoginOutcome::Locked);
+        return Err(GuardianError::RateLimited(t!(
+            "auth.login_ip_throttled",
+            secs = retry_after
+        )));
+    }
+
+    let Some(admin) = admins::Entity::find()
         .filter(admins::Column::Username.eq(&payload.username))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("用户名或密码错误"))?;
+    else {
+        throttle.record_ip_failure(client_ip).await?;
+        record_login(LoginOutcome::Failure);
+        return Err(GuardianError::Unauthorized(t!("auth.invalid_credentials")));
+    };
 
-    if let Some(status) = admin.status {
-        if status != 1 {
-            return Ok(Response::failed("账户已被禁用".to_string()));
-        }
+    if admin.status.is_some_and(|status| status != 1) {
+        record_login(LoginOutcome::Denied);
+        return Err(GuardianError::Forbidden(t!("auth.account_disabled")));
     }
 
-    if let Some(locked_until) = admin.locked_until {
-        if locked_until > chrono::Local::now() {
-            return Ok(Response::failed("账户已被锁定，请稍后重试".to_string()));
+    let now = chrono::Local::now();
+    let login_attempts = admin.login_attempts.unwrap_or(0).max(0) as u32;
+    let locked_until = admin
+        .locked_until
+        .filter(|locked_until| *locked_until > now);
+
+    // 启用验证码时，通过人机验证即可绕过用户名维度的锁定，避免攻击者借此锁死他人账户
+    let captcha_required = state.captcha.is_some()
+        && (locked_until.is_some()
+            || config.captcha_required(login_attempts)
+            || config.captcha_required(ip_state.failures));
+
+    if captcha_required {
+        if !verify_captcha(&state, payload.captcha_token.as_deref(), client_ip).await {
+            record_login(LoginOutcome::CaptchaRequired);
+            return Err(GuardianError::Code(ResponseCode::CaptchaRequired, None));
         }
+    } else if let Some(locked_until) = locked_until {
+        record_login(LoginOutcome::Locked);
+        return Err(GuardianError::RateLimited(t!(
+            "auth.login_locked",
+            secs = (locked_until.timestamp() - now.timestamp()).max(1)
+        )));
     }
 
-    if !verify_password(&payload.password, &admin.password_hash) {
-        let login_attempts = admin.login_attempts.unwrap_or(0) + 1;
-        let mut admin_model: admins::ActiveModel = admin.into_active_model();
+    if !verify_password(
+        &state.config.password_hash,
+        &payload.password,
+        &admin.password_hash,
+    ) {
+        throttle.record_ip_failure(client_ip).await?;
 
-        if login_attempts >= 5 {
-            let locked_until = chrono::Local::now() + chrono::Duration::minutes(15);
-            admin_model.locked_until = Set(Some(locked_until.into()));
-            admin_model.login_attempts = Set(Some(login_attempts));
-            admin_model.update(&state.conn).await?;
-            return Ok(Response::failed(
-                "密码错误次数过多，账户已被锁定15分钟".to_string(),
-            ));
-        }
+        let login_attempts = login_attempts.saturating_add(1);
+        let delay = config.backoff_secs(login_attempts, config.username_free_attempts);
 
-        admin_model.login_attempts = Set(Some(login_attempts));
+        let mut admin_model: admins::ActiveModel = admin.into_active_model();
+        admin_model.login_attempts = Set(Some(login_attempts.min(i32::MAX as u32) as i32));
+        admin_model.locked_until =
+            Set(delay.map(|secs| (now + chrono::Duration::seconds(secs as i64)).into()));
         admin_model.update(&state.conn).await?;
-        return Ok(Response::failed("用户名或密码错误".to_string()));
+
+        return Err(match delay {
+            Some(secs) => {
+                record_login(LoginOutcome::Locked);
+                GuardianError::RateLimited(t!("auth.password_locked", secs = secs))
+            }
+            None => {
+                record_login(LoginOutcome::Failure);
+                GuardianError::Unauthorized(t!("auth.invalid_credentials"))
+            }
+        });
     }
 
-    if admin.two_fa_secret.is_some() {
-        if payload.two_fa_code.is_none() {
-            return Ok(Response::from_code(ResponseCode::InvalidTwoFaCode, None));
-        }
-        let two_fa_code = payload.two_fa_code.unwrap();
-
-        let two_fa_secret = admin.two_fa_secret.as_ref().unwrap();
-        let secret = Secret::Encoded(two_fa_secret.to_string());
-
-        let totp = TOTP::new(
-            totp_rs::Algorithm::SHA1,
-            6,
-            1,
-            30,
-            secret.to_bytes()?,
-            Some("Guardian".to_string()),
-            admin.username.clone(),
+    // 全局规则已由中间件检查，这里补充检查该管理员的规则
+    if let Some(denial) = state
+        .ip_rules
+        .rules(&state.conn)
+        .await?
+        .check(client_ip, Some(admin.id))
+    {
+        record_ip_denial(
+            &state,
+            &denial,
+            client_ip,
+            Some((admin.id, admin.username.clone())),
+            "POST",
+            LOGIN_RESOURCE,
+            None,
         )
-        .map_err(|e| anyhow!("生成TOTP失败: {}", e))?;
+        .await;
+        record_login(LoginOutcome::Denied);
+        return Err(GuardianError::Code(ResponseCode::IpNotAllowed, None));
+    }
 
-        let is_valid = totp
-            .check_current(&two_fa_code)
-            .map_err(|e| anyhow!("验证2FA失败: {}", e))?;
+    let admin = rehash_password_if_needed(&state, admin, &payload.password).await?;
 
-        if !is_valid {
-            return Ok(ResponseCode::InvalidTwoFaCode.to_response(None));
+    // 只要注册过 WebAuthn 凭证就要求两步验证，即使当前配置未启用 WebAuthn
+    let webauthn_credentials = credential_ids(&state.conn, admin.id).await?;
+    if admin.two_fa_secret.is_some() || !webauthn_credentials.is_empty() {
+        let mut mfa_methods = available_mfa_methods(&state.conn, &admin).await?;
+        let challenge = create_mfa_challenge(&state.conn, &state.config.mfa, admin.id).await?;
+        let webauthn = assertion_options(&state, &challenge.token, webauthn_credentials);
+        if webauthn.is_some() {
+            mfa_methods.push(TwoFaMethod::WebAuthn);
         }
+        record_login(LoginOutcome::MfaRequired);
+
+        return Ok(ResponseCode::TwoFaRequired.to_response_with_data(
+            None,
+            LoginResult::MfaRequired(MfaChallengeResponse {
+                mfa_token: challenge.token,
+                mfa_methods,
+                expires_in: challenge.expires_in,
+                webauthn,
+            }),
+        ));
     }
 
+    let login_response = complete_login(&state, admin).await?;
+    record_login(LoginOutcome::Success);
+
+    Ok(Response::ok_data(LoginResult::Tokens(login_response)))
+}
+
+async fn verify_captcha(state: &AppState, token: Option<&str>, client_ip: IpAddr) -> bool {
+    let (Some(verifier), Some(token)) = (&state.captcha, token) else {
+        return false;
+    };
+
+    verifier.verify(token, client_ip).await.unwrap_or_else(|e| {
+        warn!("校验验证码失败: {}", e);
+        false
+    })
+}
+
+pub async fn login_2fa_service(
+    state: AppState,
+    payload: LoginTwoFaRequest,
+) -> Result<Response<LoginResult>> {
+    let Some(challenge) =
+        start_mfa_attempt(&state.conn, &state.config.mfa, &payload.mfa_token).await?
+    else {
+        return Err(GuardianError::Code(ResponseCode::MfaChallengeInvalid, None));
+    };
+
+    let admin = admins::Entity::find_by_id(challenge.admin_id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    if admin.status.is_some_and(|status| status != 1) {
+        discard_mfa_challenge(&state.conn, challenge.id).await?;
+        return Err(GuardianError::Forbidden(t!("auth.account_disabled")));
+    }
+
+    let method = payload.method.unwrap_or_else(|| {
+        if payload.webauthn.is_some() {
+            TwoFaMethod::WebAuthn
+        } else {
+            detect_two_fa_method(&payload.code)
+        }
+    });
+
+    let verified = match method {
+        TwoFaMethod::Totp => match &admin.two_fa_secret {
+            Some(secret) => verify_totp_code(secret, admin.username.clone(), &payload.code)?,
+            None => false,
+        },
+        TwoFaMethod::BackupCode => {
+            consume_backup_code(
+                &state.conn,
+                &state.config.password_hash,
+                admin.id,
+                &payload.code,
+            )
+            .await?
+        }
+        TwoFaMethod::WebAuthn => match &payload.webauthn {
+            Some(assertion) => {
+                verify_webauthn_assertion(&state, &admin, &payload.mfa_token, assertion).await?
+            }
+            None => false,
+        },
+    };
+
+    record_two_fa(method, verified);
+    if !verified {
+        if mfa_attempts_exhausted(&state.config.mfa, &challenge) {
+            discard_mfa_challenge(&state.conn, challenge.id).await?;
+        }
+        return Err(GuardianError::Code(ResponseCode::InvalidTwoFaCode, None));
+    }
+
+    discard_mfa_challenge(&state.conn, challenge.id).await?;
+    let login_response = complete_login(&state, admin).await?;
+    record_login(LoginOutcome::Success);
+
+    Ok(Response::ok_data(LoginResult::Tokens(login_response)))
+}
+
+async fn complete_login(state: &AppState, admin: admins::Model) -> Result<LoginResponse> {
+    let must_change_password = admin.must_change_password.unwrap_or(false)
+        || state.config.password_policy.is_expired(&admin);
+
     let token_pair = create_token_pair(
+        &state.config.jwt,
         admin.id,
         admin.username.clone(),
         admin.is_super_admin.unwrap_or(false),
+        must_change_password,
     )?;
 
     let mut admin_model: admins::ActiveModel = admin.into_active_model();
-    admin_model.last_login_at = Set(Some(now.into()));
+    admin_model.last_login_at = Set(Some(chrono::Local::now().into()));
     admin_model.login_attempts = Set(Some(0));
     admin_model.locked_until = Set(None);
     admin_model.update(&state.conn).await?;
 
-    Ok(Response::ok_data(LoginResponse {
+    Ok(LoginResponse {
         access_token: token_pair.access_token,
         refresh_token: token_pair.refresh_token,
         expires_in: token_pair.expires_in,
-    }))
+        must_change_password,
+    })
 }
 
-pub async fn logout_service(state: AppState, refresh_token: String) -> Result<Response<()>> {
-    let jti = get_jti(&refresh_token)?;
+/// 存储的哈希参数过时（调高了 Argon2 成本或启用了 pepper）时，用本次登录的明文重新哈希
+async fn rehash_password_if_needed(
+    state: &AppState,
+    admin: admins::Model,
+    password: &str,
+) -> Result<admins::Model> {
+    let hash_config = &state.config.password_hash;
+    if !password_needs_rehash(hash_config, &admin.password_hash) {
+        return Ok(admin);
+    }
 
-    let claims = crate::utils::verify_token(&refresh_token)?;
+    let mut admin_model: admins::ActiveModel = admin.into_active_model();
+    admin_model.password_hash = Set(hash_password(hash_config, password));
+    Ok(admin_model.update(&state.conn).await?)
+}
 
-    let expires_at = chrono::Local::now() + chrono::Duration::days(7);
+/// 密码修改时间之前签发的令牌一律视为失效，修改密码即可注销所有已登录会话
+pub fn is_token_revoked(admin: &admins::Model, iat: usize) -> bool {
+    admin
+        .password_changed_at
+        .is_some_and(|changed_at| (iat as i64) < changed_at.timestamp())
+}
 
-    let blacklist = token_blacklist::ActiveModel {
-        id: NotSet,
-        token_id: Set(jti),
-        expires_at: Set(expires_at.into()),
-        created_at: NotSet,
-    };
+/// 新密码通过策略校验后写入，同时记录密码历史并清除强制改密标记
+pub(crate) async fn update_password(
+    state: &AppState,
+    admin: admins::Model,
+    new_password: &str,
+) -> Result<admins::Model> {
+    let password_hash = hash_password(&state.config.password_hash, new_password);
+    let now = chrono::Local::now();
 
-    blacklist.insert(&state.conn).await?;
+    let txn = state.conn.begin().await?;
+    record_password_history(
+        &txn,
+        &state.config.password_policy,
+        admin.id,
+        &password_hash,
+    )
+    .await?;
+    let mut admin_model: admins::ActiveModel = admin.into_active_model();
+    admin_model.password_hash = Set(password_hash);
+    admin_model.password_changed_at = Set(Some(now.into()));
+    admin_model.must_change_password = Set(Some(false));
+    admin_model.updated_at = Set(Some(now.into()));
+    let admin = admin_model.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(admin)
+}
+
+pub async fn logout_service(
+    state: AppState,
+    auth_context: AuthContext,
+    refresh_token: String,
+) -> Result<Response<()>> {
+    let jti = get_jti(&state.config.jwt, &refresh_token)
+        .map_err(|_| GuardianError::Unauthorized(t!("auth.refresh_token_invalid")))?;
+
+    let expires_at = chrono::Local::now()
+        + chrono::Duration::seconds(state.config.jwt.refresh_token_ttl_secs as i64);
+    state.revocations.revoke(&jti, expires_at).await?;
+
+    // 访问令牌同时吊销，登出后立即失效而不是等到自然过期
+    let access_expires_at =
+        chrono::DateTime::from_timestamp(auth_context.token_expires_at as i64, 0)
+            .map(|expires_at| expires_at.with_timezone(&chrono::Local))
+            .unwrap_or(expires_at);
+    state
+        .revocations
+        .revoke(&auth_context.token_id, access_expires_at)
+        .await?;
 
     Ok(Response::quick_ok())
 }
@@ -129,19 +353,27 @@ pub async fn refresh_token_service(
     state: AppState,
     refresh_token: String,
 ) -> Result<Response<RefreshTokenResponse>> {
-    let jti = get_jti(&refresh_token)?;
+    let claims = verify_token(&state.config.jwt, &refresh_token)
+        .map_err(|_| GuardianError::Unauthorized(t!("auth.refresh_token_invalid")))?;
+
+    if state.revocations.is_revoked(&claims.jti).await? {
+        return Err(GuardianError::Unauthorized(t!(
+            "auth.refresh_token_revoked"
+        )));
+    }
 
-    let is_blacklisted = token_blacklist::Entity::find()
-        .filter(token_blacklist::Column::TokenId.eq(&jti))
-        .filter(token_blacklist::Column::ExpiresAt.gt(chrono::Local::now()))
+    let admin_id = uuid::Uuid::parse_str(&claims.sub)
+        .map_err(|_| GuardianError::Unauthorized(t!("auth.refresh_token_invalid")))?;
+    let admin = admins::Entity::find_by_id(admin_id)
         .one(&state.conn)
         .await?;
-
-    if is_blacklisted.is_some() {
-        return Ok(Response::failed("Refresh token已失效".to_string()));
+    if admin.is_none_or(|admin| is_token_revoked(&admin, claims.iat)) {
+        return Err(GuardianError::Unauthorized(t!(
+            "auth.refresh_token_revoked"
+        )));
     }
 
-    let access_token = refresh_access_token(&refresh_token)?;
+    let access_token = refresh_access_token(&state.config.jwt, &refresh_token)?;
 
     Ok(Response::ok_data(RefreshTokenResponse {
         access_token: access_token.access_token,
@@ -157,40 +389,31 @@ pub async fn setup_2fa_service(
         .filter(admins::Column::Id.eq(auth_context.admin_id))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
     if admin.two_fa_secret.is_some() {
-        return Ok(ResponseCode::TwoFaAlreadyEnabled.to_response(None));
+        return Err(GuardianError::Code(ResponseCode::TwoFaAlreadyEnabled, None));
     }
 
     let secret = Secret::generate_secret();
 
-    let totp = TOTP::new(
-        totp_rs::Algorithm::SHA1,
-        6,
-        1,
-        30,
-        secret.to_bytes()?,
-        Some("Guardian".to_string()),
-        auth_context.username.clone(),
-    )
-    .map_err(|e| anyhow!("生成TOTP失败: {}", e))?;
+    let secret_bytes = secret
+        .to_bytes()
+        .map_err(|e| anyhow!("生成2FA密钥失败: {}", e))?;
+    let totp = build_totp(secret_bytes, auth_context.username.clone())?;
 
     let qr_code_url = totp
         .get_qr_base64()
         .map_err(|e| anyhow!("生成QR码失败: {}", e))?;
 
-    let backup_codes: Vec<String> = (0..10)
-        .map(|_| {
-            use rand::Rng;
-            let code: u32 = rand::thread_rng().gen_range(100000..999999);
-            format!("{:08}", code)
-        })
-        .collect();
+    let backup_codes = generate_backup_codes();
 
+    let txn = state.conn.begin().await?;
+    replace_backup_codes(&txn, &state.config.password_hash, admin.id, &backup_codes).await?;
     let mut admin_model: admins::ActiveModel = admin.into_active_model();
     admin_model.two_fa_secret = Set(Some(secret.to_encoded().to_string()));
-    admin_model.update(&state.conn).await?;
+    admin_model.update(&txn).await?;
+    txn.commit().await?;
 
     Ok(Response::ok_data(TwoFaSetupResponse {
         secret: secret.to_encoded().to_string(),
@@ -208,31 +431,19 @@ pub async fn verify_2fa_service(
         .filter(admins::Column::Id.eq(auth_context.admin_id))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
-    let two_fa_secret = admin.two_fa_secret.ok_or_else(|| anyhow!("未启用2FA"))?;
-
-    let secret = Secret::Encoded(two_fa_secret.to_string());
-
-    let totp = TOTP::new(
-        totp_rs::Algorithm::SHA1,
-        6,
-        1,
-        30,
-        secret.to_bytes()?,
-        Some("Guardian".to_string()),
-        auth_context.username,
-    )
-    .map_err(|e| anyhow!("生成TOTP失败: {}", e))?;
+    let two_fa_secret = admin
+        .two_fa_secret
+        .ok_or(GuardianError::Code(ResponseCode::TwoFaNotEnabled, None))?;
 
-    let is_valid = totp
-        .check_current(&code)
-        .map_err(|e| anyhow!("验证2FA失败: {}", e))?;
+    let is_valid = verify_totp_code(&two_fa_secret, auth_context.username, &code)?;
+    record_two_fa(TwoFaMethod::Totp, is_valid);
 
     if is_valid {
         Ok(Response::ok_data(TwoFaVerifyResponse { verified: true }))
     } else {
-        Ok(ResponseCode::InvalidTwoFaCode.to_response(None))
+        Err(GuardianError::Code(ResponseCode::InvalidTwoFaCode, None))
     }
 }
 
@@ -244,17 +455,27 @@ pub async fn disable_2fa_service(
         .filter(admins::Column::Id.eq(auth_context.admin_id))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    clear_two_fa(&state, admin).await?;
+
+    Ok(Response::ok_data(TwoFaDisableResponse { disabled: true }))
+}
 
+/// 清除 TOTP 密钥及备用码，未开启两步验证时报错
+pub(crate) async fn clear_two_fa(state: &AppState, admin: admins::Model) -> Result<()> {
     if admin.two_fa_secret.is_none() {
-        return Ok(ResponseCode::TwoFaNotEnabled.to_response(None));
+        return Err(GuardianError::Code(ResponseCode::TwoFaNotEnabled, None));
     }
 
+    let txn = state.conn.begin().await?;
+    delete_backup_codes(&txn, admin.id).await?;
     let mut admin_model: admins::ActiveModel = admin.into_active_model();
     admin_model.two_fa_secret = Set(None);
-    admin_model.update(&state.conn).await?;
+    admin_model.update(&txn).await?;
+    txn.commit().await?;
 
-    Ok(Response::ok_data(TwoFaDisableResponse { disabled: true }))
+    Ok(())
 }
 
 pub async fn reset_password_service(
@@ -265,61 +486,100 @@ pub async fn reset_password_service(
         .filter(admins::Column::Username.eq(&payload.username))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("用户不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("auth.user_not_found")))?;
 
-    let two_fa_secret = admin
-        .two_fa_secret
-        .as_ref()
-        .ok_or_else(|| anyhow!("未启用2FA，无法通过此方式重置密码"))?;
-
-    let secret = Secret::Encoded(two_fa_secret.to_string());
-
-    let totp = TOTP::new(
-        totp_rs::Algorithm::SHA1,
-        6,
-        1,
-        30,
-        secret.to_bytes()?,
-        Some("Guardian".to_string()),
-        admin.username.clone(),
-    )
-    .map_err(|e| anyhow!("生成TOTP失败: {}", e))?;
+    let two_fa_secret = admin.two_fa_secret.as_ref().ok_or_else(|| {
+        GuardianError::Code(
+            ResponseCode::TwoFaNotEnabled,
+            Some(t!("auth.two_fa_required_for_reset")),
+        )
+    })?;
 
-    let is_valid = totp
-        .check_current(&payload.two_fa_code)
-        .map_err(|e| anyhow!("验证2FA失败: {}", e))?;
+    let is_valid = verify_totp_code(two_fa_secret, admin.username.clone(), &payload.two_fa_code)?;
+    record_two_fa(TwoFaMethod::Totp, is_valid);
 
     if !is_valid {
-        return Ok(ResponseCode::InvalidTwoFaCode.to_response(None));
+        return Err(GuardianError::Code(ResponseCode::InvalidTwoFaCode, None));
     }
 
-    let password_hash = hash_password(&payload.new_password);
+    if let Some(msg) =
+        check_new_password(&state, Some(&admin), &admin.username, &payload.new_password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
 
-    let mut admin_model: admins::ActiveModel = admin.into_active_model();
-    admin_model.password_hash = Set(password_hash);
-    admin_model.updated_at = Set(Some(chrono::Local::now().into()));
-    admin_model.update(&state.conn).await?;
+    update_password(&state, admin, &payload.new_password).await?;
 
-    Ok(Response::ok_msg(Some("密码重置成功".to_string())))
+    Ok(Response::ok_msg(Some(t!("auth.password_reset"))))
 }
 
+/// 修改密码后其他会话全部失效，返回新的令牌供当前客户端继续使用
 pub async fn change_own_password_service(
     state: AppState,
     auth_context: AuthContext,
-    payload: ChangeOwnPasswordRequest,
-) -> Result<Response<()>> {
+    payload: ChangePasswordRequest,
+) -> Result<Response<LoginResponse>> {
     let admin = admins::Entity::find()
         .filter(admins::Column::Id.eq(auth_context.admin_id))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("管理员不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    if !verify_password(
+        &state.config.password_hash,
+        &payload.old_password,
+        &admin.password_hash,
+    ) {
+        return Err(GuardianError::Unauthorized(t!("auth.wrong_old_password")));
+    }
+
+    if let Some(msg) =
+        check_new_password(&state, Some(&admin), &admin.username, &payload.new_password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
+
+    let admin = update_password(&state, admin, &payload.new_password).await?;
+
+    let token_pair = create_token_pair(
+        &state.config.jwt,
+        admin.id,
+        admin.username,
+        admin.is_super_admin.unwrap_or(false),
+        false,
+    )?;
 
-    let password_hash = hash_password(&payload.new_password);
+    Ok(Response::ok(
+        Some(t!("auth.password_changed")),
+        LoginResponse {
+            access_token: token_pair.access_token,
+            refresh_token: token_pair.refresh_token,
+            expires_in: token_pair.expires_in,
+            must_change_password: false,
+        },
+    ))
+}
+
+/// 保存当前管理员的偏好语言，设置后本次响应即按新语言返回
+pub async fn update_preferences_service(
+    state: AppState,
+    auth_context: AuthContext,
+    payload: UpdatePreferencesRequest,
+) -> Result<Response<()>> {
+    let admin = admins::Entity::find_by_id(auth_context.admin_id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
 
+    let locale = payload.locale.as_deref().and_then(normalize_locale);
     let mut admin_model: admins::ActiveModel = admin.into_active_model();
-    admin_model.password_hash = Set(password_hash);
+    admin_model.locale = Set(locale.clone());
     admin_model.updated_at = Set(Some(chrono::Local::now().into()));
     admin_model.update(&state.conn).await?;
 
-    Ok(Response::ok_msg(Some("密码修改成功".to_string())))
+    if let Some(locale) = locale.and_then(|tag| tag.parse().ok()) {
+        set_current_locale(locale);
+    }
+
+    Ok(Response::ok_msg(Some(t!("auth.preferences_updated"))))
 }
@@ -0,0 +1,299 @@
+//! Guardian 自身接口的内置权限
+//!
+//! 权限目录由 OpenAPI 文档生成：每个需要权限检查的接口对应一条 api 权限，按标签挂在对应的
+//! 菜单下。启动时把目录同步到 `guardian_permissions`，接口增删或路径变化后无需手工维护。
+
+use std::collections::{HashMap, HashSet};
+
+use log::{info, warn};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::dto::PermissionDefinition;
+use crate::entities::{permissions, role_permissions};
+use crate::error::Result;
+use crate::openapi::ApiDoc;
+use crate::router::API_PREFIX;
+
+/// 所有内置权限的根菜单，代码以它为前缀的系统权限都由目录维护
+pub const ROOT_CODE: &str = "guardian";
+
+/// OpenAPI 标签与菜单的对应关系；不在表中的标签（认证）是公开接口或当前账户的自助操作，
+/// 只要求登录，不经过权限检查
+const MENUS: &[(&str, &str, &str)] = &[
+    ("管理员", "guardian:admin", "管理员管理"),
+    ("角色", "guardian:role", "角色管理"),
+    ("权限", "guardian:permission", "权限管理"),
+    ("IP 规则", "guardian:ip_rule", "IP 黑白名单"),
+    ("系统信息", "guardian:system_info", "系统信息"),
+];
+
+/// 生成内置权限目录，父权限排在子权限之前
+pub fn builtin_catalog() -> Vec<PermissionDefinition> {
+    let mut catalog = vec![PermissionDefinition {
+        code: ROOT_CODE.to_string(),
+        name: "Guardian 权限中心".to_string(),
+        description: Some("Guardian 自身的管理接口".to_string()),
+        resource_type: "menu".to_string(),
+        http_method: None,
+        resource_path: None,
+        parent: None,
+        sort_order: Some(0),
+    }];
+    let mut apis: HashMap<&str, Vec<PermissionDefinition>> = HashMap::new();
+
+    let spec = ApiDoc::openapi();
+    for (path, item) in &spec.paths.paths {
+        let operations = [
+            ("GET", &item.get),
+            ("POST", &item.post),
+            ("PUT", &item.put),
+            ("PATCH", &item.patch),
+            ("DELETE", &item.delete),
+        ];
+        for (method, operation) in operations {
+            let Some(operation) = operation else {
+                continue;
+            };
+            let Some(&(_, menu, _)) = MENUS.iter().find(|(tag, ..)| {
+                operation
+                    .tags
+                    .as_ref()
+                    .is_some_and(|tags| tags.iter().any(|t| t == tag))
+            }) else {
+                continue;
+            };
+            let Some(operation_id) = &operation.operation_id else {
+                continue;
+            };
+
+            let entries = apis.entry(menu).or_default();
+            entries.push(PermissionDefinition {
+                code: format!("{}:{}", menu, operation_id),
+                name: operation
+                    .summary
+                    .clone()
+                    .unwrap_or_else(|| operation_id.clone()),
+                description: None,
+                resource_type: "api".to_string(),
+                http_method: Some(method.to_string()),
+                resource_path: Some(format!("{}{}", API_PREFIX, path)),
+                parent: Some(menu.to_string()),
+                sort_order: Some(entries.len() as i32 + 1),
+            });
+        }
+    }
+
+    for (index, (_, code, name)) in MENUS.iter().enumerate() {
+        catalog.push(PermissionDefinition {
+            code: code.to_string(),
+            name: name.to_string(),
+            description: None,
+            resource_type: "menu".to_string(),
+            http_method: None,
+            resource_path: None,
+            parent: Some(ROOT_CODE.to_string()),
+            sort_order: Some(index as i32 + 1),
+        });
+        catalog.extend(apis.remove(code).unwrap_or_default());
+    }
+
+    catalog
+}
+
+fn is_builtin_code(code: &str) -> bool {
+    code == ROOT_CODE || code.starts_with(&format!("{}:", ROOT_CODE))
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+/// 把内置权限目录同步到数据库：新增缺少的、更新有变化的、删除目录中已不存在的内置权限。
+/// 同名的非内置权限会被接管为内置权限，已有的角色授权保持不变
+pub async fn sync_builtin_permissions<C: ConnectionTrait + TransactionTrait>(
+    conn: &C,
+) -> Result<SyncSummary> {
+    let catalog = builtin_catalog();
+    let txn = conn.begin().await?;
+
+    let existing: HashMap<String, permissions::Model> = permissions::Entity::find()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|perm| (perm.code.clone(), perm))
+        .collect();
+
+    let mut summary = SyncSummary::default();
+    let mut ids: HashMap<&str, Uuid> = HashMap::new();
+    let now = chrono::Local::now();
+
+    for definition in &catalog {
+        let parent_id = definition
+            .parent
+            .as_deref()
+            .and_then(|parent| ids.get(parent).copied());
+
+        let Some(current) = existing.get(&definition.code) else {
+            let id = Uuid::now_v7();
+            permissions::ActiveModel {
+                id: Set(id),
+                code: Set(definition.code.clone()),
+                name: Set(definition.name.clone()),
+                description: Set(definition.description.clone()),
+                resource_type: Set(definition.resource_type.clone()),
+                http_method: Set(definition.http_method.clone()),
+                resource_path: Set(definition.resource_path.clone()),
+                parent_id: Set(parent_id),
+                sort_order: Set(definition.sort_order),
+                is_system: Set(Some(true)),
+                created_at: Set(Some(now.into())),
+                updated_at: Set(Some(now.into())),
+            }
+            .insert(&txn)
+            .await?;
+            ids.insert(&definition.code, id);
+            summary.created += 1;
+            continue;
+        };
+
+        ids.insert(&definition.code, current.id);
+        let unchanged = current.name == definition.name
+            && current.description == definition.description
+            && current.resource_type == definition.resource_type
+            && current.http_method == definition.http_method
+            && current.resource_path == definition.resource_path
+            && current.parent_id == parent_id
+            && current.sort_order == definition.sort_order
+            && current.is_system == Some(true);
+        if unchanged {
+            continue;
+        }
+
+        if current.is_system != Some(true) {
+            warn!(
+                "权限代码 {} 与内置权限重名，已接管为内置权限",
+                definition.code
+            );
+        }
+        permissions::ActiveModel {
+            id: Set(current.id),
+            name: Set(definition.name.clone()),
+            description: Set(definition.description.clone()),
+            resource_type: Set(definition.resource_type.clone()),
+            http_method: Set(definition.http_method.clone()),
+            resource_path: Set(definition.resource_path.clone()),
+            parent_id: Set(parent_id),
+            sort_order: Set(definition.sort_order),
+            is_system: Set(Some(true)),
+            updated_at: Set(Some(now.into())),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        summary.updated += 1;
+    }
+
+    let declared: HashSet<&str> = catalog.iter().map(|d| d.code.as_str()).collect();
+    let stale: Vec<Uuid> = existing
+        .values()
+        .filter(|perm| {
+            perm.is_system == Some(true)
+                && is_builtin_code(&perm.code)
+                && !declared.contains(perm.code.as_str())
+        })
+        .map(|perm| perm.id)
+        .collect();
+    if !stale.is_empty() {
+        role_permissions::Entity::delete_many()
+            .filter(role_permissions::Column::PermissionId.is_in(stale.clone()))
+            .exec(&txn)
+            .await?;
+        // 挂在已删除内置菜单下的自定义权限提升为根节点
+        permissions::Entity::update_many()
+            .col_expr(
+                permissions::Column::ParentId,
+                sea_orm::sea_query::Expr::value(Option::<Uuid>::None),
+            )
+            .filter(permissions::Column::ParentId.is_in(stale.clone()))
+            .exec(&txn)
+            .await?;
+        permissions::Entity::delete_many()
+            .filter(permissions::Column::Id.is_in(stale.iter().copied()))
+            .exec(&txn)
+            .await?;
+        summary.deleted = stale.len();
+    }
+
+    txn.commit().await?;
+
+    if summary != SyncSummary::default() {
+        info!(
+            "内置接口权限已同步：新增 {}，更新 {}，删除 {}",
+            summary.created, summary.updated, summary.deleted
+        );
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::protected_api_routes;
+
+    /// 受保护路由都要有对应的内置权限，否则非超级管理员无法被授权访问
+    #[test]
+    fn test_catalog_covers_protected_routes() {
+        let catalog = builtin_catalog();
+        let routes = protected_api_routes();
+        for route in &routes {
+            let path = format!("{}{}", API_PREFIX, route.path);
+            assert!(
+                catalog
+                    .iter()
+                    .any(|d| d.resource_path.as_deref() == Some(&path)
+                        && d.http_method.as_deref() == Some(route.method.as_str())),
+                "{} {} 缺少内置权限",
+                route.method,
+                path
+            );
+        }
+
+        let apis = catalog.iter().filter(|d| d.resource_type == "api").count();
+        assert_eq!(apis, routes.len(), "内置权限中存在不需要权限检查的接口");
+    }
+
+    #[test]
+    fn test_catalog_well_formed() {
+        let catalog = builtin_catalog();
+        let mut seen = HashSet::new();
+        for definition in &catalog {
+            assert!(is_builtin_code(&definition.code));
+            if let Some(parent) = &definition.parent {
+                assert!(seen.contains(parent.as_str()), "{}", definition.code);
+            }
+            assert!(seen.insert(definition.code.as_str()), "{}", definition.code);
+            validator::Validate::validate(definition).unwrap();
+        }
+
+        let list_admin = catalog
+            .iter()
+            .find(|d| d.code == "guardian:admin:list_admin")
+            .unwrap();
+        assert_eq!(
+            list_admin.resource_path.as_deref(),
+            Some("/guardian-auth/v1/admins")
+        );
+        assert!(!catalog.iter().any(|d| {
+            d.resource_path
+                .as_deref()
+                .is_some_and(|p| p.contains("/auth/"))
+        }));
+    }
+}
@@ -0,0 +1,90 @@
+//! 存活与就绪检查，供 Kubernetes 等编排系统的探针使用
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use log::warn;
+
+use crate::dto::{LivenessResponse, ReadinessResponse};
+use crate::migration::migration_status;
+use crate::router::AppState;
+
+/// 单项检查的超时，避免连接池耗尽时探针一直挂起
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 进程的运行状态，所有 `AppState` 副本共享
+pub struct Lifecycle {
+    started_at: Instant,
+    shutting_down: AtomicBool,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Lifecycle {
+    /// 收到退出信号后调用，之后的就绪检查一律失败，负载均衡不再分配新请求
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::Relaxed)
+    }
+}
+
+pub fn liveness(state: &AppState) -> LivenessResponse {
+    LivenessResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_secs: state.lifecycle.started_at.elapsed().as_secs(),
+    }
+}
+
+/// 数据库可用、迁移全部执行且未在停止中时就绪
+pub async fn readiness(state: &AppState) -> ReadinessResponse {
+    let database = match tokio::time::timeout(CHECK_TIMEOUT, state.conn.ping()).await {
+        Ok(Ok(())) => true,
+        Ok(Err(e)) => {
+            warn!("就绪检查：数据库不可用: {}", e);
+            false
+        }
+        Err(_) => {
+            warn!("就绪检查：数据库响应超时");
+            false
+        }
+    };
+
+    let mut migration = None;
+    let mut pending_migrations = Vec::new();
+    let mut migrations_checked = false;
+    if database {
+        match tokio::time::timeout(CHECK_TIMEOUT, migration_status(&state.conn)).await {
+            Ok(Ok(status)) => {
+                for (name, applied) in status {
+                    if applied {
+                        migration = Some(name);
+                    } else {
+                        pending_migrations.push(name);
+                    }
+                }
+                migrations_checked = true;
+            }
+            Ok(Err(e)) => warn!("就绪检查：无法读取迁移记录: {:#}", e),
+            Err(_) => warn!("就绪检查：读取迁移记录超时"),
+        }
+    }
+
+    let shutting_down = state.lifecycle.is_shutting_down();
+    ReadinessResponse {
+        ready: database && migrations_checked && pending_migrations.is_empty() && !shutting_down,
+        database,
+        migration,
+        pending_migrations,
+        shutting_down,
+    }
+}
@@ -1,22 +1,47 @@
-use crate::entities::{admin_roles, admins, permissions, role_permissions, roles};
+use crate::entities::admins;
 use crate::router::AppState;
+use crate::service::password_policy_service::{check_new_password, record_password_history};
 use crate::utils::hash_password;
 use anyhow::{Result, anyhow};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, EntityTrait, NotSet, QueryFilter, Set, TransactionTrait,
 };
 
-pub async fn init_system(state: AppState) -> Result<String> {
-    let existing_admin = admins::Entity::find()
-        .filter(admins::Column::Username.eq("admin"))
+pub async fn find_super_admin(state: &AppState) -> Result<Option<admins::Model>> {
+    Ok(admins::Entity::find()
+        .filter(admins::Column::IsSuperAdmin.eq(true))
         .one(&state.conn)
-        .await;
+        .await?)
+}
 
-    if let Ok(Some(_)) = existing_admin {
-        return Ok("系统已初始化".to_string());
+/// 创建首个超级管理员；已存在超级管理员时不做任何修改，避免误覆盖线上账号。
+/// 超级管理员不经过权限检查，角色和权限通过 RBAC 定义文件导入
+pub async fn init_system(
+    state: AppState,
+    username: &str,
+    password: &str,
+    must_change_password: bool,
+) -> Result<String> {
+    if let Some(admin) = find_super_admin(&state).await? {
+        return Ok(format!("系统已初始化（超级管理员：{}）", admin.username));
     }
 
-    let password_hash = hash_password("123456");
+    if admins::Entity::find()
+        .filter(admins::Column::Username.eq(username))
+        .one(&state.conn)
+        .await?
+        .is_some()
+    {
+        return Err(anyhow!("用户名 {} 已被占用", username));
+    }
+
+    if let Some(msg) = check_new_password(&state, None, username, password).await? {
+        return Err(anyhow!(msg));
+    }
+
+    let password_hash = hash_password(&state.config.password_hash, password);
+    let policy = state.config.password_policy.clone();
+    let username = username.to_string();
 
     state
         .conn
@@ -24,90 +49,19 @@ pub async fn init_system(state: AppState) -> Result<String> {
             Box::pin(async move {
                 let admin = admins::ActiveModel {
                     id: NotSet,
-                    username: Set("admin".to_string()),
+                    username: Set(username),
                     password_hash: Set(password_hash.clone()),
                     is_super_admin: Set(Some(true)),
                     status: Set(Some(1)),
+                    password_changed_at: Set(Some(chrono::Local::now().into())),
+                    must_change_password: Set(Some(must_change_password)),
                     created_at: NotSet,
                     updated_at: NotSet,
                     ..Default::default()
                 };
                 let admin = admin.insert(txn).await?;
 
-                let super_admin_role = roles::ActiveModel {
-                    id: NotSet,
-                    code: Set("super_admin".to_string()),
-                    name: Set("超级管理员".to_string()),
-                    description: Set(Some("拥有所有权限".to_string())),
-                    is_system: Set(Some(true)),
-                    created_at: NotSet,
-                    updated_at: NotSet,
-                };
-                let super_admin_role = super_admin_role.insert(txn).await?;
-
-                let admin_role = roles::ActiveModel {
-                    id: NotSet,
-                    code: Set("admin".to_string()),
-                    name: Set("普通管理员".to_string()),
-                    description: Set(Some("拥有部分权限".to_string())),
-                    is_system: Set(Some(false)),
-                    created_at: NotSet,
-                    updated_at: NotSet,
-                };
-                let admin_role = admin_role.insert(txn).await?;
-
-                let user_create_permission = permissions::ActiveModel {
-                    id: NotSet,
-                    code: Set("user:create".to_string()),
-                    name: Set("创建用户".to_string()),
-                    description: Set(Some("创建新用户".to_string())),
-                    resource_type: Set("api".to_string()),
-                    http_method: Set(Some("POST".to_string())),
-                    resource_path: Set(Some("/api/v1/users".to_string())),
-                    parent_id: Set(None),
-                    sort_order: Set(Some(1)),
-                    is_system: Set(Some(true)),
-                    created_at: NotSet,
-                    updated_at: NotSet,
-                };
-                let user_create_permission = user_create_permission.insert(txn).await?;
-
-                let user_read_permission = permissions::ActiveModel {
-                    id: NotSet,
-                    code: Set("user:read".to_string()),
-                    name: Set("查看用户".to_string()),
-                    description: Set(Some("查看用户信息".to_string())),
-                    resource_type: Set("api".to_string()),
-                    http_method: Set(Some("GET".to_string())),
-                    resource_path: Set(Some("/api/v1/users".to_string())),
-                    parent_id: Set(None),
-                    sort_order: Set(Some(2)),
-                    is_system: Set(Some(true)),
-                    created_at: NotSet,
-                    updated_at: NotSet,
-                };
-                let user_read_permission = user_read_permission.insert(txn).await?;
-
-                let admin_role_map = admin_roles::ActiveModel {
-                    admin_id: Set(admin.id),
-                    role_id: Set(super_admin_role.id),
-                    created_at: NotSet,
-                };
-                admin_role_map.insert(txn).await?;
-
-                let rp1 = role_permissions::ActiveModel {
-                    role_id: Set(super_admin_role.id),
-                    permission_id: Set(user_create_permission.id),
-                    created_at: NotSet,
-                };
-                rp1.insert(txn).await?;
-
-                let rp2 = role_permissions::ActiveModel {
-                    role_id: Set(super_admin_role.id),
-                    permission_id: Set(user_read_permission.id),
-                    created_at: NotSet,
-                };
-                rp2.insert(txn).await?;
+                record_password_history(txn, &policy, admin.id, &password_hash).await?;
 
                 Ok::<_, sea_orm::DbErr>(())
             })
@@ -0,0 +1,639 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use ipnet::IpNet;
+use log::warn;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, NotSet,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::dto::{
+    CreateIpRuleRequest, IpRuleListQuery, IpRuleListResponse, IpRuleResponse, UpdateIpRuleRequest,
+};
+use crate::entities::{admins, ip_rules};
+use crate::error::{GuardianError, Result};
+use crate::i18n::t;
+use crate::middleware::auth::AuthContext;
+use crate::response::{Response, ResponseCode};
+use crate::router::AppState;
+use crate::service::audit_service::{AuditEntry, record_audit_quietly};
+
+const IP_RULES_RESOURCE: &str = "/ip-rules";
+
+/// 同一 IP 的拒绝记录在该时间内只写一次审计日志，避免被封禁地址刷爆日志
+const DENIAL_AUDIT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpRuleType {
+    Allow,
+    Deny,
+}
+
+impl FromStr for IpRuleType {
+    type Err = GuardianError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            other => Err(GuardianError::Validation(t!(
+                "ip_rule.invalid_type",
+                value = other
+            ))),
+        }
+    }
+}
+
+/// 解析 CIDR 网段，单个 IP 视为 /32 或 /128，主机位会被清零
+pub fn parse_cidr(s: &str) -> Result<IpNet> {
+    let s = s.trim();
+    let network = match s.parse::<IpNet>() {
+        Ok(network) => network,
+        Err(_) => IpNet::from(
+            s.parse::<IpAddr>()
+                .map_err(|_| GuardianError::Validation(t!("ip_rule.invalid_cidr", value = s)))?,
+        ),
+    };
+    Ok(network.trunc())
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    id: Uuid,
+    admin_id: Option<Uuid>,
+    rule_type: IpRuleType,
+    network: IpNet,
+}
+
+/// 被拒绝的原因，写入审计日志
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpDenial {
+    /// 命中的拒绝规则，未命中白名单时为空
+    pub rule_id: Option<Uuid>,
+    /// 规则作用的管理员，全局规则为空
+    pub admin_id: Option<Uuid>,
+}
+
+impl IpDenial {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "rule_id": self.rule_id,
+            "scope": if self.admin_id.is_some() { "admin" } else { "global" },
+            "reason": if self.rule_id.is_some() { "deny_rule" } else { "not_in_allowlist" },
+        })
+    }
+}
+
+/// 编译后的 IP 规则。全局规则和管理员规则分别判定，两层都通过才放行：
+/// 命中任一拒绝规则即拒绝；该层存在允许规则时，必须命中其中之一。
+#[derive(Debug, Clone, Default)]
+pub struct IpRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl IpRuleSet {
+    pub fn from_models(models: &[ip_rules::Model]) -> Self {
+        let rules = models
+            .iter()
+            .filter_map(|model| {
+                let compiled = model
+                    .rule_type
+                    .parse()
+                    .and_then(|rule_type| Ok((rule_type, parse_cidr(&model.cidr)?)));
+                match compiled {
+                    Ok((rule_type, network)) => Some(CompiledRule {
+                        id: model.id,
+                        admin_id: model.admin_id,
+                        rule_type,
+                        network,
+                    }),
+                    Err(e) => {
+                        warn!("忽略无效的 IP 规则 {}: {}", model.id, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    pub fn check(&self, ip: IpAddr, admin_id: Option<Uuid>) -> Option<IpDenial> {
+        let ip = ip.to_canonical();
+        self.check_scope(ip, None)
+            .or_else(|| admin_id.and_then(|admin_id| self.check_scope(ip, Some(admin_id))))
+    }
+
+    fn check_scope(&self, ip: IpAddr, admin_id: Option<Uuid>) -> Option<IpDenial> {
+        let rules: Vec<&CompiledRule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.admin_id == admin_id)
+            .collect();
+
+        if let Some(rule) = rules
+            .iter()
+            .find(|rule| rule.rule_type == IpRuleType::Deny && rule.network.contains(&ip))
+        {
+            return Some(IpDenial {
+                rule_id: Some(rule.id),
+                admin_id,
+            });
+        }
+
+        let mut allow_rules = rules
+            .iter()
+            .filter(|rule| rule.rule_type == IpRuleType::Allow)
+            .peekable();
+        if allow_rules.peek().is_some() && !allow_rules.any(|rule| rule.network.contains(&ip)) {
+            return Some(IpDenial {
+                rule_id: None,
+                admin_id,
+            });
+        }
+
+        None
+    }
+}
+
+type CachedRules = Option<(Instant, Arc<IpRuleSet>)>;
+
+/// IP 规则缓存，本实例修改规则时立即失效，其他实例在 `IP_RULES_CACHE_SECS` 后刷新
+#[derive(Clone)]
+pub struct IpRuleCache {
+    ttl: Duration,
+    cached: Arc<RwLock<CachedRules>>,
+    audited: Arc<DashMap<IpAddr, Instant>>,
+}
+
+impl IpRuleCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cached: Arc::new(RwLock::new(None)),
+            audited: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub async fn rules(&self, conn: &DatabaseConnection) -> Result<Arc<IpRuleSet>> {
+        let cached = self.cached.read().unwrap().clone();
+        if let Some((loaded_at, rules)) = &cached
+            && loaded_at.elapsed() < self.ttl
+        {
+            return Ok(rules.clone());
+        }
+
+        match ip_rules::Entity::find().all(conn).await {
+            Ok(models) => {
+                let rules = Arc::new(IpRuleSet::from_models(&models));
+                *self.cached.write().unwrap() = Some((Instant::now(), rules.clone()));
+                Ok(rules)
+            }
+            // 数据库暂时不可用时沿用上一次加载的规则
+            Err(e) => match cached {
+                Some((_, rules)) => {
+                    warn!("刷新 IP 规则失败，沿用缓存: {}", e);
+                    Ok(rules)
+                }
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    pub fn invalidate(&self) {
+        *self.cached.write().unwrap() = None;
+    }
+
+    /// 直接写入规则，测试中代替从数据库加载
+    #[cfg(test)]
+    pub(crate) fn preload(&self, rules: IpRuleSet) {
+        *self.cached.write().unwrap() = Some((Instant::now(), Arc::new(rules)));
+    }
+
+    pub(crate) fn should_audit(&self, ip: IpAddr) -> bool {
+        match self.audited.entry(ip) {
+            Entry::Occupied(mut entry) => {
+                if entry.get().elapsed() < DENIAL_AUDIT_INTERVAL {
+                    return false;
+                }
+                entry.insert(Instant::now());
+                true
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Instant::now());
+                true
+            }
+        }
+    }
+
+    pub fn cleanup_expired(&self) {
+        self.audited
+            .retain(|_, audited_at| audited_at.elapsed() < DENIAL_AUDIT_INTERVAL);
+    }
+}
+
+/// 记录 IP 拒绝审计日志，同一 IP 按 `DENIAL_AUDIT_INTERVAL` 限频
+pub async fn record_ip_denial(
+    state: &AppState,
+    denial: &IpDenial,
+    client_ip: IpAddr,
+    admin: Option<(Uuid, String)>,
+    method: &str,
+    resource: &str,
+    user_agent: Option<String>,
+) {
+    if !state.ip_rules.should_audit(client_ip) {
+        return;
+    }
+
+    let (admin_id, username) = admin.unzip();
+    record_audit_quietly(
+        &state.conn,
+        AuditEntry {
+            admin_id,
+            username,
+            action: "ip_denied".to_string(),
+            resource: resource.to_string(),
+            method: method.to_string(),
+            params: None,
+            result: Some(denial.to_json()),
+            status_code: 403,
+            ip_address: Some(client_ip.to_string()),
+            user_agent,
+        },
+    )
+    .await;
+}
+
+fn to_response(rule: ip_rules::Model) -> IpRuleResponse {
+    IpRuleResponse {
+        id: rule.id,
+        admin_id: rule.admin_id,
+        rule_type: rule.rule_type,
+        cidr: rule.cidr,
+        description: rule.description,
+        created_by: rule.created_by,
+        created_at: rule
+            .created_at
+            .map(|dt| dt.into())
+            .unwrap_or_else(chrono::Local::now),
+        updated_at: rule
+            .updated_at
+            .map(|dt| dt.into())
+            .unwrap_or_else(chrono::Local::now),
+    }
+}
+
+/// 规则变更后当前请求者将无法访问时拒绝保存，避免把自己锁在外面
+async fn ensure_not_locked_out(
+    state: &AppState,
+    auth_context: &AuthContext,
+    client_ip: IpAddr,
+    apply: impl FnOnce(&mut Vec<ip_rules::Model>),
+) -> Result<()> {
+    let mut models = ip_rules::Entity::find().all(&state.conn).await?;
+    apply(&mut models);
+
+    let locked_out = IpRuleSet::from_models(&models)
+        .check(client_ip, Some(auth_context.admin_id))
+        .is_some();
+
+    if locked_out {
+        return Err(GuardianError::Code(
+            ResponseCode::IpNotAllowed,
+            Some(t!("ip_rule.self_lockout", ip = client_ip)),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn record_rule_change(
+    state: &AppState,
+    auth_context: &AuthContext,
+    client_ip: IpAddr,
+    action: &str,
+    method: &str,
+    rule: serde_json::Value,
+) {
+    state.ip_rules.invalidate();
+    record_audit_quietly(
+        &state.conn,
+        AuditEntry {
+            admin_id: Some(auth_context.admin_id),
+            username: Some(auth_context.username.clone()),
+            action: action.to_string(),
+            resource: IP_RULES_RESOURCE.to_string(),
+            method: method.to_string(),
+            params: Some(rule),
+            result: None,
+            status_code: 200,
+            ip_address: Some(client_ip.to_string()),
+            user_agent: None,
+        },
+    )
+    .await;
+}
+
+pub async fn list_ip_rule_service(
+    state: AppState,
+    query: IpRuleListQuery,
+) -> Result<Response<IpRuleListResponse>> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20);
+    let offset = page.saturating_sub(1).saturating_mul(page_size);
+
+    let mut select = ip_rules::Entity::find();
+
+    if query.global.unwrap_or(false) {
+        select = select.filter(ip_rules::Column::AdminId.is_null());
+    } else if let Some(admin_id) = query.admin_id {
+        select = select.filter(ip_rules::Column::AdminId.eq(admin_id));
+    }
+
+    if let Some(rule_type) = &query.rule_type {
+        select = select.filter(ip_rules::Column::RuleType.eq(rule_type));
+    }
+
+    let total = select.clone().count(&state.conn).await?;
+
+    let list = select
+        .order_by_desc(ip_rules::Column::CreatedAt)
+        .limit(page_size)
+        .offset(offset)
+        .all(&state.conn)
+        .await?
+        .into_iter()
+        .map(to_response)
+        .collect();
+
+    Ok(Response::ok_data(IpRuleListResponse {
+        total,
+        page,
+        page_size,
+        list,
+    }))
+}
+
+pub async fn get_ip_rule_service(state: AppState, id: Uuid) -> Result<Response<IpRuleResponse>> {
+    let rule = ip_rules::Entity::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("ip_rule.not_found")))?;
+
+    Ok(Response::ok_data(to_response(rule)))
+}
+
+pub async fn create_ip_rule_service(
+    state: AppState,
+    auth_context: AuthContext,
+    client_ip: IpAddr,
+    payload: CreateIpRuleRequest,
+) -> Result<Response<IpRuleResponse>> {
+    payload.rule_type.parse::<IpRuleType>()?;
+    let network = parse_cidr(&payload.cidr)?;
+
+    if let Some(admin_id) = payload.admin_id {
+        admins::Entity::find_by_id(admin_id)
+            .one(&state.conn)
+            .await?
+            .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+    }
+
+    let now = chrono::Local::now();
+    let rule = ip_rules::Model {
+        id: Uuid::now_v7(),
+        admin_id: payload.admin_id,
+        rule_type: payload.rule_type,
+        cidr: network.to_string(),
+        description: payload.description,
+        created_by: Some(auth_context.admin_id),
+        created_at: Some(now.into()),
+        updated_at: Some(now.into()),
+    };
+
+    let candidate = rule.clone();
+    ensure_not_locked_out(&state, &auth_context, client_ip, |models| {
+        models.push(candidate)
+    })
+    .await?;
+
+    let rule = ip_rules::ActiveModel {
+        id: Set(rule.id),
+        admin_id: Set(rule.admin_id),
+        rule_type: Set(rule.rule_type),
+        cidr: Set(rule.cidr),
+        description: Set(rule.description),
+        created_by: Set(rule.created_by),
+        created_at: NotSet,
+        updated_at: NotSet,
+    }
+    .insert(&state.conn)
+    .await?;
+
+    record_rule_change(
+        &state,
+        &auth_context,
+        client_ip,
+        "ip_rule_created",
+        "POST",
+        json!({
+            "id": rule.id,
+            "admin_id": rule.admin_id,
+            "rule_type": rule.rule_type,
+            "cidr": rule.cidr,
+        }),
+    )
+    .await;
+
+    Ok(Response::ok(Some(t!("common.created")), to_response(rule)))
+}
+
+pub async fn update_ip_rule_service(
+    state: AppState,
+    auth_context: AuthContext,
+    client_ip: IpAddr,
+    id: Uuid,
+    payload: UpdateIpRuleRequest,
+) -> Result<Response<IpRuleResponse>> {
+    let rule = ip_rules::Entity::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("ip_rule.not_found")))?;
+
+    let mut updated = rule.clone();
+    if let Some(rule_type) = payload.rule_type {
+        rule_type.parse::<IpRuleType>()?;
+        updated.rule_type = rule_type;
+    }
+    if let Some(cidr) = &payload.cidr {
+        updated.cidr = parse_cidr(cidr)?.to_string();
+    }
+    if let Some(description) = payload.description {
+        updated.description = Some(description);
+    }
+
+    let candidate = updated.clone();
+    ensure_not_locked_out(&state, &auth_context, client_ip, |models| {
+        for model in models.iter_mut().filter(|model| model.id == id) {
+            *model = candidate.clone();
+        }
+    })
+    .await?;
+
+    let mut rule_model: ip_rules::ActiveModel = rule.into_active_model();
+    rule_model.rule_type = Set(updated.rule_type);
+    rule_model.cidr = Set(updated.cidr);
+    rule_model.description = Set(updated.description);
+    rule_model.updated_at = Set(Some(chrono::Local::now().into()));
+    let rule = rule_model.update(&state.conn).await?;
+
+    record_rule_change(
+        &state,
+        &auth_context,
+        client_ip,
+        "ip_rule_updated",
+        "PUT",
+        json!({
+            "id": rule.id,
+            "admin_id": rule.admin_id,
+            "rule_type": rule.rule_type,
+            "cidr": rule.cidr,
+        }),
+    )
+    .await;
+
+    Ok(Response::ok(Some(t!("common.updated")), to_response(rule)))
+}
+
+pub async fn delete_ip_rule_service(
+    state: AppState,
+    auth_context: AuthContext,
+    client_ip: IpAddr,
+    id: Uuid,
+) -> Result<Response<()>> {
+    let rule = ip_rules::Entity::find_by_id(id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("ip_rule.not_found")))?;
+
+    ensure_not_locked_out(&state, &auth_context, client_ip, |models| {
+        models.retain(|model| model.id != id)
+    })
+    .await?;
+
+    let rule_json = json!({
+        "id": rule.id,
+        "admin_id": rule.admin_id,
+        "rule_type": rule.rule_type,
+        "cidr": rule.cidr,
+    });
+    rule.into_active_model().delete(&state.conn).await?;
+
+    record_rule_change(
+        &state,
+        &auth_context,
+        client_ip,
+        "ip_rule_deleted",
+        "DELETE",
+        rule_json,
+    )
+    .await;
+
+    Ok(Response::ok_msg(Some(t!("common.deleted"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(admin_id: Option<Uuid>, rule_type: &str, cidr: &str) -> ip_rules::Model {
+        ip_rules::Model {
+            id: Uuid::now_v7(),
+            admin_id,
+            rule_type: rule_type.to_string(),
+            cidr: cidr.to_string(),
+            description: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_cidr() {
+        assert_eq!(parse_cidr("10.1.2.3/8").unwrap().to_string(), "10.0.0.0/8");
+        assert_eq!(
+            parse_cidr(" 192.168.1.1 ").unwrap().to_string(),
+            "192.168.1.1/32"
+        );
+        assert_eq!(
+            parse_cidr("2001:db8::1/32").unwrap().to_string(),
+            "2001:db8::/32"
+        );
+        assert!(parse_cidr("10.0.0.0/33").is_err());
+        assert!(parse_cidr("office").is_err());
+    }
+
+    #[test]
+    fn test_global_rules() {
+        let deny = rule(None, "deny", "10.0.0.99");
+        let rules = IpRuleSet::from_models(&[rule(None, "allow", "10.0.0.0/24"), deny.clone()]);
+
+        assert_eq!(rules.check("10.0.0.1".parse().unwrap(), None), None);
+        assert_eq!(
+            rules.check("10.0.0.99".parse().unwrap(), None),
+            Some(IpDenial {
+                rule_id: Some(deny.id),
+                admin_id: None
+            })
+        );
+        assert_eq!(
+            rules.check("192.168.0.1".parse().unwrap(), None),
+            Some(IpDenial {
+                rule_id: None,
+                admin_id: None
+            })
+        );
+        // IPv4 映射的 IPv6 地址按 IPv4 匹配
+        assert_eq!(rules.check("::ffff:10.0.0.1".parse().unwrap(), None), None);
+    }
+
+    #[test]
+    fn test_admin_rules() {
+        let admin_id = Uuid::now_v7();
+        let other_id = Uuid::now_v7();
+        let rules = IpRuleSet::from_models(&[
+            rule(None, "deny", "203.0.113.0/24"),
+            rule(Some(admin_id), "allow", "10.8.0.0/16"),
+        ]);
+
+        assert_eq!(
+            rules.check("10.8.1.1".parse().unwrap(), Some(admin_id)),
+            None
+        );
+        assert!(
+            rules
+                .check("172.16.0.1".parse().unwrap(), Some(admin_id))
+                .is_some()
+        );
+        assert_eq!(
+            rules.check("172.16.0.1".parse().unwrap(), Some(other_id)),
+            None
+        );
+        assert_eq!(rules.check("172.16.0.1".parse().unwrap(), None), None);
+        // 全局拒绝优先于管理员白名单之外的判断
+        assert!(
+            rules
+                .check("203.0.113.5".parse().unwrap(), Some(other_id))
+                .is_some()
+        );
+    }
+}
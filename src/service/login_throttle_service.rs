@@ -0,0 +1,181 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::middleware::rate_limit::now_millis;
+use crate::store::CounterStore;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoginThrottleConfig {
+    /// 同一 IP 连续失败达到该次数后开始退避
+    pub ip_free_attempts: u32,
+    /// 同一用户名连续失败达到该次数后开始退避
+    pub username_free_attempts: u32,
+    /// 第一次退避的等待秒数，之后每失败一次翻倍
+    pub base_delay_secs: u64,
+    /// 单次退避的最长等待秒数
+    pub max_delay_secs: u64,
+    /// 超过该秒数没有新的失败时清零计数（用户名计数由后台维护任务在锁定到期后清零）
+    pub reset_after_secs: u64,
+    /// 失败达到该次数后要求提交验证码（仅在配置了验证码时生效），0 表示不要求
+    pub captcha_after_failures: u32,
+}
+
+impl Default for LoginThrottleConfig {
+    fn default() -> Self {
+        Self {
+            ip_free_attempts: 20,
+            username_free_attempts: 5,
+            base_delay_secs: 30,
+            max_delay_secs: 900,
+            reset_after_secs: 3600,
+            captcha_after_failures: 3,
+        }
+    }
+}
+
+impl LoginThrottleConfig {
+    /// 连续失败 `failures` 次后需要等待的秒数，未达到 `free_attempts` 时返回 `None`
+    pub fn backoff_secs(&self, failures: u32, free_attempts: u32) -> Option<u64> {
+        if free_attempts == 0 || failures < free_attempts {
+            return None;
+        }
+
+        let exponent = (failures - free_attempts).min(31);
+        Some(
+            self.base_delay_secs
+                .saturating_mul(1u64 << exponent)
+                .min(self.max_delay_secs),
+        )
+    }
+
+    pub fn captcha_required(&self, failures: u32) -> bool {
+        self.captcha_after_failures > 0 && failures >= self.captcha_after_failures
+    }
+}
+
+/// 某个 IP 当前的登录失败情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IpThrottleState {
+    /// 连续失败次数（已过期的记录视为 0）
+    pub failures: u32,
+    /// 仍处于退避期时的剩余等待秒数
+    pub retry_after: Option<u64>,
+}
+
+/// 登录节流。用户名维度的失败次数和锁定时间保存在管理员表中，
+/// IP 维度保存在限流计数存储中（`RATE_LIMIT_STORE=postgres` 时多实例共享），
+/// 避免攻击者通过不存在的用户名无限尝试。
+#[derive(Clone)]
+pub struct LoginThrottle {
+    config: Arc<LoginThrottleConfig>,
+    store: Arc<dyn CounterStore>,
+}
+
+impl LoginThrottle {
+    pub fn new(config: LoginThrottleConfig, store: Arc<dyn CounterStore>) -> Self {
+        Self {
+            config: Arc::new(config),
+            store,
+        }
+    }
+
+    pub fn config(&self) -> &LoginThrottleConfig {
+        &self.config
+    }
+
+    fn reset_after_ms(&self) -> i64 {
+        self.config
+            .reset_after_secs
+            .saturating_mul(1000)
+            .min(i64::MAX as u64) as i64
+    }
+
+    fn ip_key(ip: IpAddr) -> String {
+        format!("login_ip:{}", ip)
+    }
+
+    pub async fn ip_state(&self, ip: IpAddr) -> Result<IpThrottleState> {
+        let Some((failures, last_failure_ms)) = self.store.failure_state(&Self::ip_key(ip)).await?
+        else {
+            return Ok(IpThrottleState::default());
+        };
+
+        let elapsed_ms = now_millis().saturating_sub(last_failure_ms).max(0);
+        if elapsed_ms >= self.reset_after_ms() {
+            return Ok(IpThrottleState::default());
+        }
+
+        let failures = failures.min(u32::MAX as u64) as u32;
+        let retry_after = self
+            .config
+            .backoff_secs(failures, self.config.ip_free_attempts)
+            .and_then(|delay| {
+                let delay_ms = delay.saturating_mul(1000).min(i64::MAX as u64) as i64;
+                let remaining_ms = delay_ms - elapsed_ms;
+                (remaining_ms > 0).then(|| (remaining_ms as u64).div_ceil(1000))
+            });
+
+        Ok(IpThrottleState {
+            failures,
+            retry_after,
+        })
+    }
+
+    pub async fn record_ip_failure(&self, ip: IpAddr) -> Result<()> {
+        self.store
+            .record_failure(&Self::ip_key(ip), now_millis(), self.reset_after_ms())
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryCounterStore;
+
+    #[test]
+    fn test_backoff_secs() {
+        let config = LoginThrottleConfig::default();
+
+        assert_eq!(config.backoff_secs(4, 5), None);
+        assert_eq!(config.backoff_secs(5, 5), Some(30));
+        assert_eq!(config.backoff_secs(6, 5), Some(60));
+        assert_eq!(config.backoff_secs(7, 5), Some(120));
+        assert_eq!(config.backoff_secs(100, 5), Some(900));
+        assert_eq!(config.backoff_secs(100, 0), None);
+    }
+
+    #[tokio::test]
+    async fn test_ip_throttle() {
+        let store: Arc<dyn CounterStore> = Arc::new(MemoryCounterStore::default());
+        let config = LoginThrottleConfig {
+            ip_free_attempts: 3,
+            ..LoginThrottleConfig::default()
+        };
+        let throttle = LoginThrottle::new(config.clone(), store.clone());
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+
+        throttle.record_ip_failure(ip).await.unwrap();
+        throttle.record_ip_failure(ip).await.unwrap();
+        assert_eq!(throttle.ip_state(ip).await.unwrap().retry_after, None);
+
+        throttle.record_ip_failure(ip).await.unwrap();
+        let state = throttle.ip_state(ip).await.unwrap();
+        assert_eq!(state.failures, 3);
+        assert!(state.retry_after.is_some_and(|secs| secs <= 30));
+        assert_eq!(
+            throttle.ip_state(other).await.unwrap(),
+            IpThrottleState::default()
+        );
+
+        // 共用同一存储的其他实例看到相同的计数
+        let peer = LoginThrottle::new(config, store);
+        assert_eq!(peer.ip_state(ip).await.unwrap(), state);
+    }
+}
@@ -0,0 +1,187 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::Result;
+use log::{info, warn};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::config::MaintenanceConfig;
+use crate::entities::{
+    admins, audit_logs, mfa_challenges, password_reset_tokens, webauthn_challenges,
+};
+use crate::router::AppState;
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<u64>> + Send>>;
+
+/// 周期性维护任务
+struct MaintenanceJob {
+    name: &'static str,
+    /// 执行间隔，0 表示禁用
+    interval_secs: u64,
+    run: fn(AppState) -> JobFuture,
+}
+
+fn jobs(config: &MaintenanceConfig) -> Vec<MaintenanceJob> {
+    vec![
+        MaintenanceJob {
+            name: "limiter_cleanup",
+            interval_secs: config.limiter_cleanup_interval_secs,
+            run: |state| Box::pin(cleanup_limiters(state)),
+        },
+        MaintenanceJob {
+            name: "expired_tokens",
+            interval_secs: config.expired_tokens_interval_secs,
+            run: |state| Box::pin(purge_expired_tokens(state)),
+        },
+        MaintenanceJob {
+            name: "audit_retention",
+            interval_secs: config.audit_retention_interval_secs,
+            run: |state| Box::pin(purge_audit_logs(state)),
+        },
+        MaintenanceJob {
+            name: "lock_reset",
+            interval_secs: config.lock_reset_interval_secs,
+            run: |state| Box::pin(reset_expired_locks(state)),
+        },
+    ]
+}
+
+/// 清理过期的限流和登录失败计数，以及内存中的 IP 拒绝审计计数
+async fn cleanup_limiters(state: AppState) -> Result<u64> {
+    state.ip_rules.cleanup_expired();
+    state.rate_limiter.cleanup_expired().await
+}
+
+/// 删除已过期的吊销令牌、2FA 挑战和密码重置令牌
+async fn purge_expired_tokens(state: AppState) -> Result<u64> {
+    let now = chrono::Local::now();
+
+    let revocations = state.revocations.purge_expired().await?;
+
+    let challenges = mfa_challenges::Entity::delete_many()
+        .filter(mfa_challenges::Column::ExpiresAt.lt(now))
+        .exec(&state.conn)
+        .await?;
+
+    let webauthn_challenges = webauthn_challenges::Entity::delete_many()
+        .filter(webauthn_challenges::Column::ExpiresAt.lt(now))
+        .exec(&state.conn)
+        .await?;
+
+    let reset_tokens = password_reset_tokens::Entity::delete_many()
+        .filter(password_reset_tokens::Column::ExpiresAt.lt(now))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(revocations
+        + challenges.rows_affected
+        + webauthn_challenges.rows_affected
+        + reset_tokens.rows_affected)
+}
+
+/// 按 `maintenance.audit_log_retention_days` 删除过期审计日志，0 表示永久保留
+async fn purge_audit_logs(state: AppState) -> Result<u64> {
+    let retention_days = state.config.maintenance.audit_log_retention_days;
+    if retention_days == 0 {
+        return Ok(0);
+    }
+
+    // 配置校验已限制上限，这里不会溢出
+    let cutoff = chrono::Local::now() - chrono::Duration::days(retention_days as i64);
+    let result = audit_logs::Entity::delete_many()
+        .filter(audit_logs::Column::CreatedAt.lt(cutoff))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(result.rows_affected)
+}
+
+/// 锁定到期且此后 `login_throttle.reset_after_secs` 内没有新失败的账户，清零失败计数
+async fn reset_expired_locks(state: AppState) -> Result<u64> {
+    let reset_after = state.login_throttle.config().reset_after_secs as i64;
+    let cutoff = chrono::Local::now() - chrono::Duration::seconds(reset_after);
+
+    let result = admins::Entity::update_many()
+        .col_expr(admins::Column::LoginAttempts, Expr::value(0))
+        .col_expr(
+            admins::Column::LockedUntil,
+            Expr::value(Option::<chrono::DateTime<chrono::FixedOffset>>::None),
+        )
+        .filter(admins::Column::LockedUntil.lt(cutoff))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(result.rows_affected)
+}
+
+/// 后台维护调度器，`shutdown` 后等待正在执行的任务结束
+pub struct MaintenanceScheduler {
+    shutdown_tx: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl MaintenanceScheduler {
+    pub fn start(state: AppState) -> Self {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let handles = jobs(&state.config.maintenance)
+            .into_iter()
+            .filter_map(|job| {
+                let interval_secs = job.interval_secs;
+                if interval_secs == 0 {
+                    info!("维护任务 {} 已禁用", job.name);
+                    return None;
+                }
+
+                Some(tokio::spawn(run_job(
+                    job,
+                    Duration::from_secs(interval_secs),
+                    state.clone(),
+                    shutdown_rx.clone(),
+                )))
+            })
+            .collect();
+
+        Self {
+            shutdown_tx,
+            handles,
+        }
+    }
+
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        for handle in self.handles {
+            if let Err(e) = handle.await {
+                warn!("维护任务退出异常: {}", e);
+            }
+        }
+        info!("后台维护任务已停止");
+    }
+}
+
+async fn run_job(
+    job: MaintenanceJob,
+    interval: Duration,
+    state: AppState,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = shutdown_rx.changed() => break,
+        }
+
+        match (job.run)(state.clone()).await {
+            Ok(0) => {}
+            Ok(affected) => info!("维护任务 {} 完成，清理 {} 条记录", job.name, affected),
+            Err(e) => warn!("维护任务 {} 执行失败: {}", job.name, e),
+        }
+    }
+}
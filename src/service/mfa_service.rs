@@ -0,0 +1,253 @@
+use anyhow::{Result, anyhow};
+use rand::Rng;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+};
+use totp_rs::{Secret, TOTP};
+
+use crate::config::MfaConfig;
+use crate::dto::TwoFaMethod;
+use crate::entities::{admin_backup_codes, admins, mfa_challenges};
+use crate::utils::{
+    PasswordHashConfig, generate_opaque_token, hash_password, sha256_hex, verify_password,
+};
+
+const BACKUP_CODE_COUNT: usize = 10;
+
+pub fn build_totp(secret: Vec<u8>, account_name: String) -> Result<TOTP> {
+    TOTP::new(
+        totp_rs::Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some("Guardian".to_string()),
+        account_name,
+    )
+    .map_err(|e| anyhow!("生成TOTP失败: {}", e))
+}
+
+pub fn verify_totp_code(encoded_secret: &str, account_name: String, code: &str) -> Result<bool> {
+    let secret = Secret::Encoded(encoded_secret.to_string());
+    let totp = build_totp(secret.to_bytes()?, account_name)?;
+
+    totp.check_current(code)
+        .map_err(|e| anyhow!("验证2FA失败: {}", e))
+}
+
+/// 未显式指定验证方式时，6 位数字视为 TOTP，其余视为备用码
+pub fn detect_two_fa_method(code: &str) -> TwoFaMethod {
+    let code = code.trim();
+    if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+        TwoFaMethod::Totp
+    } else {
+        TwoFaMethod::BackupCode
+    }
+}
+
+pub struct IssuedMfaChallenge {
+    pub token: String,
+    pub expires_in: i64,
+}
+
+/// 密码验证通过后签发一次性 2FA 挑战令牌，数据库只保存其 SHA-256 摘要
+pub async fn create_mfa_challenge<C: ConnectionTrait>(
+    conn: &C,
+    config: &MfaConfig,
+    admin_id: uuid::Uuid,
+) -> Result<IssuedMfaChallenge> {
+    let now = chrono::Local::now();
+
+    mfa_challenges::Entity::delete_many()
+        .filter(mfa_challenges::Column::AdminId.eq(admin_id))
+        .filter(mfa_challenges::Column::ExpiresAt.lte(now))
+        .exec(conn)
+        .await?;
+
+    let token = generate_opaque_token();
+    let expires_in = config.challenge_ttl_secs as i64;
+
+    let challenge = mfa_challenges::ActiveModel {
+        id: Set(uuid::Uuid::now_v7()),
+        admin_id: Set(admin_id),
+        challenge_hash: Set(sha256_hex(&token)),
+        attempts: Set(0),
+        expires_at: Set((now + chrono::Duration::seconds(expires_in)).into()),
+        created_at: Set(Some(now.into())),
+    };
+    challenge.insert(conn).await?;
+
+    Ok(IssuedMfaChallenge { token, expires_in })
+}
+
+/// 登记一次挑战尝试。挑战不存在、已过期或尝试次数耗尽时返回 `None`。
+///
+/// 尝试次数在校验验证码之前原子递增，避免并发请求绕过次数限制。
+pub async fn start_mfa_attempt<C: ConnectionTrait>(
+    conn: &C,
+    config: &MfaConfig,
+    token: &str,
+) -> Result<Option<mfa_challenges::Model>> {
+    let challenge = mfa_challenges::Entity::find()
+        .filter(mfa_challenges::Column::ChallengeHash.eq(sha256_hex(token)))
+        .filter(mfa_challenges::Column::ExpiresAt.gt(chrono::Local::now()))
+        .one(conn)
+        .await?;
+
+    let Some(mut challenge) = challenge else {
+        return Ok(None);
+    };
+
+    let updated = mfa_challenges::Entity::update_many()
+        .col_expr(
+            mfa_challenges::Column::Attempts,
+            Expr::col(mfa_challenges::Column::Attempts).add(1),
+        )
+        .filter(mfa_challenges::Column::Id.eq(challenge.id))
+        .filter(mfa_challenges::Column::Attempts.lt(config.challenge_max_attempts))
+        .exec(conn)
+        .await?;
+
+    if updated.rows_affected == 0 {
+        discard_mfa_challenge(conn, challenge.id).await?;
+        return Ok(None);
+    }
+
+    challenge.attempts += 1;
+    Ok(Some(challenge))
+}
+
+pub fn mfa_attempts_exhausted(config: &MfaConfig, challenge: &mfa_challenges::Model) -> bool {
+    challenge.attempts >= config.challenge_max_attempts
+}
+
+pub async fn discard_mfa_challenge<C: ConnectionTrait>(conn: &C, id: uuid::Uuid) -> Result<()> {
+    mfa_challenges::Entity::delete_by_id(id).exec(conn).await?;
+    Ok(())
+}
+
+pub async fn available_mfa_methods<C: ConnectionTrait>(
+    conn: &C,
+    admin: &admins::Model,
+) -> Result<Vec<TwoFaMethod>> {
+    let mut methods = Vec::new();
+
+    if admin.two_fa_secret.is_some() {
+        methods.push(TwoFaMethod::Totp);
+    }
+
+    let unused_backup_codes = admin_backup_codes::Entity::find()
+        .filter(admin_backup_codes::Column::AdminId.eq(admin.id))
+        .filter(admin_backup_codes::Column::UsedAt.is_null())
+        .count(conn)
+        .await?;
+
+    if unused_backup_codes > 0 {
+        methods.push(TwoFaMethod::BackupCode);
+    }
+
+    Ok(methods)
+}
+
+pub fn generate_backup_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| format!("{:08}", rng.gen_range(0..100_000_000u32)))
+        .collect()
+}
+
+/// 备用码熵较低，使用 Argon2 哈希存储，旧的备用码全部作废
+pub async fn replace_backup_codes<C: ConnectionTrait>(
+    conn: &C,
+    hash_config: &PasswordHashConfig,
+    admin_id: uuid::Uuid,
+    codes: &[String],
+) -> Result<()> {
+    delete_backup_codes(conn, admin_id).await?;
+
+    for code in codes {
+        let backup_code = admin_backup_codes::ActiveModel {
+            id: Set(uuid::Uuid::now_v7()),
+            admin_id: Set(admin_id),
+            code_hash: Set(hash_password(hash_config, code)),
+            used_at: Set(None),
+            created_at: Set(Some(chrono::Local::now().into())),
+        };
+        backup_code.insert(conn).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_backup_codes<C: ConnectionTrait>(conn: &C, admin_id: uuid::Uuid) -> Result<()> {
+    admin_backup_codes::Entity::delete_many()
+        .filter(admin_backup_codes::Column::AdminId.eq(admin_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// 校验并消费一个备用码，每个备用码只能使用一次
+pub async fn consume_backup_code<C: ConnectionTrait>(
+    conn: &C,
+    hash_config: &PasswordHashConfig,
+    admin_id: uuid::Uuid,
+    code: &str,
+) -> Result<bool> {
+    let normalized: String = code.chars().filter(|c| c.is_ascii_digit()).collect();
+    if normalized.is_empty() {
+        return Ok(false);
+    }
+
+    let candidates = admin_backup_codes::Entity::find()
+        .filter(admin_backup_codes::Column::AdminId.eq(admin_id))
+        .filter(admin_backup_codes::Column::UsedAt.is_null())
+        .all(conn)
+        .await?;
+
+    let Some(matched) = candidates
+        .into_iter()
+        .find(|candidate| verify_password(hash_config, &normalized, &candidate.code_hash))
+    else {
+        return Ok(false);
+    };
+
+    let updated = admin_backup_codes::Entity::update_many()
+        .col_expr(
+            admin_backup_codes::Column::UsedAt,
+            Expr::value(Some(chrono::DateTime::<chrono::FixedOffset>::from(
+                chrono::Local::now(),
+            ))),
+        )
+        .filter(admin_backup_codes::Column::Id.eq(matched.id))
+        .filter(admin_backup_codes::Column::UsedAt.is_null())
+        .exec(conn)
+        .await?;
+
+    Ok(updated.rows_affected == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_two_fa_method() {
+        assert_eq!(detect_two_fa_method("123456"), TwoFaMethod::Totp);
+        assert_eq!(detect_two_fa_method(" 123456 "), TwoFaMethod::Totp);
+        assert_eq!(detect_two_fa_method("12345678"), TwoFaMethod::BackupCode);
+        assert_eq!(detect_two_fa_method("1234-5678"), TwoFaMethod::BackupCode);
+    }
+
+    #[test]
+    fn test_generate_backup_codes() {
+        let codes = generate_backup_codes();
+        assert_eq!(codes.len(), BACKUP_CODE_COUNT);
+        assert!(
+            codes
+                .iter()
+                .all(|c| c.len() == 8 && c.chars().all(|ch| ch.is_ascii_digit()))
+        );
+    }
+}
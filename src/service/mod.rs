@@ -1,16 +1,23 @@
 pub mod admin_service;
+pub mod audit_service;
 pub mod auth_service;
+pub mod builtin_permission_service;
+pub mod health_service;
 pub mod init;
+pub mod ip_rule_service;
+pub mod login_throttle_service;
+pub mod maintenance_service;
+pub mod mfa_service;
+pub mod password_policy_service;
+pub mod password_reset_service;
 pub mod permission_check_service;
 pub mod permission_check_service_tests;
 pub mod permission_service;
+pub mod rbac_service;
 pub mod role_service;
 pub mod system_info_service;
+pub mod webauthn_service;
 
-pub use admin_service::*;
 pub use auth_service::*;
-pub use init::*;
 pub use permission_check_service::*;
-pub use permission_service::*;
-pub use role_service::*;
 pub use system_info_service::*;
@@ -0,0 +1,379 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use serde::Deserialize;
+
+use crate::entities::{admins, password_history};
+use crate::error::Result;
+use crate::i18n::t;
+use crate::router::AppState;
+use crate::utils::verify_password;
+
+/// 常见弱密码，比较时忽略大小写
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "12345",
+    "1234567",
+    "111111",
+    "000000",
+    "666666",
+    "888888",
+    "123123",
+    "654321",
+    "112233",
+    "abc123",
+    "abc12345",
+    "a123456",
+    "a12345678",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "1q2w3e4r",
+    "1qaz2wsx",
+    "zaq12wsx",
+    "asdfghjkl",
+    "password",
+    "password1",
+    "password123",
+    "passw0rd",
+    "p@ssw0rd",
+    "p@ssword",
+    "admin",
+    "admin123",
+    "admin@123",
+    "administrator",
+    "root",
+    "root123",
+    "guardian",
+    "guardian123",
+    "welcome",
+    "welcome1",
+    "iloveyou",
+    "letmein",
+    "monkey",
+    "dragon",
+    "sunshine",
+    "football",
+    "baseball",
+    "superman",
+    "changeme",
+    "woaini1314",
+];
+
+/// 用户名短于该长度时不检查密码是否包含用户名，否则几乎所有密码都会被拒绝
+const MIN_USERNAME_CHECK_LEN: usize = 3;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// 不允许与最近 N 次使用过的密码相同，0 表示不限制
+    pub history_size: u64,
+    /// 密码最长使用天数，0 表示永不过期
+    pub max_age_days: u64,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: false,
+            history_size: 5,
+            max_age_days: 0,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// 校验密码强度，返回所有未满足的规则
+    pub fn validate(&self, password: &str, username: &str) -> Vec<PasswordViolation> {
+        let mut violations = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            violations.push(PasswordViolation::TooShort {
+                min: self.min_length,
+            });
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_ascii_uppercase()) {
+            violations.push(PasswordViolation::MissingUppercase);
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_ascii_lowercase()) {
+            violations.push(PasswordViolation::MissingLowercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PasswordViolation::MissingDigit);
+        }
+        if self.require_symbol
+            && !password
+                .chars()
+                .any(|c| !c.is_alphanumeric() && !c.is_whitespace())
+        {
+            violations.push(PasswordViolation::MissingSymbol);
+        }
+
+        let lowered = password.to_lowercase();
+        if COMMON_PASSWORDS.contains(&lowered.as_str()) {
+            violations.push(PasswordViolation::TooCommon);
+        }
+        if username.chars().count() >= MIN_USERNAME_CHECK_LEN
+            && lowered.contains(&username.to_lowercase())
+        {
+            violations.push(PasswordViolation::ContainsUsername);
+        }
+
+        violations
+    }
+
+    /// 生成一个满足当前策略的随机密码，用于命令行初始化/重置时不便手动输入的场景
+    pub fn generate(&self) -> String {
+        const UPPER: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+        const LOWER: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+        const DIGIT: &[u8] = b"23456789";
+        const SYMBOL: &[u8] = b"!@#$%^&*-_=+?";
+
+        let mut rng = rand::thread_rng();
+        let all = [UPPER, LOWER, DIGIT, SYMBOL].concat();
+        let mut chars: Vec<u8> = [UPPER, LOWER, DIGIT, SYMBOL]
+            .iter()
+            .map(|set| set[rng.gen_range(0..set.len())])
+            .collect();
+        while chars.len() < self.min_length.max(20) {
+            chars.push(all[rng.gen_range(0..all.len())]);
+        }
+        chars.shuffle(&mut rng);
+
+        String::from_utf8(chars).expect("字符集均为 ASCII")
+    }
+
+    pub fn is_expired(&self, admin: &admins::Model) -> bool {
+        if self.max_age_days == 0 {
+            return false;
+        }
+
+        // 未记录修改时间的旧账户不视为过期，避免启用策略后所有旧账户同时被要求改密
+        let Some(changed_at) = admin.password_changed_at else {
+            return false;
+        };
+
+        // 配置校验已限制上限，这里不会溢出
+        changed_at + chrono::Duration::days(self.max_age_days as i64) <= chrono::Local::now()
+    }
+}
+
+/// 未满足的密码规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordViolation {
+    TooShort { min: usize },
+    MissingUppercase,
+    MissingLowercase,
+    MissingDigit,
+    MissingSymbol,
+    TooCommon,
+    ContainsUsername,
+}
+
+impl PasswordViolation {
+    /// 按当前请求的语言渲染的提示信息
+    pub fn message(&self) -> String {
+        match self {
+            PasswordViolation::TooShort { min } => t!("password.too_short", min = min),
+            PasswordViolation::MissingUppercase => t!("password.require_uppercase"),
+            PasswordViolation::MissingLowercase => t!("password.require_lowercase"),
+            PasswordViolation::MissingDigit => t!("password.require_digit"),
+            PasswordViolation::MissingSymbol => t!("password.require_symbol"),
+            PasswordViolation::TooCommon => t!("password.too_common"),
+            PasswordViolation::ContainsUsername => t!("password.contains_username"),
+        }
+    }
+}
+
+/// 校验新密码：强度规则 + 最近 N 次密码不可复用。
+///
+/// 返回 `None` 表示通过，否则返回可直接展示给调用方的错误信息。
+pub async fn check_new_password(
+    state: &AppState,
+    admin: Option<&admins::Model>,
+    username: &str,
+    password: &str,
+) -> Result<Option<String>> {
+    let policy = &state.config.password_policy;
+    let hash_config = &state.config.password_hash;
+    let violations = policy.validate(password, username);
+    if !violations.is_empty() {
+        let messages: Vec<String> = violations.iter().map(PasswordViolation::message).collect();
+        return Ok(Some(messages.join(&t!("password.separator"))));
+    }
+
+    if let Some(admin) = admin
+        && policy.history_size > 0
+    {
+        if verify_password(hash_config, password, &admin.password_hash) {
+            return Ok(Some(t!("password.same_as_current")));
+        }
+
+        let recent = password_history::Entity::find()
+            .filter(password_history::Column::AdminId.eq(admin.id))
+            .order_by_desc(password_history::Column::CreatedAt)
+            .limit(policy.history_size)
+            .all(&state.conn)
+            .await?;
+
+        if recent
+            .iter()
+            .any(|entry| verify_password(hash_config, password, &entry.password_hash))
+        {
+            return Ok(Some(t!(
+                "password.recently_used",
+                count = policy.history_size
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+/// 记录一次密码变更，并清理超出保留数量的历史记录
+pub async fn record_password_history<C: ConnectionTrait>(
+    conn: &C,
+    policy: &PasswordPolicy,
+    admin_id: uuid::Uuid,
+    password_hash: &str,
+) -> Result<(), DbErr> {
+    let entry = password_history::ActiveModel {
+        id: Set(uuid::Uuid::now_v7()),
+        admin_id: Set(admin_id),
+        password_hash: Set(password_hash.to_string()),
+        created_at: Set(Some(chrono::Local::now().into())),
+    };
+    entry.insert(conn).await?;
+
+    let expired_ids: Vec<uuid::Uuid> = password_history::Entity::find()
+        .select_only()
+        .column(password_history::Column::Id)
+        .filter(password_history::Column::AdminId.eq(admin_id))
+        .order_by_desc(password_history::Column::CreatedAt)
+        .offset(policy.history_size.max(1))
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    if !expired_ids.is_empty() {
+        password_history::Entity::delete_many()
+            .filter(password_history::Column::Id.is_in(expired_ids))
+            .exec(conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_password() {
+        let policy = PasswordPolicy {
+            min_length: 24,
+            require_symbol: true,
+            ..Default::default()
+        };
+
+        let password = policy.generate();
+        assert_eq!(password.len(), 24);
+        assert!(policy.validate(&password, "admin").is_empty());
+        assert_ne!(password, policy.generate());
+    }
+
+    #[test]
+    fn test_validate_password() {
+        let policy = PasswordPolicy::default();
+
+        assert!(policy.validate("Str0ngPassw", "admin").is_empty());
+        assert_eq!(
+            policy.validate("Ab1", "admin"),
+            [PasswordViolation::TooShort { min: 8 }]
+        );
+        assert_eq!(
+            policy.validate("alllowercase1", "admin"),
+            [PasswordViolation::MissingUppercase]
+        );
+        assert!(
+            policy
+                .validate("Password1", "admin")
+                .contains(&PasswordViolation::TooCommon)
+        );
+        assert!(
+            policy
+                .validate("Admin2024x", "admin")
+                .contains(&PasswordViolation::ContainsUsername)
+        );
+    }
+
+    #[test]
+    fn test_short_username_not_checked() {
+        let policy = PasswordPolicy::default();
+
+        // 过短的用户名几乎出现在所有密码中，不做包含检查
+        assert!(policy.validate("Str0ngPassw", "a").is_empty());
+        assert!(policy.validate("Str0ngPassw", "St").is_empty());
+        assert_eq!(
+            policy.validate("Str0ngPassw", "str"),
+            [PasswordViolation::ContainsUsername]
+        );
+    }
+
+    #[test]
+    fn test_password_expired() {
+        let policy = PasswordPolicy {
+            max_age_days: 90,
+            ..PasswordPolicy::default()
+        };
+        let now = chrono::Local::now();
+        let mut admin = admins::Model {
+            id: uuid::Uuid::now_v7(),
+            username: "admin".to_string(),
+            email: None,
+            password_hash: String::new(),
+            two_fa_secret: None,
+            is_super_admin: Some(false),
+            status: Some(1),
+            last_login_at: None,
+            login_attempts: Some(0),
+            locked_until: None,
+            password_changed_at: Some((now - chrono::Duration::days(91)).into()),
+            must_change_password: Some(false),
+            locale: None,
+            created_at: Some(now.into()),
+            updated_at: Some(now.into()),
+        };
+        assert!(policy.is_expired(&admin));
+
+        admin.password_changed_at = Some((now - chrono::Duration::days(1)).into());
+        assert!(!policy.is_expired(&admin));
+
+        admin.password_changed_at = None;
+        admin.created_at = Some((now - chrono::Duration::days(365)).into());
+        assert!(!policy.is_expired(&admin));
+
+        let never_expire = PasswordPolicy {
+            max_age_days: 0,
+            ..PasswordPolicy::default()
+        };
+        admin.password_changed_at = Some((now - chrono::Duration::days(365)).into());
+        assert!(!never_expire.is_expired(&admin));
+    }
+}
@@ -0,0 +1,216 @@
+use std::net::IpAddr;
+
+use log::warn;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use serde_json::json;
+
+use crate::dto::{PasswordResetConfirmRequest, PasswordResetRequest};
+use crate::entities::{admins, password_reset_tokens};
+use crate::error::{GuardianError, Result};
+use crate::i18n::{current_locale, t, translate};
+use crate::response::{Response, ResponseCode};
+use crate::router::AppState;
+use crate::service::admin_service::normalize_email;
+use crate::service::audit_service::{AuditEntry, record_audit_quietly};
+use crate::service::auth_service::update_password;
+use crate::service::password_policy_service::check_new_password;
+use crate::trace::spawn_traced;
+use crate::utils::mail::{MailMessage, Mailer};
+use crate::utils::{generate_opaque_token, sha256_hex};
+
+const RESET_REQUEST_RESOURCE: &str = "/auth/password-reset/request";
+const RESET_CONFIRM_RESOURCE: &str = "/auth/password-reset/confirm";
+
+/// 申请重置密码。无论邮箱是否存在都立即返回相同结果，查找账户和发信在后台完成，
+/// 避免通过响应内容或响应时间枚举已注册邮箱。未配置邮件发送通道时直接拒绝。
+pub async fn request_password_reset_service(
+    state: AppState,
+    payload: PasswordResetRequest,
+    client_ip: IpAddr,
+    user_agent: Option<String>,
+) -> Result<Response<()>> {
+    let Some(mailer) = state.mailer.clone() else {
+        return Err(GuardianError::Code(
+            ResponseCode::ServiceUnavailable,
+            Some(t!("password_reset.mail_disabled")),
+        ));
+    };
+
+    if let Some(email) = normalize_email(&payload.email) {
+        spawn_traced(async move {
+            if let Err(e) =
+                send_reset_email(&state, mailer.as_ref(), &email, client_ip, user_agent).await
+            {
+                warn!("发送重置密码邮件失败: {}", e);
+            }
+        });
+    }
+
+    Ok(Response::ok_msg(Some(t!("password_reset.requested"))))
+}
+
+async fn send_reset_email(
+    state: &AppState,
+    mailer: &dyn Mailer,
+    email: &str,
+    client_ip: IpAddr,
+    user_agent: Option<String>,
+) -> Result<()> {
+    let admin = admins::Entity::find()
+        .filter(admins::Column::Email.eq(email))
+        .one(&state.conn)
+        .await?;
+
+    let Some(admin) = admin.filter(|admin| admin.status.is_none_or(|status| status == 1)) else {
+        record_audit_quietly(
+            &state.conn,
+            AuditEntry {
+                action: "password_reset_requested".to_string(),
+                resource: RESET_REQUEST_RESOURCE.to_string(),
+                method: "POST".to_string(),
+                params: Some(json!({ "email": email })),
+                result: Some(json!({ "matched": false })),
+                status_code: 200,
+                ip_address: Some(client_ip.to_string()),
+                user_agent,
+                ..Default::default()
+            },
+        )
+        .await;
+        return Ok(());
+    };
+
+    // 同一账户只保留最新的重置链接
+    password_reset_tokens::Entity::delete_many()
+        .filter(password_reset_tokens::Column::AdminId.eq(admin.id))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .exec(&state.conn)
+        .await?;
+
+    let token = generate_opaque_token();
+    let reset = &state.config.password_reset;
+    let ttl = reset.token_ttl_secs as i64;
+    let now = chrono::Local::now();
+
+    let reset_token = password_reset_tokens::ActiveModel {
+        id: Set(uuid::Uuid::now_v7()),
+        admin_id: Set(admin.id),
+        token_hash: Set(sha256_hex(&token)),
+        expires_at: Set((now + chrono::Duration::seconds(ttl)).into()),
+        used_at: Set(None),
+        created_at: Set(Some(now.into())),
+    };
+    reset_token.insert(&state.conn).await?;
+
+    // 邮件优先使用管理员的偏好语言
+    let locale = admin
+        .locale
+        .as_deref()
+        .and_then(|tag| tag.parse().ok())
+        .unwrap_or_else(current_locale);
+    mailer
+        .send(MailMessage {
+            to: email.to_string(),
+            subject: translate(locale, "password_reset.mail_subject", &[]),
+            body: translate(
+                locale,
+                "password_reset.mail_body",
+                &[
+                    ("username", admin.username.clone()),
+                    ("minutes", (ttl / 60).to_string()),
+                    ("link", reset.url.replace("{token}", &token)),
+                ],
+            ),
+        })
+        .await?;
+
+    record_audit_quietly(
+        &state.conn,
+        AuditEntry {
+            admin_id: Some(admin.id),
+            username: Some(admin.username),
+            action: "password_reset_requested".to_string(),
+            resource: RESET_REQUEST_RESOURCE.to_string(),
+            method: "POST".to_string(),
+            params: Some(json!({ "email": email })),
+            result: Some(json!({ "matched": true })),
+            status_code: 200,
+            ip_address: Some(client_ip.to_string()),
+            user_agent,
+        },
+    )
+    .await;
+
+    Ok(())
+}
+
+pub async fn confirm_password_reset_service(
+    state: AppState,
+    payload: PasswordResetConfirmRequest,
+    client_ip: IpAddr,
+    user_agent: Option<String>,
+) -> Result<Response<()>> {
+    let reset_token = password_reset_tokens::Entity::find()
+        .filter(password_reset_tokens::Column::TokenHash.eq(sha256_hex(&payload.token)))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .filter(password_reset_tokens::Column::ExpiresAt.gt(chrono::Local::now()))
+        .one(&state.conn)
+        .await?;
+
+    let Some(reset_token) = reset_token else {
+        return Err(GuardianError::Code(ResponseCode::ResetTokenInvalid, None));
+    };
+
+    let admin = admins::Entity::find_by_id(reset_token.admin_id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    if admin.status.is_some_and(|status| status != 1) {
+        return Err(GuardianError::Forbidden(t!("auth.account_disabled")));
+    }
+
+    if let Some(msg) =
+        check_new_password(&state, Some(&admin), &admin.username, &payload.new_password).await?
+    {
+        return Err(GuardianError::Validation(msg));
+    }
+
+    // 原子地标记为已使用，并发提交同一链接时只有一个请求能成功
+    let consumed = password_reset_tokens::Entity::update_many()
+        .col_expr(
+            password_reset_tokens::Column::UsedAt,
+            Expr::value(Some(chrono::DateTime::<chrono::FixedOffset>::from(
+                chrono::Local::now(),
+            ))),
+        )
+        .filter(password_reset_tokens::Column::Id.eq(reset_token.id))
+        .filter(password_reset_tokens::Column::UsedAt.is_null())
+        .exec(&state.conn)
+        .await?;
+
+    if consumed.rows_affected == 0 {
+        return Err(GuardianError::Code(ResponseCode::ResetTokenInvalid, None));
+    }
+
+    let admin = update_password(&state, admin, &payload.new_password).await?;
+
+    record_audit_quietly(
+        &state.conn,
+        AuditEntry {
+            admin_id: Some(admin.id),
+            username: Some(admin.username),
+            action: "password_reset_completed".to_string(),
+            resource: RESET_CONFIRM_RESOURCE.to_string(),
+            method: "POST".to_string(),
+            status_code: 200,
+            ip_address: Some(client_ip.to_string()),
+            user_agent,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    Ok(Response::ok_msg(Some(t!("auth.password_reset"))))
+}
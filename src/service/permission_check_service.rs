@@ -1,8 +1,11 @@
-use anyhow::{Ok, Result};
-use log::info;
+use std::time::Instant;
+
+use log::debug;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
-use crate::entities::{admin_roles, permissions, prelude::*};
+use crate::entities::{admin_roles, prelude::*};
+use crate::error::Result;
+use crate::metrics::record_permission_check;
 use crate::middleware::auth::AuthContext;
 use crate::router::AppState;
 
@@ -12,13 +15,26 @@ pub async fn check_api_permission(
     method: String,
     path: String,
 ) -> Result<bool> {
-    info!(
-        "=== 权限检查 ===\n  Admin ID: {}\n  Username: {}\n  Is Super Admin: {}\n  Method: {}\n  Path: {}",
-        auth_context.admin_id, auth_context.username, auth_context.is_super_admin, method, path
+    let started = Instant::now();
+    let allowed = has_api_permission(state, &auth_context, &method, &path).await?;
+    record_permission_check(allowed, started.elapsed());
+    debug!(
+        "权限检查 {} {}: {}",
+        method,
+        path,
+        if allowed { "允许" } else { "拒绝" }
     );
 
+    Ok(allowed)
+}
+
+async fn has_api_permission(
+    state: AppState,
+    auth_context: &AuthContext,
+    method: &str,
+    path: &str,
+) -> Result<bool> {
     if auth_context.is_super_admin {
-        info!("  -> 超级管理员，跳过权限检查，返回 true");
         return Ok(true);
     }
 
@@ -63,13 +79,9 @@ pub async fn check_api_permission(
                         true
                     } else {
                         let pattern = p.replace("*", ".*").replace("{id}", "[^/]+");
-                        let regex_result = regex::Regex::new(&format!("^{}$", pattern));
-                        if regex_result.is_ok() {
-                            let regex = regex_result.unwrap();
-                            regex.is_match(&path)
-                        } else {
-                            false
-                        }
+                        regex::Regex::new(&format!("^{}$", pattern))
+                            .map(|regex| regex.is_match(path))
+                            .unwrap_or(false)
                     }
                 }
                 None => false,
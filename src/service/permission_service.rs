@@ -1,4 +1,3 @@
-use anyhow::{Result, anyhow};
 use sea_orm::ActiveValue::NotSet;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter,
@@ -9,7 +8,9 @@ use crate::dto::{
     CreatePermissionRequest, PermissionListQuery, PermissionListResponse, PermissionResponse,
     PermissionTreeResponse, UpdatePermissionRequest,
 };
-use crate::entities::{permissions, prelude::*};
+use crate::entities::permissions;
+use crate::error::{GuardianError, Result};
+use crate::i18n::t;
 use crate::response::Response;
 use crate::router::AppState;
 
@@ -19,7 +20,6 @@ pub async fn list_permission_service(
 ) -> Result<Response<PermissionListResponse>> {
     let page = query.page.unwrap_or(1);
     let page_size = query.page_size.unwrap_or(20);
-    let offset = (page - 1) * page_size;
 
     let mut select = permissions::Entity::find();
 
@@ -42,9 +42,9 @@ pub async fn list_permission_service(
     let total = paginator
         .num_items_and_pages()
         .await
-        .map(|(items)| items.number_of_items)
+        .map(|items| items.number_of_items)
         .unwrap_or(0);
-    let permissions_list = paginator.fetch_page(page - 1).await?;
+    let permissions_list = paginator.fetch_page(page.saturating_sub(1)).await?;
 
     let list: Vec<PermissionResponse> = permissions_list
         .into_iter()
@@ -125,7 +125,7 @@ pub async fn get_permission_service(
     let permission = permissions::Entity::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("权限不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("permission.not_found")))?;
 
     Ok(Response::ok_data(PermissionResponse {
         id: permission.id,
@@ -159,7 +159,7 @@ pub async fn create_permission_service(
         .await?;
 
     if existing.is_some() {
-        return Ok(Response::failed("权限代码已存在".to_string()));
+        return Err(GuardianError::Conflict(t!("permission.code_taken")));
     }
 
     let permission = permissions::ActiveModel {
@@ -209,10 +209,10 @@ pub async fn update_permission_service(
     let permission = permissions::Entity::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("权限不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("permission.not_found")))?;
 
     if permission.is_system.unwrap_or(false) {
-        return Ok(Response::failed("系统内置权限不可修改".to_string()));
+        return Err(GuardianError::Forbidden(t!("permission.system_readonly")));
     }
 
     let mut perm_model: permissions::ActiveModel = permission.into_active_model();
@@ -274,14 +274,16 @@ pub async fn delete_permission_service(state: AppState, id: uuid::Uuid) -> Resul
     let permission = permissions::Entity::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("权限不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("permission.not_found")))?;
 
     if permission.is_system.unwrap_or(false) {
-        return Ok(Response::failed("系统内置权限不可删除".to_string()));
+        return Err(GuardianError::Forbidden(t!(
+            "permission.system_undeletable"
+        )));
     }
 
     let perm_model: permissions::ActiveModel = permission.into_active_model();
     perm_model.delete(&state.conn).await?;
 
-    Ok(Response::ok_msg(Some("删除成功".to_string())))
+    Ok(Response::ok_msg(Some(t!("common.deleted"))))
 }
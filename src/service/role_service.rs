@@ -1,7 +1,8 @@
-use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter,
-    QueryOrder, QuerySelect, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 
 use crate::dto::{
@@ -9,6 +10,8 @@ use crate::dto::{
     RoleResponse, UpdateRoleRequest,
 };
 use crate::entities::{permissions, prelude::*, role_permissions, roles};
+use crate::error::{GuardianError, Result, transaction_error};
+use crate::i18n::t;
 use crate::response::Response;
 use crate::router::AppState;
 
@@ -18,7 +21,7 @@ pub async fn list_role_service(
 ) -> Result<Response<RoleListResponse>> {
     let page = query.page.unwrap_or(1);
     let page_size = query.page_size.unwrap_or(20);
-    let offset = (page - 1) * page_size;
+    let offset = page.saturating_sub(1).saturating_mul(page_size);
 
     let mut select = Roles::find();
 
@@ -73,7 +76,7 @@ pub async fn get_role_service(
     let role = Roles::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
 
     let role_permissions_list = role_permissions::Entity::find()
         .filter(role_permissions::Column::RoleId.eq(id))
@@ -109,6 +112,35 @@ pub async fn get_role_service(
     }))
 }
 
+/// 角色写入涉及的唯一约束：并发请求抢先占用了角色代码，或权限 ID 有重复
+const ROLE_CONSTRAINTS: &[(&str, &str)] = &[
+    ("guardian_roles_code_key", "role.code_taken"),
+    (
+        "guardian_role_permissions_pkey",
+        "role.duplicate_permission",
+    ),
+];
+
+/// 关联表不使用外键，写入前确认权限都存在，否则返回 422
+async fn ensure_permissions_exist<C: ConnectionTrait>(
+    conn: &C,
+    permission_ids: &[uuid::Uuid],
+) -> Result<()> {
+    let ids: HashSet<uuid::Uuid> = permission_ids.iter().copied().collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let found = Permissions::find()
+        .filter(permissions::Column::Id.is_in(ids.iter().copied()))
+        .count(conn)
+        .await?;
+    if found < ids.len() as u64 {
+        return Err(GuardianError::Validation(t!("role.permission_not_found")));
+    }
+    Ok(())
+}
+
 pub async fn create_role_service(
     state: AppState,
     payload: CreateRoleRequest,
@@ -119,7 +151,10 @@ pub async fn create_role_service(
         .await?;
 
     if existing.is_some() {
-        return Ok(Response::failed("角色代码已存在".to_string()));
+        return Err(GuardianError::Conflict(t!("role.code_taken")));
+    }
+    if let Some(permission_ids) = &payload.permission_ids {
+        ensure_permissions_exist(&state.conn, permission_ids).await?;
     }
 
     let payload_clone = payload.clone();
@@ -155,16 +190,16 @@ pub async fn create_role_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("创建角色失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ROLE_CONSTRAINTS))?;
 
     let role = roles::Entity::find()
         .filter(roles::Column::Code.eq(&payload.code))
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色创建后查询失败"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
 
     Ok(Response::ok(
-        Some("创建成功".to_string()),
+        Some(t!("common.created")),
         RoleResponse {
             id: role.id,
             code: role.code,
@@ -191,10 +226,13 @@ pub async fn update_role_service(
     let role = Roles::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
 
     if role.is_system.unwrap_or(false) {
-        return Ok(Response::failed("系统内置角色不可修改".to_string()));
+        return Err(GuardianError::Forbidden(t!("role.system_readonly")));
+    }
+    if let Some(permission_ids) = &payload.permission_ids {
+        ensure_permissions_exist(&state.conn, permission_ids).await?;
     }
 
     state
@@ -234,15 +272,15 @@ pub async fn update_role_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("更新角色失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ROLE_CONSTRAINTS))?;
 
     let role = Roles::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色更新后查询失败"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
 
     Ok(Response::ok(
-        Some("更新成功".to_string()),
+        Some(t!("common.updated")),
         RoleResponse {
             id: role.id,
             code: role.code,
@@ -265,10 +303,10 @@ pub async fn delete_role_service(state: AppState, id: uuid::Uuid) -> Result<Resp
     let role = Roles::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
 
     if role.is_system.unwrap_or(false) {
-        return Ok(Response::failed("系统内置角色不可删除".to_string()));
+        return Err(GuardianError::Forbidden(t!("role.system_undeletable")));
     }
 
     role_permissions::Entity::delete_many()
@@ -279,7 +317,7 @@ pub async fn delete_role_service(state: AppState, id: uuid::Uuid) -> Result<Resp
     let role_model: roles::ActiveModel = role.into_active_model();
     role_model.delete(&state.conn).await?;
 
-    Ok(Response::ok_msg(Some("删除成功".to_string())))
+    Ok(Response::ok_msg(Some(t!("common.deleted"))))
 }
 
 pub async fn assign_permissions_service(
@@ -287,10 +325,11 @@ pub async fn assign_permissions_service(
     id: uuid::Uuid,
     permission_ids: Vec<uuid::Uuid>,
 ) -> Result<Response<()>> {
-    let role = Roles::find_by_id(id)
+    Roles::find_by_id(id)
         .one(&state.conn)
         .await?
-        .ok_or_else(|| anyhow!("角色不存在"))?;
+        .ok_or_else(|| GuardianError::NotFound(t!("role.not_found")))?;
+    ensure_permissions_exist(&state.conn, &permission_ids).await?;
 
     state
         .conn
@@ -314,7 +353,7 @@ pub async fn assign_permissions_service(
             })
         })
         .await
-        .map_err(|e| anyhow!("分配权限失败: {}", e))?;
+        .map_err(|e| transaction_error(e, ROLE_CONSTRAINTS))?;
 
-    Ok(Response::ok_msg(Some("权限分配成功".to_string())))
+    Ok(Response::ok_msg(Some(t!("role.permissions_assigned"))))
 }
@@ -1,8 +1,8 @@
-use anyhow::Result;
 use sea_orm::{EntityTrait, QueryOrder, QuerySelect};
 
 use crate::dto::{SystemInfoQuery, SystemInfoResponse};
 use crate::entities::guardian_systeminfo;
+use crate::error::Result;
 use crate::response::Response;
 use crate::router::AppState;
 
@@ -0,0 +1,291 @@
+use log::warn;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+    SqlErr,
+};
+use uuid::Uuid;
+
+use crate::dto::{
+    WebAuthnAssertionOptions, WebAuthnAssertionRequest, WebAuthnCredentialResponse,
+    WebAuthnRegisterOptionsResponse, WebAuthnRegisterRequest,
+};
+use crate::entities::{admins, webauthn_challenges, webauthn_credentials};
+use crate::error::{GuardianError, Result};
+use crate::i18n::t;
+use crate::middleware::auth::AuthContext;
+use crate::response::Response;
+use crate::router::AppState;
+use crate::utils::generate_opaque_token;
+use crate::utils::webauthn::{
+    Assertion, SUPPORTED_ALGORITHMS, decode_base64url, encode_base64url, verify_assertion,
+    verify_registration,
+};
+
+fn ensure_enabled(state: &AppState) -> Result<()> {
+    if !state.config.webauthn.enabled() {
+        return Err(GuardianError::BadRequest(t!("webauthn.not_enabled")));
+    }
+    Ok(())
+}
+
+/// 浏览器等待用户操作认证器的时长，与挑战有效期一致
+fn timeout_ms(state: &AppState) -> u64 {
+    state.config.mfa.challenge_ttl_secs * 1000
+}
+
+/// 签发注册挑战，每个管理员同时只保留一个未完成的注册
+pub async fn register_options_service(
+    state: AppState,
+    auth_context: AuthContext,
+) -> Result<Response<WebAuthnRegisterOptionsResponse>> {
+    ensure_enabled(&state)?;
+
+    let admin = admins::Entity::find_by_id(auth_context.admin_id)
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::NotFound(t!("admin.not_found")))?;
+
+    let challenge = generate_opaque_token();
+    let now = chrono::Local::now();
+    let expires_at = now + chrono::Duration::seconds(state.config.mfa.challenge_ttl_secs as i64);
+
+    webauthn_challenges::Entity::delete_many()
+        .filter(webauthn_challenges::Column::AdminId.eq(admin.id))
+        .exec(&state.conn)
+        .await?;
+    webauthn_challenges::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        admin_id: Set(admin.id),
+        challenge: Set(challenge.clone()),
+        expires_at: Set(expires_at.into()),
+        created_at: Set(Some(now.into())),
+    }
+    .insert(&state.conn)
+    .await?;
+
+    let config = &state.config.webauthn;
+    Ok(Response::ok_data(WebAuthnRegisterOptionsResponse {
+        challenge,
+        rp_id: config.rp_id.clone(),
+        rp_name: config.rp_name.clone(),
+        user_id: encode_base64url(admin.id.as_bytes()),
+        user_name: admin.username,
+        algorithms: SUPPORTED_ALGORITHMS.to_vec(),
+        exclude_credentials: credential_ids(&state.conn, admin.id).await?,
+        timeout_ms: timeout_ms(&state),
+    }))
+}
+
+/// 校验注册结果并保存凭证，注册挑战无论成败只能使用一次
+pub async fn register_service(
+    state: AppState,
+    auth_context: AuthContext,
+    payload: WebAuthnRegisterRequest,
+) -> Result<Response<WebAuthnCredentialResponse>> {
+    ensure_enabled(&state)?;
+
+    let challenge = webauthn_challenges::Entity::find()
+        .filter(webauthn_challenges::Column::AdminId.eq(auth_context.admin_id))
+        .one(&state.conn)
+        .await?
+        .ok_or_else(|| GuardianError::BadRequest(t!("webauthn.challenge_invalid")))?;
+    webauthn_challenges::Entity::delete_by_id(challenge.id)
+        .exec(&state.conn)
+        .await?;
+    if challenge.expires_at <= chrono::Local::now() {
+        return Err(GuardianError::BadRequest(t!("webauthn.challenge_invalid")));
+    }
+
+    let verified = (|| {
+        verify_registration(
+            &state.config.webauthn,
+            &challenge.challenge,
+            &decode_base64url(&payload.client_data_json)?,
+            &decode_base64url(&payload.authenticator_data)?,
+            &decode_base64url(&payload.public_key)?,
+            payload.public_key_algorithm,
+        )
+    })();
+    let credential = verified.map_err(|e| {
+        warn!(
+            "管理员 {} 注册 WebAuthn 凭证失败: {}",
+            auth_context.username, e
+        );
+        GuardianError::BadRequest(t!("webauthn.verification_failed"))
+    })?;
+
+    let now = chrono::Local::now();
+    let model = webauthn_credentials::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        admin_id: Set(auth_context.admin_id),
+        credential_id: Set(credential.credential_id),
+        public_key: Set(credential.public_key),
+        algorithm: Set(credential.algorithm),
+        sign_count: Set(credential.sign_count as i64),
+        name: Set(payload.name.trim().to_string()),
+        last_used_at: Set(None),
+        created_at: Set(Some(now.into())),
+    }
+    .insert(&state.conn)
+    .await
+    .map_err(|err| match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            GuardianError::Conflict(t!("webauthn.credential_taken"))
+        }
+        _ => err.into(),
+    })?;
+
+    Ok(Response::ok(Some(t!("common.created")), to_response(model)))
+}
+
+pub async fn list_credentials_service(
+    state: AppState,
+    auth_context: AuthContext,
+) -> Result<Response<Vec<WebAuthnCredentialResponse>>> {
+    let credentials = webauthn_credentials::Entity::find()
+        .filter(webauthn_credentials::Column::AdminId.eq(auth_context.admin_id))
+        .order_by_asc(webauthn_credentials::Column::CreatedAt)
+        .all(&state.conn)
+        .await?;
+
+    Ok(Response::ok_data(
+        credentials.into_iter().map(to_response).collect(),
+    ))
+}
+
+pub async fn delete_credential_service(
+    state: AppState,
+    auth_context: AuthContext,
+    id: Uuid,
+) -> Result<Response<()>> {
+    let deleted = webauthn_credentials::Entity::delete_many()
+        .filter(webauthn_credentials::Column::Id.eq(id))
+        .filter(webauthn_credentials::Column::AdminId.eq(auth_context.admin_id))
+        .exec(&state.conn)
+        .await?;
+
+    if deleted.rows_affected == 0 {
+        return Err(GuardianError::NotFound(t!("webauthn.credential_not_found")));
+    }
+    Ok(Response::ok_msg(Some(t!("common.deleted"))))
+}
+
+pub(crate) async fn credential_ids<C: ConnectionTrait>(
+    conn: &C,
+    admin_id: Uuid,
+) -> Result<Vec<String>> {
+    let credentials = webauthn_credentials::Entity::find()
+        .filter(webauthn_credentials::Column::AdminId.eq(admin_id))
+        .all(conn)
+        .await?;
+    Ok(credentials.into_iter().map(|c| c.credential_id).collect())
+}
+
+/// 登录 2FA 挑战中的断言参数，挑战即 `mfa_token`。未启用 WebAuthn 或没有凭证时返回 `None`
+pub(crate) fn assertion_options(
+    state: &AppState,
+    mfa_token: &str,
+    allow_credentials: Vec<String>,
+) -> Option<WebAuthnAssertionOptions> {
+    if !state.config.webauthn.enabled() || allow_credentials.is_empty() {
+        return None;
+    }
+
+    Some(WebAuthnAssertionOptions {
+        challenge: mfa_token.to_string(),
+        rp_id: state.config.webauthn.rp_id.clone(),
+        allow_credentials,
+        timeout_ms: timeout_ms(state),
+    })
+}
+
+/// 校验登录断言并更新签名计数。
+///
+/// 计数按旧值条件更新，同一断言并发提交时只有一个请求能通过。
+pub(crate) async fn verify_webauthn_assertion(
+    state: &AppState,
+    admin: &admins::Model,
+    mfa_token: &str,
+    payload: &WebAuthnAssertionRequest,
+) -> Result<bool> {
+    if !state.config.webauthn.enabled() {
+        return Ok(false);
+    }
+
+    let Some(credential) = webauthn_credentials::Entity::find()
+        .filter(webauthn_credentials::Column::AdminId.eq(admin.id))
+        .filter(webauthn_credentials::Column::CredentialId.eq(&payload.credential_id))
+        .one(&state.conn)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    let verified = (|| {
+        verify_assertion(
+            &state.config.webauthn,
+            mfa_token,
+            &credential.public_key,
+            credential.algorithm,
+            credential.sign_count as u32,
+            &Assertion {
+                client_data_json: &decode_base64url(&payload.client_data_json)?,
+                authenticator_data: &decode_base64url(&payload.authenticator_data)?,
+                signature: &decode_base64url(&payload.signature)?,
+            },
+        )
+    })();
+    let sign_count = match verified {
+        Ok(sign_count) => sign_count,
+        Err(e) => {
+            warn!(
+                "管理员 {} 的 WebAuthn 凭证 {} 校验失败: {}",
+                admin.username, credential.id, e
+            );
+            return Ok(false);
+        }
+    };
+
+    let updated = webauthn_credentials::Entity::update_many()
+        .col_expr(
+            webauthn_credentials::Column::SignCount,
+            Expr::value(sign_count as i64),
+        )
+        .col_expr(
+            webauthn_credentials::Column::LastUsedAt,
+            Expr::value(Some(chrono::DateTime::<chrono::FixedOffset>::from(
+                chrono::Local::now(),
+            ))),
+        )
+        .filter(webauthn_credentials::Column::Id.eq(credential.id))
+        .filter(webauthn_credentials::Column::SignCount.eq(credential.sign_count))
+        .exec(&state.conn)
+        .await?;
+
+    Ok(updated.rows_affected == 1)
+}
+
+pub(crate) async fn delete_webauthn_credentials<C: ConnectionTrait>(
+    conn: &C,
+    admin_id: Uuid,
+) -> Result<u64> {
+    let deleted = webauthn_credentials::Entity::delete_many()
+        .filter(webauthn_credentials::Column::AdminId.eq(admin_id))
+        .exec(conn)
+        .await?;
+    Ok(deleted.rows_affected)
+}
+
+fn to_response(credential: webauthn_credentials::Model) -> WebAuthnCredentialResponse {
+    WebAuthnCredentialResponse {
+        id: credential.id,
+        name: credential.name,
+        algorithm: credential.algorithm,
+        created_at: credential
+            .created_at
+            .map(|dt| dt.into())
+            .unwrap_or_else(chrono::Local::now),
+        last_used_at: credential.last_used_at.map(|dt| dt.into()),
+    }
+}
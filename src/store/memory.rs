@@ -0,0 +1,225 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use dashmap::DashMap;
+
+use crate::store::{CounterStore, RevocationStore};
+
+#[derive(Debug, Clone, Copy)]
+struct WindowCounter {
+    count: u64,
+    expires_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at_ms: i64,
+    expires_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailureCounter {
+    failures: u64,
+    last_failure_ms: i64,
+    expires_at_ms: i64,
+}
+
+/// 进程内计数，多实例部署时各实例独立计数
+#[derive(Default)]
+pub struct MemoryCounterStore {
+    windows: DashMap<(String, i64), WindowCounter>,
+    buckets: DashMap<String, TokenBucket>,
+    failures: DashMap<String, FailureCounter>,
+}
+
+#[async_trait]
+impl CounterStore for MemoryCounterStore {
+    async fn window_count(&self, key: &str, window: i64) -> Result<u64> {
+        Ok(self
+            .windows
+            .get(&(key.to_string(), window))
+            .map(|counter| counter.count)
+            .unwrap_or(0))
+    }
+
+    async fn increment_window(
+        &self,
+        key: &str,
+        window: i64,
+        cap: u64,
+        expires_at_ms: i64,
+    ) -> Result<Option<u64>> {
+        let mut entry = self
+            .windows
+            .entry((key.to_string(), window))
+            .or_insert(WindowCounter {
+                count: 0,
+                expires_at_ms,
+            });
+
+        let counter = entry.value_mut();
+        if counter.count >= cap {
+            return Ok(None);
+        }
+
+        counter.count += 1;
+        Ok(Some(counter.count))
+    }
+
+    async fn take_token(
+        &self,
+        key: &str,
+        capacity: f64,
+        refill_per_ms: f64,
+        now_ms: i64,
+    ) -> Result<(bool, f64)> {
+        let mut entry = self.buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: capacity,
+            refilled_at_ms: now_ms,
+            expires_at_ms: now_ms,
+        });
+
+        let bucket = entry.value_mut();
+        let elapsed_ms = (now_ms - bucket.refilled_at_ms).max(0) as f64;
+        bucket.tokens = (bucket.tokens + elapsed_ms * refill_per_ms).min(capacity);
+        bucket.refilled_at_ms = bucket.refilled_at_ms.max(now_ms);
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        // 令牌补满后该记录与新建的桶等价，可以清理
+        bucket.expires_at_ms = now_ms + ((capacity - bucket.tokens) / refill_per_ms).ceil() as i64;
+
+        Ok((allowed, bucket.tokens))
+    }
+
+    async fn record_failure(&self, key: &str, now_ms: i64, reset_after_ms: i64) -> Result<u64> {
+        let mut entry = self
+            .failures
+            .entry(key.to_string())
+            .or_insert(FailureCounter {
+                failures: 0,
+                last_failure_ms: now_ms,
+                expires_at_ms: now_ms,
+            });
+
+        let counter = entry.value_mut();
+        if counter.last_failure_ms.saturating_add(reset_after_ms) <= now_ms {
+            counter.failures = 0;
+        }
+        counter.failures = counter.failures.saturating_add(1);
+        counter.last_failure_ms = counter.last_failure_ms.max(now_ms);
+        counter.expires_at_ms = counter.last_failure_ms.saturating_add(reset_after_ms);
+
+        Ok(counter.failures)
+    }
+
+    async fn failure_state(&self, key: &str) -> Result<Option<(u64, i64)>> {
+        Ok(self
+            .failures
+            .get(key)
+            .map(|counter| (counter.failures, counter.last_failure_ms)))
+    }
+
+    async fn cleanup_expired(&self, now_ms: i64) -> Result<u64> {
+        let before = self.windows.len() + self.buckets.len() + self.failures.len();
+        self.windows
+            .retain(|_, counter| counter.expires_at_ms > now_ms);
+        self.buckets
+            .retain(|_, bucket| bucket.expires_at_ms > now_ms);
+        self.failures
+            .retain(|_, counter| counter.expires_at_ms > now_ms);
+        Ok((before - self.windows.len() - self.buckets.len() - self.failures.len()) as u64)
+    }
+}
+
+/// 进程内吊销集合，重启后丢失，多实例部署时不共享
+#[derive(Default)]
+pub struct MemoryRevocationStore {
+    revoked: DashMap<String, DateTime<Local>>,
+}
+
+#[async_trait]
+impl RevocationStore for MemoryRevocationStore {
+    async fn revoke(&self, token_id: &str, expires_at: DateTime<Local>) -> Result<()> {
+        self.revoked.insert(token_id.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn is_revoked(&self, token_id: &str) -> Result<bool> {
+        Ok(self
+            .revoked
+            .get(token_id)
+            .is_some_and(|expires_at| *expires_at > Local::now()))
+    }
+
+    async fn purge_expired(&self) -> Result<u64> {
+        let before = self.revoked.len();
+        let now = Local::now();
+        self.revoked.retain(|_, expires_at| *expires_at > now);
+        Ok((before - self.revoked.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_window_counter() {
+        let store = MemoryCounterStore::default();
+
+        assert_eq!(
+            store.increment_window("k", 1, 2, 100).await.unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            store.increment_window("k", 1, 2, 100).await.unwrap(),
+            Some(2)
+        );
+        assert_eq!(store.increment_window("k", 1, 2, 100).await.unwrap(), None);
+        assert_eq!(store.window_count("k", 1).await.unwrap(), 2);
+        assert_eq!(store.window_count("k", 2).await.unwrap(), 0);
+
+        assert_eq!(store.cleanup_expired(100).await.unwrap(), 1);
+        assert_eq!(store.window_count("k", 1).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failure_counter() {
+        let store = MemoryCounterStore::default();
+
+        assert_eq!(store.failure_state("k").await.unwrap(), None);
+        assert_eq!(store.record_failure("k", 1_000, 500).await.unwrap(), 1);
+        assert_eq!(store.record_failure("k", 1_200, 500).await.unwrap(), 2);
+        assert_eq!(store.failure_state("k").await.unwrap(), Some((2, 1_200)));
+
+        // 距上次失败已满 reset_after_ms，重新计数
+        assert_eq!(store.record_failure("k", 1_700, 500).await.unwrap(), 1);
+
+        assert_eq!(store.cleanup_expired(2_199).await.unwrap(), 0);
+        assert_eq!(store.cleanup_expired(2_200).await.unwrap(), 1);
+        assert_eq!(store.failure_state("k").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_revocation() {
+        let store = MemoryRevocationStore::default();
+
+        store
+            .revoke("a", Local::now() + chrono::Duration::minutes(5))
+            .await
+            .unwrap();
+        store
+            .revoke("b", Local::now() - chrono::Duration::minutes(5))
+            .await
+            .unwrap();
+
+        assert!(store.is_revoked("a").await.unwrap());
+        assert!(!store.is_revoked("b").await.unwrap());
+        assert!(!store.is_revoked("c").await.unwrap());
+        assert_eq!(store.purge_expired().await.unwrap(), 1);
+    }
+}
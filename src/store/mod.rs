@@ -0,0 +1,83 @@
+pub mod memory;
+pub mod postgres;
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use log::info;
+use sea_orm::DatabaseConnection;
+
+pub use memory::{MemoryCounterStore, MemoryRevocationStore};
+pub use postgres::{PostgresCounterStore, PostgresRevocationStore};
+
+/// 限流计数存储。时间统一使用 Unix 毫秒，由调用方传入，便于多实例共享同一套窗口。
+#[async_trait]
+pub trait CounterStore: Send + Sync {
+    /// 读取指定窗口的计数，不存在时为 0
+    async fn window_count(&self, key: &str, window: i64) -> Result<u64>;
+
+    /// 窗口计数小于 `cap` 时原子加一并返回新计数，否则返回 `None`
+    async fn increment_window(
+        &self,
+        key: &str,
+        window: i64,
+        cap: u64,
+        expires_at_ms: i64,
+    ) -> Result<Option<u64>>;
+
+    /// 令牌桶取一个令牌，返回是否成功以及剩余令牌数
+    async fn take_token(
+        &self,
+        key: &str,
+        capacity: f64,
+        refill_per_ms: f64,
+        now_ms: i64,
+    ) -> Result<(bool, f64)>;
+
+    /// 记录一次失败并返回连续失败次数。距上次失败已达 `reset_after_ms` 时从 1 重新计数
+    async fn record_failure(&self, key: &str, now_ms: i64, reset_after_ms: i64) -> Result<u64>;
+
+    /// 读取连续失败次数和最近一次失败时间（Unix 毫秒），没有记录时返回 `None`
+    async fn failure_state(&self, key: &str) -> Result<Option<(u64, i64)>>;
+
+    /// 删除已过期的计数，返回删除条数
+    async fn cleanup_expired(&self, now_ms: i64) -> Result<u64>;
+}
+
+/// 已吊销令牌（JWT jti）集合
+#[async_trait]
+pub trait RevocationStore: Send + Sync {
+    async fn revoke(&self, token_id: &str, expires_at: DateTime<Local>) -> Result<()>;
+
+    async fn is_revoked(&self, token_id: &str) -> Result<bool>;
+
+    /// 删除已自然过期的吊销记录，返回删除条数
+    async fn purge_expired(&self) -> Result<u64>;
+}
+
+/// `memory`（仅单实例有效）或 `postgres`（多实例共享）
+pub fn counter_store(store: &str, conn: &DatabaseConnection) -> Result<Arc<dyn CounterStore>> {
+    info!("限流计数存储: {}", store);
+
+    match store {
+        "memory" => Ok(Arc::new(MemoryCounterStore::default())),
+        "postgres" => Ok(Arc::new(PostgresCounterStore::new(conn.clone()))),
+        other => Err(anyhow!("不支持的 RATE_LIMIT_STORE: {}", other)),
+    }
+}
+
+/// `postgres`（多实例共享且重启不丢失）或 `memory`
+pub fn revocation_store(
+    store: &str,
+    conn: &DatabaseConnection,
+) -> Result<Arc<dyn RevocationStore>> {
+    info!("令牌吊销存储: {}", store);
+
+    match store {
+        "memory" => Ok(Arc::new(MemoryRevocationStore::default())),
+        "postgres" => Ok(Arc::new(PostgresRevocationStore::new(conn.clone()))),
+        other => Err(anyhow!("不支持的 REVOCATION_STORE: {}", other)),
+    }
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, NotSet,
+    PaginatorTrait, QueryFilter, Set, Statement,
+};
+
+use crate::entities::token_blacklist;
+use crate::store::{CounterStore, RevocationStore};
+
+/// 基于 Postgres 的限流计数，多实例共享。
+///
+/// 计数的条件自增和令牌桶扣减都在一条 upsert 语句中完成，依赖行锁保证原子性。
+pub struct PostgresCounterStore {
+    conn: DatabaseConnection,
+}
+
+impl PostgresCounterStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl CounterStore for PostgresCounterStore {
+    async fn window_count(&self, key: &str, window: i64) -> Result<u64> {
+        let row = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT "count" FROM "guardian_rate_limit_counters" WHERE "key" = $1 AND "window_index" = $2"#,
+                [key.into(), window.into()],
+            ))
+            .await?;
+
+        Ok(match row {
+            Some(row) => row.try_get::<i64>("", "count")?.max(0) as u64,
+            None => 0,
+        })
+    }
+
+    async fn increment_window(
+        &self,
+        key: &str,
+        window: i64,
+        cap: u64,
+        expires_at_ms: i64,
+    ) -> Result<Option<u64>> {
+        if cap == 0 {
+            return Ok(None);
+        }
+
+        let row = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "guardian_rate_limit_counters" AS c ("key", "window_index", "count", "expires_at")
+VALUES ($1, $2, 1, to_timestamp($4::float8 / 1000))
+ON CONFLICT ("key", "window_index") DO UPDATE SET "count" = c."count" + 1
+WHERE c."count" < $3
+RETURNING "count""#,
+                [
+                    key.into(),
+                    window.into(),
+                    (cap.min(i64::MAX as u64) as i64).into(),
+                    expires_at_ms.into(),
+                ],
+            ))
+            .await?;
+
+        Ok(match row {
+            Some(row) => Some(row.try_get::<i64>("", "count")?.max(0) as u64),
+            None => None,
+        })
+    }
+
+    async fn take_token(
+        &self,
+        key: &str,
+        capacity: f64,
+        refill_per_ms: f64,
+        now_ms: i64,
+    ) -> Result<(bool, f64)> {
+        let row = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"WITH params AS (SELECT $2::float8 AS capacity, $3::float8 AS rate, $4::bigint AS now_ms)
+INSERT INTO "guardian_rate_limit_buckets" AS b ("key", "tokens", "refilled_at_ms", "last_allowed", "expires_at")
+SELECT $1, capacity - 1, now_ms, capacity >= 1, to_timestamp((now_ms + 1 / rate) / 1000) FROM params
+ON CONFLICT ("key") DO UPDATE SET
+    "tokens" = (
+        SELECT CASE WHEN level >= 1 THEN level - 1 ELSE level END
+        FROM (SELECT LEAST(p.capacity, b."tokens" + GREATEST(p.now_ms - b."refilled_at_ms", 0) * p.rate) AS level FROM params p) t
+    ),
+    "last_allowed" = (
+        SELECT LEAST(p.capacity, b."tokens" + GREATEST(p.now_ms - b."refilled_at_ms", 0) * p.rate) >= 1 FROM params p
+    ),
+    "refilled_at_ms" = GREATEST(b."refilled_at_ms", EXCLUDED."refilled_at_ms"),
+    "expires_at" = (SELECT to_timestamp((p.now_ms + p.capacity / p.rate) / 1000) FROM params p)
+RETURNING "tokens", "last_allowed""#,
+                [key.into(), capacity.into(), refill_per_ms.into(), now_ms.into()],
+            ))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("令牌桶更新失败"))?;
+
+        Ok((
+            row.try_get::<bool>("", "last_allowed")?,
+            row.try_get::<f64>("", "tokens")?.max(0.0),
+        ))
+    }
+
+    async fn record_failure(&self, key: &str, now_ms: i64, reset_after_ms: i64) -> Result<u64> {
+        let row = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "guardian_rate_limit_failures" AS f ("key", "failures", "last_failure_ms", "expires_at")
+VALUES ($1, 1, $2, to_timestamp(($2 + $3)::float8 / 1000))
+ON CONFLICT ("key") DO UPDATE SET
+    "failures" = CASE WHEN f."last_failure_ms" + $3 <= $2 THEN 1 ELSE f."failures" + 1 END,
+    "last_failure_ms" = GREATEST(f."last_failure_ms", EXCLUDED."last_failure_ms"),
+    "expires_at" = to_timestamp((GREATEST(f."last_failure_ms", EXCLUDED."last_failure_ms") + $3)::float8 / 1000)
+RETURNING "failures""#,
+                [key.into(), now_ms.into(), reset_after_ms.into()],
+            ))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("失败计数更新失败"))?;
+
+        Ok(row.try_get::<i64>("", "failures")?.max(0) as u64)
+    }
+
+    async fn failure_state(&self, key: &str) -> Result<Option<(u64, i64)>> {
+        let row = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT "failures", "last_failure_ms" FROM "guardian_rate_limit_failures" WHERE "key" = $1"#,
+                [key.into()],
+            ))
+            .await?;
+
+        Ok(match row {
+            Some(row) => Some((
+                row.try_get::<i64>("", "failures")?.max(0) as u64,
+                row.try_get::<i64>("", "last_failure_ms")?,
+            )),
+            None => None,
+        })
+    }
+
+    async fn cleanup_expired(&self, now_ms: i64) -> Result<u64> {
+        let mut deleted = 0;
+        for table in [
+            "guardian_rate_limit_counters",
+            "guardian_rate_limit_buckets",
+            "guardian_rate_limit_failures",
+        ] {
+            let result = self
+                .conn
+                .execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    format!(
+                        r#"DELETE FROM "{}" WHERE "expires_at" <= to_timestamp($1::float8 / 1000)"#,
+                        table
+                    ),
+                    [now_ms.into()],
+                ))
+                .await?;
+            deleted += result.rows_affected();
+        }
+        Ok(deleted)
+    }
+}
+
+/// 基于令牌黑名单表的吊销集合
+pub struct PostgresRevocationStore {
+    conn: DatabaseConnection,
+}
+
+impl PostgresRevocationStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl RevocationStore for PostgresRevocationStore {
+    async fn revoke(&self, token_id: &str, expires_at: DateTime<Local>) -> Result<()> {
+        let blacklist = token_blacklist::ActiveModel {
+            id: NotSet,
+            token_id: Set(token_id.to_string()),
+            expires_at: Set(expires_at.into()),
+            created_at: NotSet,
+        };
+
+        token_blacklist::Entity::insert(blacklist)
+            .on_conflict(
+                OnConflict::column(token_blacklist::Column::TokenId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(&self.conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn is_revoked(&self, token_id: &str) -> Result<bool> {
+        let count = token_blacklist::Entity::find()
+            .filter(token_blacklist::Column::TokenId.eq(token_id))
+            .filter(token_blacklist::Column::ExpiresAt.gt(Local::now()))
+            .count(&self.conn)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn purge_expired(&self) -> Result<u64> {
+        let result = token_blacklist::Entity::delete_many()
+            .filter(token_blacklist::Column::ExpiresAt.lt(Local::now()))
+            .exec(&self.conn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
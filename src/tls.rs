@@ -0,0 +1,420 @@
+//! HTTPS 终止
+//!
+//! 配置证书后直接以 HTTPS 监听，小规模部署不需要在前面再放一层反向代理。证书文件按间隔检查
+//! 修改时间，变化后重新加载：新连接使用新证书，已建立的连接不受影响，加载失败时继续使用旧证书。
+//!
+//! 配置客户端 CA 后，握手时请求客户端证书但不强制，管理后台等浏览器访问不受影响；
+//! 证书状态随请求传递，由 `client_cert_middleware` 对服务间调用的接口要求有效证书。
+
+use std::{future::Future, io, pin::Pin, sync::Arc, time::SystemTime};
+
+use anyhow::{Context, Result, anyhow};
+use axum_server::{
+    accept::{Accept, DefaultAcceptor},
+    tls_rustls::{RustlsAcceptor, RustlsConfig},
+};
+use log::{info, warn};
+use rustls::{
+    RootCertStore, ServerConfig,
+    crypto::{CryptoProvider, ring},
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    task::JoinHandle,
+};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+
+use crate::config::TlsConfig;
+
+/// HTTPS 连接的客户端证书状态，放在请求扩展中；明文 HTTP 请求没有该扩展
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCertificate {
+    /// 客户端出示了证书且通过了客户端 CA 的校验
+    pub verified: bool,
+}
+
+/// 读取证书、私钥和客户端 CA，生成 rustls 配置
+pub fn load_server_config(config: &TlsConfig) -> Result<Arc<ServerConfig>> {
+    let certs = read_certs(&config.cert_file)?;
+    let key = PrivateKeyDer::from_pem_file(&config.key_file)
+        .with_context(|| format!("无法读取私钥 {}", config.key_file))?;
+
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("TLS 协议版本配置失败")?;
+    let builder = if config.client_auth_enabled() {
+        builder.with_client_cert_verifier(client_verifier(&config.client_ca_file, provider)?)
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .with_context(|| format!("证书 {} 与私钥不匹配或格式不受支持", config.cert_file))?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(server_config))
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("无法读取证书 {}", path))?;
+    if certs.is_empty() {
+        return Err(anyhow!("{} 中没有 PEM 格式的证书", path));
+    }
+    Ok(certs)
+}
+
+/// 未出示证书的连接仍然放行，出示了无效证书的连接在握手时拒绝
+fn client_verifier(
+    ca_file: &str,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+    let mut roots = RootCertStore::empty();
+    for cert in read_certs(ca_file)? {
+        roots
+            .add(cert)
+            .with_context(|| format!("客户端 CA {} 无效", ca_file))?;
+    }
+
+    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+        .allow_unauthenticated()
+        .build()
+        .with_context(|| format!("客户端 CA {} 无效", ca_file))
+}
+
+/// 在 rustls 握手之后把客户端证书状态附加到该连接的每个请求上
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    inner: RustlsAcceptor<DefaultAcceptor>,
+}
+
+impl TlsAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        Self {
+            inner: RustlsAcceptor::new(config),
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for TlsAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientCertificate>;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, Self::Service)>> + Send>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            // 校验在握手时完成，能取到证书说明已通过客户端 CA 的校验
+            let verified = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .is_some_and(|certs| !certs.is_empty());
+            Ok((
+                stream,
+                AddExtension::new(service, ClientCertificate { verified }),
+            ))
+        })
+    }
+}
+
+/// 按 `tls.reload_interval_secs` 检查证书文件，修改后重新加载；间隔为 0 时不启动
+pub fn spawn_reloader(config: TlsConfig, rustls: RustlsConfig) -> Option<JoinHandle<()>> {
+    let interval = config.reload_interval()?;
+    Some(tokio::spawn(async move {
+        let mut last_modified = modified_times(&config);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let modified = modified_times(&config);
+            if modified == last_modified {
+                continue;
+            }
+            // 证书和私钥通常先后写入，不匹配时等下一次修改再重试，不重复告警
+            last_modified = modified;
+
+            match load_server_config(&config) {
+                Ok(server_config) => {
+                    rustls.reload_from_config(server_config);
+                    info!("已重新加载 TLS 证书");
+                }
+                Err(e) => warn!("重新加载 TLS 证书失败，继续使用当前证书: {:#}", e),
+            }
+        }
+    }))
+}
+
+/// 跟随符号链接，Kubernetes 挂载的 Secret 通过替换链接更新时同样能检测到
+fn modified_times(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [&config.cert_file, &config.key_file, &config.client_ca_file]
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+    use axum::{Extension, Router, routing::get};
+    use rcgen::{
+        BasicConstraints, CertificateParams, CertifiedKey, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+    };
+    use rustls::{ClientConfig, pki_types::ServerName};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+
+    use super::*;
+
+    /// 测试用的 CA、服务端证书和客户端证书，写入临时目录
+    struct TestPki {
+        dir: PathBuf,
+        ca: CertifiedKey,
+    }
+
+    impl TestPki {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("guardian-tls-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let pki = Self {
+                dir,
+                ca: self_signed_ca(),
+            };
+
+            let server = pki.issue(
+                vec!["localhost".to_string()],
+                ExtendedKeyUsagePurpose::ServerAuth,
+            );
+            pki.write("server.pem", &server.cert.pem());
+            pki.write("server.key", &server.key_pair.serialize_pem());
+            pki.write("ca.pem", &pki.ca.cert.pem());
+            pki
+        }
+
+        fn issue(&self, names: Vec<String>, usage: ExtendedKeyUsagePurpose) -> CertifiedKey {
+            let key_pair = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(names).unwrap();
+            params.extended_key_usages = vec![usage];
+            let cert = params
+                .signed_by(&key_pair, &self.ca.cert, &self.ca.key_pair)
+                .unwrap();
+            CertifiedKey { cert, key_pair }
+        }
+
+        fn write(&self, name: &str, content: &str) {
+            std::fs::write(self.dir.join(name), content).unwrap();
+        }
+
+        fn path(&self, name: &str) -> String {
+            self.dir.join(name).to_string_lossy().into_owned()
+        }
+
+        fn config(&self, client_auth: bool) -> TlsConfig {
+            TlsConfig {
+                cert_file: self.path("server.pem"),
+                key_file: self.path("server.key"),
+                client_ca_file: if client_auth {
+                    self.path("ca.pem")
+                } else {
+                    String::new()
+                },
+                ..TlsConfig::default()
+            }
+        }
+    }
+
+    impl Drop for TestPki {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn self_signed_ca() -> CertifiedKey {
+        let key_pair = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let cert = params.self_signed(&key_pair).unwrap();
+        CertifiedKey { cert, key_pair }
+    }
+
+    /// 信任测试 CA 的 TLS 连接
+    async fn connect(
+        addr: SocketAddr,
+        pki: &TestPki,
+        client: Option<&CertifiedKey>,
+    ) -> io::Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+        let mut roots = RootCertStore::empty();
+        roots.add(pki.ca.cert.der().clone()).unwrap();
+        let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+        let config = match client {
+            Some(client) => builder
+                .with_client_auth_cert(
+                    vec![client.cert.der().clone()],
+                    PrivateKeyDer::try_from(client.key_pair.serialize_der()).unwrap(),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+    }
+
+    /// 新建连接握手时服务端出示的证书
+    async fn server_certificate(addr: SocketAddr, pki: &TestPki) -> CertificateDer<'static> {
+        let stream = connect(addr, pki, None).await.unwrap();
+        stream.get_ref().1.peer_certificates().unwrap()[0].clone()
+    }
+
+    /// 通过 TLS 发送一个 HTTP/1.1 请求，返回响应体
+    async fn get_over_tls(
+        addr: SocketAddr,
+        pki: &TestPki,
+        client: Option<&CertifiedKey>,
+    ) -> io::Result<String> {
+        let mut stream = connect(addr, pki, client).await?;
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_string())
+            .unwrap_or_default())
+    }
+
+    #[test]
+    fn test_load_server_config() {
+        let pki = TestPki::new();
+        let server_config = load_server_config(&pki.config(false)).unwrap();
+        assert_eq!(server_config.alpn_protocols[1], b"http/1.1");
+        assert!(load_server_config(&pki.config(true)).is_ok());
+
+        // 私钥与证书不匹配
+        let other = pki.issue(
+            vec!["localhost".to_string()],
+            ExtendedKeyUsagePurpose::ServerAuth,
+        );
+        pki.write("other.key", &other.key_pair.serialize_pem());
+        let config = TlsConfig {
+            key_file: pki.path("other.key"),
+            ..pki.config(false)
+        };
+        assert!(load_server_config(&config).is_err());
+
+        let config = TlsConfig {
+            cert_file: pki.path("missing.pem"),
+            ..pki.config(false)
+        };
+        let err = format!("{:#}", load_server_config(&config).unwrap_err());
+        assert!(err.contains("missing.pem"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_client_certificate_verification() {
+        let pki = TestPki::new();
+        let rustls = RustlsConfig::from_config(load_server_config(&pki.config(true)).unwrap());
+        let app = Router::new().route(
+            "/",
+            get(|Extension(cert): Extension<ClientCertificate>| async move {
+                cert.verified.to_string()
+            }),
+        );
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = axum_server::Handle::new();
+        tokio::spawn(
+            axum_server::from_tcp(listener)
+                .acceptor(TlsAcceptor::new(rustls))
+                .handle(handle.clone())
+                .serve(app.into_make_service()),
+        );
+
+        // 未出示证书的连接可以建立，由中间件按路径决定是否拒绝
+        assert_eq!(get_over_tls(addr, &pki, None).await.unwrap(), "false");
+
+        let client = pki.issue(Vec::new(), ExtendedKeyUsagePurpose::ClientAuth);
+        assert_eq!(
+            get_over_tls(addr, &pki, Some(&client)).await.unwrap(),
+            "true"
+        );
+
+        // 其他 CA 签发的证书在握手时被拒绝
+        let untrusted = TestPki::new();
+        let client = untrusted.issue(Vec::new(), ExtendedKeyUsagePurpose::ClientAuth);
+        assert!(get_over_tls(addr, &pki, Some(&client)).await.is_err());
+
+        handle.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_reload_certificate() {
+        let pki = TestPki::new();
+        let config = TlsConfig {
+            reload_interval_secs: 1,
+            ..pki.config(false)
+        };
+        let rustls = RustlsConfig::from_config(load_server_config(&config).unwrap());
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = axum_server::Handle::new();
+        tokio::spawn(
+            axum_server::from_tcp(listener)
+                .acceptor(TlsAcceptor::new(rustls.clone()))
+                .handle(handle.clone())
+                .serve(Router::new().into_make_service()),
+        );
+        let reloader = spawn_reloader(config, rustls).unwrap();
+
+        let original = server_certificate(addr, &pki).await;
+        // 先让重新加载任务记下当前的修改时间
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let renewed = pki.issue(
+            vec!["localhost".to_string()],
+            ExtendedKeyUsagePurpose::ServerAuth,
+        );
+        pki.write("server.pem", &renewed.cert.pem());
+        pki.write("server.key", &renewed.key_pair.serialize_pem());
+
+        let mut current = original.clone();
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            current = server_certificate(addr, &pki).await;
+            if current != original {
+                break;
+            }
+        }
+        assert_eq!(&current, renewed.cert.der());
+
+        // 替换成无效文件时加载失败，新连接继续使用当前证书
+        pki.write("server.pem", "not a certificate");
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        assert_eq!(&server_certificate(addr, &pki).await, renewed.cert.der());
+
+        reloader.abort();
+        handle.shutdown();
+    }
+}
@@ -0,0 +1,190 @@
+//! 请求追踪 ID
+//!
+//! 每个请求有一个追踪 ID：优先取 W3C `traceparent` 中的 trace-id，其次取 `X-Request-Id`，
+//! 都没有时生成新的。追踪 ID 在请求处理期间保存在 task-local 中，写入响应体、审计日志和日志 span，
+//! 便于把同一请求在各处留下的记录关联起来。
+
+use std::future::Future;
+
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// 接受的 `X-Request-Id` 最大长度，与审计日志 trace_id 列一致
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    static CURRENT_TRACE: TraceContext;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: String,
+    /// 上游 `traceparent` 中的 trace-flags，没有时为 `01`（已采样）
+    pub flags: String,
+}
+
+impl TraceContext {
+    /// 从请求头中提取追踪 ID，无效的值会被忽略
+    pub fn from_headers(traceparent: Option<&str>, request_id: Option<&str>) -> Self {
+        if let Some((trace_id, flags)) = traceparent.and_then(parse_traceparent) {
+            return Self { trace_id, flags };
+        }
+
+        let trace_id = request_id
+            .map(str::trim)
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+        Self {
+            trace_id,
+            flags: "01".to_string(),
+        }
+    }
+
+    /// 响应中回写的 `traceparent`，以本服务为新的 parent；追踪 ID 不是 32 位十六进制
+    /// （来自自定义的 `X-Request-Id`）时无法表示为 W3C 格式，返回 None
+    pub fn traceparent(&self) -> Option<String> {
+        is_lower_hex(&self.trace_id, 32).then(|| {
+            let span_id = &Uuid::new_v4().simple().to_string()[..16];
+            format!("00-{}-{}-{}", self.trace_id, span_id, self.flags)
+        })
+    }
+}
+
+/// 解析 `version-traceid-parentid-flags`，返回 trace-id 和 flags
+fn parse_traceparent(value: &str) -> Option<(String, String)> {
+    let mut parts = value.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let parent_id = parts.next()?;
+    let flags = parts.next()?;
+
+    // 版本 00 不允许多余字段，更高版本按规范只读取前四段
+    if !is_lower_hex(version, 2) || version == "ff" || (version == "00" && parts.next().is_some()) {
+        return None;
+    }
+    if !is_lower_hex(trace_id, 32) || trace_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    if !is_lower_hex(parent_id, 16) || parent_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    if !is_lower_hex(flags, 2) {
+        return None;
+    }
+
+    Some((trace_id.to_string(), flags.to_string()))
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// 只接受常见的 ID 字符，避免把任意内容写入日志和数据库
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// 当前请求的追踪 ID，不在请求上下文中（后台任务、命令行）时返回 None
+pub fn current_trace_id() -> Option<String> {
+    CURRENT_TRACE.try_with(|trace| trace.trace_id.clone()).ok()
+}
+
+/// 在指定追踪上下文中执行 future
+pub async fn scope_trace<F: Future>(trace: TraceContext, f: F) -> F::Output {
+    CURRENT_TRACE.scope(trace, f).await
+}
+
+/// 在后台执行请求触发的任务，沿用当前请求的追踪 ID 和日志 span
+pub fn spawn_traced<F>(f: F) -> tokio::task::JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let f = f.in_current_span();
+    match CURRENT_TRACE.try_with(Clone::clone) {
+        Ok(trace) => tokio::spawn(scope_trace(trace, f)),
+        Err(_) => tokio::spawn(f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    #[test]
+    fn test_traceparent_preferred() {
+        let trace = TraceContext::from_headers(
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00"),
+            Some("req-1"),
+        );
+        assert_eq!(trace.trace_id, TRACE_ID);
+        assert_eq!(trace.flags, "00");
+
+        let traceparent = trace.traceparent().unwrap();
+        assert!(traceparent.starts_with(&format!("00-{}-", TRACE_ID)));
+        assert!(traceparent.ends_with("-00"));
+        assert_eq!(traceparent.len(), 55);
+    }
+
+    #[test]
+    fn test_invalid_traceparent_ignored() {
+        for value in [
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "garbage",
+        ] {
+            let trace = TraceContext::from_headers(Some(value), Some("req-1"));
+            assert_eq!(trace.trace_id, "req-1", "{}", value);
+        }
+
+        // 更高版本允许附加字段
+        let trace = TraceContext::from_headers(
+            Some("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"),
+            None,
+        );
+        assert_eq!(trace.trace_id, TRACE_ID);
+    }
+
+    #[test]
+    fn test_request_id_fallback() {
+        let trace = TraceContext::from_headers(None, Some("  abc.DEF:123_x-y  "));
+        assert_eq!(trace.trace_id, "abc.DEF:123_x-y");
+        assert!(trace.traceparent().is_none());
+
+        for value in ["", "has space", "<script>", &"a".repeat(65)] {
+            let trace = TraceContext::from_headers(None, Some(value));
+            assert!(is_lower_hex(&trace.trace_id, 32), "{}", value);
+            assert!(trace.traceparent().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scoped_trace_id() {
+        assert_eq!(current_trace_id(), None);
+        let trace = TraceContext::from_headers(None, Some("req-1"));
+        let id = scope_trace(trace.clone(), async { current_trace_id() }).await;
+        assert_eq!(id.as_deref(), Some("req-1"));
+
+        // 任务在另一个 task 中执行，追踪 ID 需要显式传递
+        scope_trace(trace, async {
+            spawn_traced(async {
+                assert_eq!(current_trace_id().as_deref(), Some("req-1"));
+            })
+            .await
+            .unwrap();
+        })
+        .await;
+    }
+}
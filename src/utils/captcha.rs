@@ -0,0 +1,72 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use log::info;
+use serde::Deserialize;
+
+use crate::config::CaptchaConfig;
+
+/// 人机验证校验器，登录失败次数达到阈值后要求客户端提交验证码
+#[async_trait]
+pub trait CaptchaVerifier: Send + Sync {
+    async fn verify(&self, token: &str, client_ip: IpAddr) -> Result<bool>;
+}
+
+/// 兼容 reCAPTCHA / hCaptcha / Turnstile 的 siteverify 接口
+pub struct SiteVerifyCaptcha {
+    client: reqwest::Client,
+    verify_url: String,
+    secret: String,
+}
+
+#[derive(Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+}
+
+impl SiteVerifyCaptcha {
+    pub fn new(verify_url: String, secret: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            verify_url,
+            secret,
+        }
+    }
+}
+
+#[async_trait]
+impl CaptchaVerifier for SiteVerifyCaptcha {
+    async fn verify(&self, token: &str, client_ip: IpAddr) -> Result<bool> {
+        let response: SiteVerifyResponse = self
+            .client
+            .post(&self.verify_url)
+            .form(&[
+                ("secret", self.secret.as_str()),
+                ("response", token),
+                ("remoteip", &client_ip.to_string()),
+            ])
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response.success)
+    }
+}
+
+/// 未配置 `captcha.secret` 时不启用验证码
+pub fn captcha_verifier_from_config(config: &CaptchaConfig) -> Option<Arc<dyn CaptchaVerifier>> {
+    if !config.enabled() {
+        return None;
+    }
+    info!("已启用登录验证码: {}", config.verify_url);
+
+    Some(Arc::new(SiteVerifyCaptcha::new(
+        config.verify_url.clone(),
+        config.secret.clone(),
+    )))
+}
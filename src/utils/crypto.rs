@@ -1,28 +1,156 @@
 use argon2::{
-    Argon2,
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version,
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use log::warn;
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
-pub fn hash_password(password: &str) -> String {
+/// 密码哈希配置：Argon2id 参数 + 可选的服务端 pepper
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasswordHashConfig {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub pepper: Option<String>,
+    /// 写入哈希串 `keyid` 参数，用于识别哈希使用的是哪个 pepper
+    pub pepper_id: String,
+}
+
+impl Default for PasswordHashConfig {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            pepper: None,
+            pepper_id: "v1".to_string(),
+        }
+    }
+}
+
+impl PasswordHashConfig {
+    /// 启动时校验 Argon2 参数和 pepper 标识，避免运行中才回退到默认参数
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        Params::new(self.m_cost, self.t_cost, self.p_cost, None).map_err(|e| e.to_string())?;
+        if self.pepper.is_some() && KeyId::new(self.pepper_id.as_bytes()).is_err() {
+            return Err(format!(
+                "pepper_id 最长 {} 字节: {}",
+                KeyId::MAX_LEN,
+                self.pepper_id
+            ));
+        }
+        Ok(())
+    }
+
+    fn keyid(&self) -> Option<KeyId> {
+        self.pepper.as_ref()?;
+        KeyId::new(self.pepper_id.as_bytes()).ok()
+    }
+
+    fn params(&self) -> Params {
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(self.m_cost)
+            .t_cost(self.t_cost)
+            .p_cost(self.p_cost);
+        if let Some(keyid) = self.keyid() {
+            builder.keyid(keyid);
+        }
+        builder.build().unwrap_or_else(|e| {
+            warn!("Argon2 参数无效，使用默认参数: {}", e);
+            Params::default()
+        })
+    }
+
+    /// 启用 pepper 时先用 HMAC-SHA256 对密码做预处理，再交给 Argon2
+    fn peppered(&self, password: &str, pepper: &str) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(pepper.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(password.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+pub fn hash_password(config: &PasswordHashConfig, password: &str) -> String {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(password.as_bytes(), &salt)
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, config.params());
+    let input = match (&config.pepper, config.keyid()) {
+        (Some(pepper), Some(_)) => config.peppered(password, pepper),
+        _ => password.as_bytes().to_vec(),
+    };
+    argon2
+        .hash_password(&input, &salt)
         .expect("Unable to hash password")
-        .to_string();
-    password_hash
+        .to_string()
 }
 
-pub fn verify_password(password: &str, hash: &str) -> bool {
+pub fn verify_password(config: &PasswordHashConfig, password: &str, hash: &str) -> bool {
     let parsed_hash = match PasswordHash::new(hash) {
         Ok(hash) => hash,
         Err(_) => return false,
     };
+
+    // 哈希串中带 keyid 说明写入时使用了 pepper，未带则是历史的无 pepper 哈希
+    let input = match parsed_hash.params.get_str("keyid") {
+        None => password.as_bytes().to_vec(),
+        Some(keyid) => match (&config.pepper, config.keyid()) {
+            (Some(pepper), Some(current)) if Some(current) == keyid_from_b64(keyid) => {
+                config.peppered(password, pepper)
+            }
+            _ => {
+                warn!("密码哈希使用的 pepper（keyid={}）未配置，无法校验", keyid);
+                return false;
+            }
+        },
+    };
+
     Argon2::default()
-        .verify_password(password.as_bytes(), &parsed_hash)
+        .verify_password(&input, &parsed_hash)
         .is_ok()
 }
 
+/// 判断已存储的哈希是否使用了过时的算法参数或 pepper，登录成功后据此透明升级
+pub fn password_needs_rehash(config: &PasswordHashConfig, hash: &str) -> bool {
+    let Ok(parsed_hash) = PasswordHash::new(hash) else {
+        return true;
+    };
+    let Ok(params) = Params::try_from(&parsed_hash) else {
+        return true;
+    };
+    let expected = config.params();
+
+    parsed_hash.algorithm != Algorithm::Argon2id.ident()
+        || parsed_hash.version != Some(Version::V0x13.into())
+        || params.m_cost() != expected.m_cost()
+        || params.t_cost() != expected.t_cost()
+        || params.p_cost() != expected.p_cost()
+        || params.keyid() != expected.keyid()
+}
+
+fn keyid_from_b64(value: &str) -> Option<KeyId> {
+    let bytes = base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(value)
+        .ok()?;
+    KeyId::new(&bytes).ok()
+}
+
+/// 生成 URL 安全的不透明随机令牌（32 字节熵）
+pub fn generate_opaque_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 计算 SHA-256 十六进制摘要，用于存储高熵令牌（无需加盐）
+pub fn sha256_hex(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,16 +158,79 @@ mod tests {
     #[test]
     fn test_hash_password() {
         let password = "123456";
-        let hash = hash_password(password);
+        let hash = hash_password(&PasswordHashConfig::default(), password);
         assert!(hash.starts_with("$argon2"));
         assert!(hash.len() > 50);
     }
 
     #[test]
     fn test_verify_password() {
+        let config = PasswordHashConfig::default();
         let password = "123456";
-        let hash = hash_password(password);
-        assert!(verify_password(password, &hash));
-        assert!(!verify_password("wrong_password", &hash));
+        let hash = hash_password(&config, password);
+        assert!(verify_password(&config, password, &hash));
+        assert!(!verify_password(&config, "wrong_password", &hash));
+    }
+
+    #[test]
+    fn test_password_pepper() {
+        let plain = PasswordHashConfig::default();
+        let peppered = PasswordHashConfig {
+            pepper: Some("server-side-pepper".to_string()),
+            ..PasswordHashConfig::default()
+        };
+
+        let hash = hash_password(&peppered, "Str0ngPassw");
+        assert!(hash.contains("keyid="));
+        assert!(verify_password(&peppered, "Str0ngPassw", &hash));
+        assert!(!verify_password(&peppered, "wrong_password", &hash));
+        assert!(!verify_password(&plain, "Str0ngPassw", &hash));
+
+        let legacy_hash = hash_password(&plain, "Str0ngPassw");
+        assert!(verify_password(&peppered, "Str0ngPassw", &legacy_hash));
+        assert!(password_needs_rehash(&peppered, &legacy_hash));
+        assert!(!password_needs_rehash(&peppered, &hash));
+    }
+
+    #[test]
+    fn test_password_needs_rehash() {
+        let config = PasswordHashConfig::default();
+        let hash = hash_password(&config, "Str0ngPassw");
+        assert!(!password_needs_rehash(&config, &hash));
+
+        let stronger = PasswordHashConfig {
+            t_cost: config.t_cost + 1,
+            ..PasswordHashConfig::default()
+        };
+        assert!(password_needs_rehash(&stronger, &hash));
+        assert!(verify_password(&stronger, "Str0ngPassw", &hash));
+
+        assert!(config.validate().is_ok());
+        let too_small = PasswordHashConfig {
+            m_cost: 1,
+            ..PasswordHashConfig::default()
+        };
+        assert!(too_small.validate().is_err());
+        let long_pepper_id = PasswordHashConfig {
+            pepper: Some("server-side-pepper".to_string()),
+            pepper_id: "too-long-pepper-id".to_string(),
+            ..PasswordHashConfig::default()
+        };
+        assert!(long_pepper_id.validate().is_err());
+    }
+
+    #[test]
+    fn test_generate_opaque_token() {
+        let token = generate_opaque_token();
+        assert_eq!(token.len(), 43);
+        assert_ne!(token, generate_opaque_token());
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
     }
 }
@@ -5,6 +5,8 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::config::JwtConfig;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
 pub enum TokenType {
     #[default]
@@ -18,6 +20,9 @@ pub struct Claims {
     pub sub: String,
     pub username: String,
     pub is_super_admin: bool,
+    /// 必须先修改密码，令牌仅可访问修改密码和登出接口
+    #[serde(default)]
+    pub must_change_password: bool,
     pub exp: usize,
     pub iat: usize,
 }
@@ -36,23 +41,20 @@ pub struct AccessToken {
 }
 
 impl TokenType {
-    fn duration(&self) -> Duration {
+    fn duration(&self, config: &JwtConfig) -> Duration {
         match self {
-            TokenType::Access => Duration::from_secs(15 * 60),
-            TokenType::Refresh => Duration::from_secs(7 * 24 * 60 * 60),
+            TokenType::Access => Duration::from_secs(config.access_token_ttl_secs),
+            TokenType::Refresh => Duration::from_secs(config.refresh_token_ttl_secs),
         }
     }
 }
 
-fn get_secret() -> String {
-    std::env::var("JWT_SECRET")
-        .unwrap_or_else(|_| "ASDFGHJKLZXCVBNMQWERTYUIOP1234567890".to_string())
-}
-
 pub fn create_token_pair(
+    config: &JwtConfig,
     admin_id: Uuid,
     username: String,
     is_super_admin: bool,
+    must_change_password: bool,
 ) -> Result<TokenPair> {
     let jti = Uuid::new_v4().to_string();
 
@@ -61,14 +63,15 @@ pub fn create_token_pair(
         sub: admin_id.to_string(),
         username: username.clone(),
         is_super_admin,
-        exp: (chrono::Local::now() + TokenType::Access.duration()).timestamp() as usize,
+        must_change_password,
+        exp: (chrono::Local::now() + TokenType::Access.duration(config)).timestamp() as usize,
         iat: chrono::Local::now().timestamp() as usize,
     };
 
     let access_token = encode(
         &Header::default(),
         &access_claims,
-        &EncodingKey::from_secret(get_secret().as_ref()),
+        &EncodingKey::from_secret(config.secret.as_ref()),
     )?;
 
     let refresh_jti = Uuid::new_v4().to_string();
@@ -77,27 +80,28 @@ pub fn create_token_pair(
         sub: admin_id.to_string(),
         username,
         is_super_admin,
-        exp: (chrono::Local::now() + TokenType::Refresh.duration()).timestamp() as usize,
+        must_change_password,
+        exp: (chrono::Local::now() + TokenType::Refresh.duration(config)).timestamp() as usize,
         iat: chrono::Local::now().timestamp() as usize,
     };
 
     let refresh_token = encode(
         &Header::default(),
         &refresh_claims,
-        &EncodingKey::from_secret(get_secret().as_ref()),
+        &EncodingKey::from_secret(config.secret.as_ref()),
     )?;
 
     Ok(TokenPair {
         access_token,
         refresh_token,
-        expires_in: 15 * 60,
+        expires_in: config.access_token_ttl_secs as i64,
     })
 }
 
-pub fn refresh_access_token(refresh_token: &str) -> Result<AccessToken> {
+pub fn refresh_access_token(config: &JwtConfig, refresh_token: &str) -> Result<AccessToken> {
     let token_data = decode::<Claims>(
         refresh_token,
-        &DecodingKey::from_secret(get_secret().as_ref()),
+        &DecodingKey::from_secret(config.secret.as_ref()),
         &Validation::default(),
     )?;
 
@@ -108,32 +112,33 @@ pub fn refresh_access_token(refresh_token: &str) -> Result<AccessToken> {
         sub: claims.sub.clone(),
         username: claims.username.clone(),
         is_super_admin: claims.is_super_admin,
-        exp: (chrono::Local::now() + TokenType::Access.duration()).timestamp() as usize,
+        must_change_password: claims.must_change_password,
+        exp: (chrono::Local::now() + TokenType::Access.duration(config)).timestamp() as usize,
         iat: chrono::Local::now().timestamp() as usize,
     };
 
     let access_token = encode(
         &Header::default(),
         &access_claims,
-        &EncodingKey::from_secret(get_secret().as_ref()),
+        &EncodingKey::from_secret(config.secret.as_ref()),
     )?;
 
     Ok(AccessToken {
         access_token,
-        expires_in: 15 * 60,
+        expires_in: config.access_token_ttl_secs as i64,
     })
 }
 
-pub fn verify_token(token: &str) -> Result<Claims> {
+pub fn verify_token(config: &JwtConfig, token: &str) -> Result<Claims> {
     let token_data = decode::<Claims>(
         token,
-        &DecodingKey::from_secret(get_secret().as_ref()),
+        &DecodingKey::from_secret(config.secret.as_ref()),
         &Validation::default(),
     )?;
     Ok(token_data.claims)
 }
 
-pub fn get_jti(token: &str) -> Result<String> {
-    let claims = verify_token(token)?;
+pub fn get_jti(config: &JwtConfig, token: &str) -> Result<String> {
+    let claims = verify_token(config, token)?;
     Ok(claims.jti)
 }
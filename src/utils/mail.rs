@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use log::info;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::MailConfig;
+
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// 邮件发送通道，通过 `mail.transport` 选择具体实现
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: MailMessage) -> Result<()>;
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn from_config(config: &MailConfig) -> Result<Self> {
+        let host = config.smtp_host.as_str();
+        let from = config
+            .smtp_from
+            .parse::<Mailbox>()
+            .map_err(|e| anyhow!("mail.smtp_from 格式错误: {}", e))?;
+
+        let mut builder = match config.smtp_tls.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+        };
+
+        if let Some(port) = config.smtp_port {
+            builder = builder.port(port);
+        }
+
+        if !config.smtp_username.is_empty() {
+            builder = builder.credentials(Credentials::new(
+                config.smtp_username.clone(),
+                config.smtp_password.clone(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(message.to.parse()?)
+            .subject(message.subject)
+            .body(message.body)?;
+
+        self.transport.send(email).await?;
+        Ok(())
+    }
+}
+
+/// 将邮件追加写入本地文件，只用于开发和测试
+pub struct FileMailer {
+    path: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+
+        file.write_all(format_message(&message).as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// 将邮件打印到标准输出，只用于开发和测试：重置链接会出现在日志中
+pub struct StdoutMailer;
+
+#[async_trait]
+impl Mailer for StdoutMailer {
+    async fn send(&self, message: MailMessage) -> Result<()> {
+        print!("{}", format_message(&message));
+        Ok(())
+    }
+}
+
+fn format_message(message: &MailMessage) -> String {
+    format!(
+        "==== {} ====\nTo: {}\nSubject: {}\n\n{}\n\n",
+        chrono::Local::now().to_rfc3339(),
+        message.to,
+        message.subject,
+        message.body
+    )
+}
+
+/// `none` 时返回 `None`，邮件重置密码不可用
+pub fn mailer_from_config(config: &MailConfig) -> Result<Option<Arc<dyn Mailer>>> {
+    info!("邮件发送通道: {}", config.transport);
+
+    match config.transport.as_str() {
+        "none" => Ok(None),
+        "smtp" => Ok(Some(Arc::new(SmtpMailer::from_config(config)?))),
+        "file" => Ok(Some(Arc::new(FileMailer::new(&config.file_path)))),
+        "stdout" => Ok(Some(Arc::new(StdoutMailer))),
+        other => Err(anyhow!("不支持的邮件发送通道: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_mailer() {
+        let path = std::env::temp_dir().join(format!("guardian-mail-{}.log", uuid::Uuid::new_v4()));
+        let mailer = FileMailer::new(&path);
+
+        mailer
+            .send(MailMessage {
+                to: "admin@example.com".to_string(),
+                subject: "重置密码".to_string(),
+                body: "token=abc".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(content.contains("To: admin@example.com"));
+        assert!(content.contains("token=abc"));
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
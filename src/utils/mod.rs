@@ -1,5 +1,8 @@
+pub mod captcha;
 pub mod crypto;
 pub mod jwt;
+pub mod mail;
+pub mod webauthn;
 
 pub use crypto::*;
 pub use jwt::*;
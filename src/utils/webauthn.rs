@@ -0,0 +1,540 @@
+//! WebAuthn（安全密钥 / 通行密钥）的服务端校验
+//!
+//! 注册时由浏览器 `AuthenticatorAttestationResponse` 的 `getAuthenticatorData()` 和
+//! `getPublicKey()` 提供认证器数据与 SPKI 格式公钥，服务端无需解析 CBOR 格式的
+//! attestationObject；不校验认证器证明（相当于 `attestation: "none"`）。
+//! 支持 ES256、EdDSA 和 RS256 三种签名算法。
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::WebAuthnConfig;
+
+/// COSE 算法标识，与浏览器 `pubKeyCredParams` 中的取值一致
+pub const ALG_ES256: i32 = -7;
+pub const ALG_EDDSA: i32 = -8;
+pub const ALG_RS256: i32 = -257;
+pub const SUPPORTED_ALGORITHMS: [i32; 3] = [ALG_ES256, ALG_EDDSA, ALG_RS256];
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// rpIdHash(32) + flags(1) + signCount(4)
+const AUTH_DATA_MIN_LEN: usize = 37;
+/// 凭证 ID 最长 1023 字节（WebAuthn Level 3）
+const MAX_CREDENTIAL_ID_LEN: usize = 1023;
+
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const OID_P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+const DER_SEQUENCE: u8 = 0x30;
+const DER_BIT_STRING: u8 = 0x03;
+const DER_OID: u8 = 0x06;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebAuthnError {
+    #[error("数据格式错误: {0}")]
+    Malformed(&'static str),
+    #[error("clientDataJSON 类型应为 {0}")]
+    TypeMismatch(&'static str),
+    #[error("挑战不匹配")]
+    ChallengeMismatch,
+    #[error("来源不在允许列表中: {0}")]
+    OriginNotAllowed(String),
+    #[error("RP ID 摘要不匹配")]
+    RpIdMismatch,
+    #[error("认证器未确认用户在场")]
+    UserNotPresent,
+    #[error("不支持的公钥算法: {0}")]
+    UnsupportedAlgorithm(i32),
+    #[error("签名计数器未递增，凭证可能已被克隆")]
+    CounterRegressed,
+    #[error("签名无效")]
+    BadSignature,
+}
+
+pub type Result<T> = std::result::Result<T, WebAuthnError>;
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+pub struct AuthenticatorData {
+    pub flags: u8,
+    pub sign_count: u32,
+    /// 仅注册时存在（AT 标志位）
+    pub credential_id: Option<Vec<u8>>,
+}
+
+/// 注册校验通过后需要保存的凭证信息，`public_key` 为 ring 可直接使用的公钥格式
+pub struct RegisteredCredential {
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    pub sign_count: u32,
+}
+
+/// 浏览器传入的二进制字段均为 base64url 编码，兼容带填充的写法
+pub fn decode_base64url(value: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| WebAuthnError::Malformed("base64url"))
+}
+
+pub fn encode_base64url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 校验注册结果：clientDataJSON、认证器数据和公钥，返回需要保存的凭证
+pub fn verify_registration(
+    config: &WebAuthnConfig,
+    expected_challenge: &str,
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    public_key_spki: &[u8],
+    algorithm: i32,
+) -> Result<RegisteredCredential> {
+    verify_client_data(
+        config,
+        client_data_json,
+        "webauthn.create",
+        expected_challenge,
+    )?;
+    let data = verify_authenticator_data(config, authenticator_data)?;
+    let credential_id = data
+        .credential_id
+        .ok_or(WebAuthnError::Malformed("authenticatorData 缺少凭证数据"))?;
+    let public_key = parse_public_key(public_key_spki, algorithm)?;
+
+    Ok(RegisteredCredential {
+        credential_id: encode_base64url(&credential_id),
+        public_key,
+        algorithm,
+        sign_count: data.sign_count,
+    })
+}
+
+/// 浏览器 `navigator.credentials.get()` 返回的断言（已解码）
+pub struct Assertion<'a> {
+    pub client_data_json: &'a [u8],
+    pub authenticator_data: &'a [u8],
+    pub signature: &'a [u8],
+}
+
+/// 校验登录断言，签名覆盖 `authenticatorData || SHA-256(clientDataJSON)`，返回认证器新的签名计数
+pub fn verify_assertion(
+    config: &WebAuthnConfig,
+    expected_challenge: &str,
+    public_key: &[u8],
+    algorithm: i32,
+    stored_sign_count: u32,
+    assertion: &Assertion,
+) -> Result<u32> {
+    verify_client_data(
+        config,
+        assertion.client_data_json,
+        "webauthn.get",
+        expected_challenge,
+    )?;
+    let data = verify_authenticator_data(config, assertion.authenticator_data)?;
+
+    let mut message = assertion.authenticator_data.to_vec();
+    message.extend_from_slice(&Sha256::digest(assertion.client_data_json));
+    UnparsedPublicKey::new(verification_algorithm(algorithm)?, public_key)
+        .verify(&message, assertion.signature)
+        .map_err(|_| WebAuthnError::BadSignature)?;
+
+    check_sign_count(stored_sign_count, data.sign_count)?;
+    Ok(data.sign_count)
+}
+
+fn verify_client_data(
+    config: &WebAuthnConfig,
+    client_data_json: &[u8],
+    expected_type: &'static str,
+    expected_challenge: &str,
+) -> Result<()> {
+    let client_data: CollectedClientData = serde_json::from_slice(client_data_json)
+        .map_err(|_| WebAuthnError::Malformed("clientDataJSON"))?;
+
+    if client_data.kind != expected_type {
+        return Err(WebAuthnError::TypeMismatch(expected_type));
+    }
+    if client_data.challenge.trim_end_matches('=') != expected_challenge {
+        return Err(WebAuthnError::ChallengeMismatch);
+    }
+    if !config.origins.contains(&client_data.origin) {
+        return Err(WebAuthnError::OriginNotAllowed(client_data.origin));
+    }
+    Ok(())
+}
+
+fn verify_authenticator_data(config: &WebAuthnConfig, bytes: &[u8]) -> Result<AuthenticatorData> {
+    let data = parse_authenticator_data(bytes)?;
+
+    if bytes[..32] != Sha256::digest(config.rp_id.as_bytes())[..] {
+        return Err(WebAuthnError::RpIdMismatch);
+    }
+    if data.flags & FLAG_USER_PRESENT == 0 {
+        return Err(WebAuthnError::UserNotPresent);
+    }
+    Ok(data)
+}
+
+fn parse_authenticator_data(bytes: &[u8]) -> Result<AuthenticatorData> {
+    if bytes.len() < AUTH_DATA_MIN_LEN {
+        return Err(WebAuthnError::Malformed("authenticatorData 长度不足"));
+    }
+
+    let flags = bytes[32];
+    let sign_count = u32::from_be_bytes([bytes[33], bytes[34], bytes[35], bytes[36]]);
+
+    // attestedCredentialData: aaguid(16) + credentialIdLength(2) + credentialId
+    let credential_id = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        let rest = &bytes[AUTH_DATA_MIN_LEN..];
+        if rest.len() < 18 {
+            return Err(WebAuthnError::Malformed("attestedCredentialData 长度不足"));
+        }
+        let len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+        if len == 0 || len > MAX_CREDENTIAL_ID_LEN || rest.len() < 18 + len {
+            return Err(WebAuthnError::Malformed("凭证 ID 长度错误"));
+        }
+        Some(rest[18..18 + len].to_vec())
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        flags,
+        sign_count,
+        credential_id,
+    })
+}
+
+/// 计数器均为 0 表示认证器不支持计数；否则新值必须大于已保存的值
+fn check_sign_count(stored: u32, received: u32) -> Result<()> {
+    if (stored != 0 || received != 0) && received <= stored {
+        return Err(WebAuthnError::CounterRegressed);
+    }
+    Ok(())
+}
+
+fn verification_algorithm(algorithm: i32) -> Result<&'static dyn VerificationAlgorithm> {
+    match algorithm {
+        ALG_ES256 => Ok(&signature::ECDSA_P256_SHA256_ASN1),
+        ALG_EDDSA => Ok(&signature::ED25519),
+        ALG_RS256 => Ok(&signature::RSA_PKCS1_2048_8192_SHA256),
+        _ => Err(WebAuthnError::UnsupportedAlgorithm(algorithm)),
+    }
+}
+
+/// 从 SubjectPublicKeyInfo 中取出公钥，并检查其类型与声明的算法一致。
+///
+/// 返回值：ES256 为 65 字节未压缩点，EdDSA 为 32 字节公钥，RS256 为 PKCS#1 RSAPublicKey。
+pub fn parse_public_key(spki: &[u8], algorithm: i32) -> Result<Vec<u8>> {
+    let malformed = WebAuthnError::Malformed("publicKey");
+
+    let (info, rest) = der_element(spki, DER_SEQUENCE)?;
+    if !rest.is_empty() {
+        return Err(malformed);
+    }
+    let (algorithm_identifier, info) = der_element(info, DER_SEQUENCE)?;
+    let (key_bits, rest) = der_element(info, DER_BIT_STRING)?;
+    if !rest.is_empty() {
+        return Err(malformed);
+    }
+    let key = match key_bits.split_first() {
+        Some((0, key)) => key,
+        _ => return Err(malformed),
+    };
+    let (oid, parameters) = der_element(algorithm_identifier, DER_OID)?;
+
+    let matches = match algorithm {
+        ALG_ES256 => {
+            oid == OID_EC_PUBLIC_KEY
+                && der_element(parameters, DER_OID)? == (OID_P256, &[][..])
+                && key.len() == 65
+                && key[0] == 0x04
+        }
+        ALG_EDDSA => oid == OID_ED25519 && parameters.is_empty() && key.len() == 32,
+        ALG_RS256 => oid == OID_RSA_ENCRYPTION,
+        _ => return Err(WebAuthnError::UnsupportedAlgorithm(algorithm)),
+    };
+    if !matches {
+        return Err(malformed);
+    }
+    Ok(key.to_vec())
+}
+
+/// 读取一个 DER 元素，返回内容和剩余字节
+fn der_element(input: &[u8], tag: u8) -> Result<(&[u8], &[u8])> {
+    let malformed = WebAuthnError::Malformed("publicKey");
+
+    let (&actual, input) = input.split_first().ok_or(malformed.clone())?;
+    let (&first, input) = input.split_first().ok_or(malformed.clone())?;
+    if actual != tag {
+        return Err(malformed);
+    }
+
+    let (len, input) = match first {
+        0x00..=0x7f => (first as usize, input),
+        0x81 => {
+            let (&len, input) = input.split_first().ok_or(malformed.clone())?;
+            (len as usize, input)
+        }
+        0x82 if input.len() >= 2 => (
+            u16::from_be_bytes([input[0], input[1]]) as usize,
+            &input[2..],
+        ),
+        _ => return Err(malformed),
+    };
+    if input.len() < len {
+        return Err(malformed);
+    }
+    Ok(input.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    const ORIGIN: &str = "https://guardian.example.com";
+    const CHALLENGE: &str = "Y2hhbGxlbmdlLWNoYWxsZW5nZS1jaGFsbGVuZ2UtMDE";
+
+    const P256_SPKI_PREFIX: &[u8] = &[
+        0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08,
+        0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+    ];
+    const ED25519_SPKI_PREFIX: &[u8] = &[
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+    ];
+
+    fn config() -> WebAuthnConfig {
+        WebAuthnConfig {
+            rp_id: "guardian.example.com".to_string(),
+            origins: vec![ORIGIN.to_string()],
+            ..WebAuthnConfig::default()
+        }
+    }
+
+    fn client_data(kind: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        serde_json::json!({ "type": kind, "challenge": challenge, "origin": origin })
+            .to_string()
+            .into_bytes()
+    }
+
+    fn authenticator_data(
+        rp_id: &str,
+        flags: u8,
+        sign_count: u32,
+        credential_id: Option<&[u8]>,
+    ) -> Vec<u8> {
+        let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        if let Some(id) = credential_id {
+            data.extend_from_slice(&[0u8; 16]);
+            data.extend_from_slice(&(id.len() as u16).to_be_bytes());
+            data.extend_from_slice(id);
+        }
+        data
+    }
+
+    fn p256_key() -> (EcdsaKeyPair, Vec<u8>) {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+            .unwrap();
+        let spki = [P256_SPKI_PREFIX, key.public_key().as_ref()].concat();
+        (key, spki)
+    }
+
+    fn signed_message(auth_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        [auth_data, &Sha256::digest(client_data_json)[..]].concat()
+    }
+
+    #[test]
+    fn test_verify_registration() {
+        let config = config();
+        let (_, spki) = p256_key();
+        let client_data_json = client_data("webauthn.create", CHALLENGE, ORIGIN);
+        let auth_data = authenticator_data(
+            &config.rp_id,
+            FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL,
+            0,
+            Some(b"credential-1"),
+        );
+
+        let credential = verify_registration(
+            &config,
+            CHALLENGE,
+            &client_data_json,
+            &auth_data,
+            &spki,
+            ALG_ES256,
+        )
+        .unwrap();
+        assert_eq!(credential.credential_id, encode_base64url(b"credential-1"));
+        assert_eq!(credential.public_key, spki[P256_SPKI_PREFIX.len()..]);
+
+        let register = |client_data_json: &[u8], auth_data: &[u8], algorithm| {
+            verify_registration(
+                &config,
+                CHALLENGE,
+                client_data_json,
+                auth_data,
+                &spki,
+                algorithm,
+            )
+            .err()
+        };
+        assert_eq!(
+            register(
+                &client_data("webauthn.get", CHALLENGE, ORIGIN),
+                &auth_data,
+                ALG_ES256
+            ),
+            Some(WebAuthnError::TypeMismatch("webauthn.create"))
+        );
+        assert_eq!(
+            register(
+                &client_data("webauthn.create", "other", ORIGIN),
+                &auth_data,
+                ALG_ES256
+            ),
+            Some(WebAuthnError::ChallengeMismatch)
+        );
+        assert_eq!(
+            register(
+                &client_data("webauthn.create", CHALLENGE, "https://evil.example.com"),
+                &auth_data,
+                ALG_ES256
+            ),
+            Some(WebAuthnError::OriginNotAllowed(
+                "https://evil.example.com".to_string()
+            ))
+        );
+        assert_eq!(
+            register(
+                &client_data_json,
+                &authenticator_data("evil.example.com", 0x41, 0, Some(b"id")),
+                ALG_ES256
+            ),
+            Some(WebAuthnError::RpIdMismatch)
+        );
+        assert_eq!(
+            register(
+                &client_data_json,
+                &authenticator_data(&config.rp_id, FLAG_ATTESTED_CREDENTIAL, 0, Some(b"id")),
+                ALG_ES256
+            ),
+            Some(WebAuthnError::UserNotPresent)
+        );
+        // 公钥类型与声明的算法不一致
+        assert!(register(&client_data_json, &auth_data, ALG_EDDSA).is_some());
+    }
+
+    #[test]
+    fn test_verify_assertion_es256() {
+        let config = config();
+        let rng = SystemRandom::new();
+        let (key, spki) = p256_key();
+        let public_key = parse_public_key(&spki, ALG_ES256).unwrap();
+
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let auth_data = authenticator_data(&config.rp_id, FLAG_USER_PRESENT, 5, None);
+        let signature = key
+            .sign(&rng, &signed_message(&auth_data, &client_data_json))
+            .unwrap();
+
+        let assert = |stored_sign_count, signature: &[u8]| {
+            verify_assertion(
+                &config,
+                CHALLENGE,
+                &public_key,
+                ALG_ES256,
+                stored_sign_count,
+                &Assertion {
+                    client_data_json: &client_data_json,
+                    authenticator_data: &auth_data,
+                    signature,
+                },
+            )
+        };
+        assert_eq!(assert(4, signature.as_ref()), Ok(5));
+        assert_eq!(
+            assert(5, signature.as_ref()),
+            Err(WebAuthnError::CounterRegressed)
+        );
+
+        let mut tampered = signature.as_ref().to_vec();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert_eq!(assert(0, &tampered), Err(WebAuthnError::BadSignature));
+    }
+
+    #[test]
+    fn test_verify_assertion_ed25519() {
+        let config = config();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let spki = [ED25519_SPKI_PREFIX, key.public_key().as_ref()].concat();
+        let public_key = parse_public_key(&spki, ALG_EDDSA).unwrap();
+
+        // 不支持计数的认证器始终返回 0
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let auth_data = authenticator_data(&config.rp_id, FLAG_USER_PRESENT, 0, None);
+        let signature = key.sign(&signed_message(&auth_data, &client_data_json));
+
+        let sign_count = verify_assertion(
+            &config,
+            CHALLENGE,
+            &public_key,
+            ALG_EDDSA,
+            0,
+            &Assertion {
+                client_data_json: &client_data_json,
+                authenticator_data: &auth_data,
+                signature: signature.as_ref(),
+            },
+        );
+        assert_eq!(sign_count, Ok(0));
+    }
+
+    #[test]
+    fn test_parse_public_key() {
+        let (_, spki) = p256_key();
+        assert!(parse_public_key(&spki, ALG_ES256).is_ok());
+        assert!(parse_public_key(&spki[..spki.len() - 1], ALG_ES256).is_err());
+        assert!(parse_public_key(&[spki.as_slice(), &[0]].concat(), ALG_ES256).is_err());
+        assert_eq!(
+            parse_public_key(&spki, -35),
+            Err(WebAuthnError::UnsupportedAlgorithm(-35))
+        );
+    }
+
+    #[test]
+    fn test_parse_authenticator_data() {
+        assert!(parse_authenticator_data(&[0u8; 36]).is_err());
+
+        let data = authenticator_data("example.com", 0x41, 7, Some(b"abc"));
+        let parsed = parse_authenticator_data(&data).unwrap();
+        assert_eq!(parsed.sign_count, 7);
+        assert_eq!(parsed.credential_id.as_deref(), Some(&b"abc"[..]));
+
+        // 声明的凭证 ID 长度超出实际数据
+        let truncated = &data[..data.len() - 1];
+        assert!(parse_authenticator_data(truncated).is_err());
+    }
+}